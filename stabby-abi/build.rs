@@ -116,7 +116,7 @@ fn main() {
     tuples(max_tuple).unwrap();
     println!("cargo:rustc-check-cfg=cfg(stabby_nightly, values(none()))");
     println!(
-        r#"cargo:rustc-check-cfg=cfg(stabby_default_alloc, values("RustAlloc", "LibcAlloc", "disabled"))"#
+        r#"cargo:rustc-check-cfg=cfg(stabby_default_alloc, values("RustAlloc", "LibcAlloc", "MmapAlloc", "StaticAlloc", "disabled"))"#
     );
     println!(
         r#"cargo:rustc-check-cfg=cfg(stabby_check_unreachable, values(none(), "true", "false"))"#
@@ -125,6 +125,7 @@ fn main() {
     println!(
         r#"cargo:rustc-check-cfg=cfg(stabby_vtables, values(none(), "vec", "btree", "no_alloc"))"#
     );
+    println!("cargo:rustc-check-cfg=cfg(loom, values(none()))");
     if std::env::var("CARGO_CFG_STABBY_DEFAULT_ALLOC").is_err() {
         if std::env::var("CARGO_FEATURE_ALLOC_RS").is_ok() {
             println!(r#"cargo:rustc-cfg=stabby_default_alloc="RustAlloc""#);
@@ -0,0 +1,209 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::ptr::NonNull;
+
+use allocator_api2::alloc::Allocator;
+
+use crate::alloc::{IAlloc, Layout};
+
+/// An adapter to use any [`allocator_api2::alloc::Allocator`] as an [`IAlloc`].
+///
+/// Since [`IAlloc::free`] doesn't provide the allocation's layout, each allocation is preceded by a header
+/// storing it.
+///
+/// Note that the wrapped allocator is stored by value, and will be moved into the stabby containers using it:
+/// allocators that must be shared between containers should be wrapped by reference.
+#[crate::stabby]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiAlloc<A> {
+    inner: A,
+}
+impl<A> ApiAlloc<A> {
+    /// Wraps `inner` to be used as an [`IAlloc`].
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+    /// Returns a reference to the wrapped allocator.
+    pub const fn inner(&self) -> &A {
+        &self.inner
+    }
+    /// Returns the wrapped allocator.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+    /// Returns the offset of the requested allocation within the actual allocation, and the actual allocation's layout.
+    fn actual_layout(layout: Layout) -> Option<(usize, core::alloc::Layout)> {
+        let offset = layout.align.max(core::mem::size_of::<Layout>());
+        let actual = Layout {
+            size: offset.checked_add(layout.size)?,
+            align: layout.align.max(core::mem::align_of::<Layout>()),
+        };
+        Some((offset, actual.try_into().ok()?))
+    }
+    /// # Safety
+    /// `ptr` must have been allocated by an [`ApiAlloc`].
+    const unsafe fn header(ptr: *mut ()) -> *mut Layout {
+        // SAFETY: the header is stored right before the allocation.
+        unsafe { ptr.cast::<Layout>().sub(1) }
+    }
+    /// Returns the start of the actual allocation, and its layout.
+    ///
+    /// # Safety
+    /// `ptr` must have been allocated by an [`ApiAlloc`].
+    unsafe fn actual_allocation(ptr: *mut ()) -> (NonNull<u8>, core::alloc::Layout) {
+        // SAFETY: `ptr` is preceded by its header, which stores a valid layout.
+        unsafe {
+            let actual = Self::header(ptr).read();
+            let offset = actual.align.max(core::mem::size_of::<Layout>());
+            (
+                NonNull::new_unchecked(ptr.cast::<u8>().sub(offset)),
+                core::alloc::Layout::from_size_align_unchecked(actual.size, actual.align),
+            )
+        }
+    }
+    /// Writes the header of an allocation, returning the pointer to the requested allocation.
+    ///
+    /// # Safety
+    /// `start` must be an allocation of `actual` bytes, and `offset` must have been computed by [`Self::actual_layout`].
+    unsafe fn init(start: NonNull<[u8]>, offset: usize, actual: core::alloc::Layout) -> *mut () {
+        // SAFETY: `offset` leaves room for the header at the start of the actual allocation.
+        unsafe {
+            let ptr = start.cast::<u8>().as_ptr().add(offset).cast::<()>();
+            Self::header(ptr).write(actual.into());
+            ptr
+        }
+    }
+}
+impl<A: Allocator + Unpin> IAlloc for ApiAlloc<A> {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        if layout.size == 0 {
+            return core::ptr::null_mut();
+        }
+        let Some((offset, actual)) = Self::actual_layout(layout) else {
+            return core::ptr::null_mut();
+        };
+        match self.inner.allocate(actual) {
+            // SAFETY: `start` was just allocated with `actual`.
+            Ok(start) => unsafe { Self::init(start, offset, actual) },
+            Err(_) => core::ptr::null_mut(),
+        }
+    }
+    fn alloc_zeroed(&mut self, layout: Layout) -> *mut () {
+        if layout.size == 0 {
+            return core::ptr::null_mut();
+        }
+        let Some((offset, actual)) = Self::actual_layout(layout) else {
+            return core::ptr::null_mut();
+        };
+        match self.inner.allocate_zeroed(actual) {
+            // SAFETY: `start` was just allocated with `actual`.
+            Ok(start) => unsafe { Self::init(start, offset, actual) },
+            Err(_) => core::ptr::null_mut(),
+        }
+    }
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        // SAFETY: `ptr` was allocated by `self`, so its header describes the actual allocation.
+        unsafe {
+            let (start, actual) = Self::actual_allocation(ptr);
+            self.inner.deallocate(start, actual)
+        }
+    }
+    unsafe fn realloc(&mut self, ptr: *mut (), prev_layout: Layout, new_size: usize) -> *mut () {
+        if new_size == 0 {
+            return core::ptr::null_mut();
+        }
+        let Some((offset, new_actual)) = Self::actual_layout(Layout {
+            size: new_size,
+            align: prev_layout.align,
+        }) else {
+            return core::ptr::null_mut();
+        };
+        // SAFETY: `ptr` was allocated by `self`, so its header describes the actual allocation,
+        // which `grow` and `shrink` leave untouched if they fail.
+        unsafe {
+            let (start, actual) = Self::actual_allocation(ptr);
+            let new = if new_actual.size() >= actual.size() {
+                self.inner.grow(start, actual, new_actual)
+            } else {
+                self.inner.shrink(start, actual, new_actual)
+            };
+            match new {
+                Ok(start) => Self::init(start, offset, new_actual),
+                Err(_) => core::ptr::null_mut(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn api_alloc() {
+    use crate::alloc::{sync::Arc, vec::Vec};
+    use allocator_api2::alloc::AllocError;
+    use core::sync::atomic::{AtomicIsize, Ordering};
+    static LIVE: AtomicIsize = AtomicIsize::new(0);
+    #[derive(Clone, Copy, Default)]
+    struct Counting;
+    // SAFETY: allocations are forwarded to the global allocator.
+    unsafe impl Allocator for Counting {
+        fn allocate(&self, layout: core::alloc::Layout) -> Result<NonNull<[u8]>, AllocError> {
+            // SAFETY: `ApiAlloc` never requests zero-sized allocations.
+            let ptr = NonNull::new(unsafe { std::alloc::alloc(layout) }).ok_or(AllocError)?;
+            LIVE.fetch_add(1, Ordering::Relaxed);
+            Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+        }
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: core::alloc::Layout) {
+            LIVE.fetch_sub(1, Ordering::Relaxed);
+            // SAFETY: `ptr` was allocated by `allocate` with `layout`.
+            unsafe { std::alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+    }
+    {
+        let arc = Arc::new_in([1u64; 4], ApiAlloc::new(Counting));
+        let weak = Arc::downgrade(&arc);
+        assert_eq!(LIVE.load(Ordering::Relaxed), 1);
+        drop(arc);
+        assert!(weak.upgrade().is_none());
+    }
+    assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+    {
+        let mut vec = Vec::new_in(ApiAlloc::new(Counting));
+        for i in 0..1000u64 {
+            vec.push(i);
+        }
+        vec.truncate(10);
+        vec.shrink_to_fit();
+        assert!(vec.iter().copied().eq(0..10));
+        assert_eq!(LIVE.load(Ordering::Relaxed), 1);
+    }
+    assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+    let mut alloc = ApiAlloc::new(Counting);
+    let layout = Layout {
+        size: 100,
+        align: 64,
+    };
+    let ptr = alloc.alloc_zeroed(layout);
+    assert_eq!(ptr as usize % 64, 0);
+    // SAFETY: `ptr` was just allocated with `layout`.
+    unsafe {
+        assert!(core::slice::from_raw_parts(ptr.cast::<u8>(), layout.size)
+            .iter()
+            .all(|&x| x == 0));
+        let ptr = alloc.realloc(ptr, layout, 1000);
+        assert_eq!(ptr as usize % 64, 0);
+        IAlloc::free(&mut alloc, ptr);
+    }
+    assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+}
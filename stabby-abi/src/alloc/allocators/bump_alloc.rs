@@ -0,0 +1,178 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::{marker::PhantomData, ptr::NonNull};
+
+use crate::alloc::{IAlloc, Layout, TryAllocError};
+
+/// A bump allocator over a caller-provided buffer, which doesn't depend on any global allocator.
+///
+/// Allocating simply moves a cursor forward in the buffer, and freeing is a no-op: the memory is only
+/// reclaimed once the buffer itself is released. Reallocating the latest allocation is done in place
+/// whenever the buffer has enough room left.
+///
+/// # Lifetimes
+/// `stabby`'s containers store their allocator alongside their data (reference-counted ones even store it
+/// inside their allocation's prefix), so a [`BumpAlloc`] is moved into the container that uses it, and
+/// keeps the buffer borrowed for as long as that container lives. Since handing out the same memory twice
+/// would be unsound, [`BumpAlloc`] isn't [`Clone`]: to place several containers in a single buffer, split
+/// it (using [`slice::split_at_mut`] for example), and give each container its own [`BumpAlloc`].
+#[crate::stabby]
+pub struct BumpAlloc<'a> {
+    start: NonNull<u8>,
+    len: usize,
+    cursor: usize,
+    marker: PhantomData<&'a mut u8>,
+}
+// SAFETY: `BumpAlloc` behaves like the `&mut [u8]` it was constructed from.
+unsafe impl Send for BumpAlloc<'_> {}
+// SAFETY: `BumpAlloc` behaves like the `&mut [u8]` it was constructed from.
+unsafe impl Sync for BumpAlloc<'_> {}
+impl core::fmt::Debug for BumpAlloc<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BumpAlloc")
+            .field("len", &self.len)
+            .field("used", &self.cursor)
+            .finish()
+    }
+}
+impl<'a> BumpAlloc<'a> {
+    /// Constructs an allocator that will hand out memory from `buffer`.
+    #[rustversion::attr(since(1.83), const)]
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self {
+            // SAFETY: slices are never null.
+            start: unsafe { NonNull::new_unchecked(buffer.as_mut_ptr()) },
+            len: buffer.len(),
+            cursor: 0,
+            marker: PhantomData,
+        }
+    }
+    /// The number of bytes of the buffer that have been handed out, including alignment padding.
+    pub const fn used(&self) -> usize {
+        self.cursor
+    }
+    /// The number of bytes of the buffer that haven't been handed out yet.
+    pub const fn remaining(&self) -> usize {
+        self.len - self.cursor
+    }
+    /// Makes the whole buffer available for allocation again.
+    ///
+    /// # Safety
+    /// No allocation made by `self` may be used after this is called.
+    #[rustversion::attr(since(1.83), const)]
+    pub unsafe fn reset(&mut self) {
+        self.cursor = 0;
+    }
+    fn offset_of(&self, ptr: *mut ()) -> usize {
+        ptr as usize - self.start.as_ptr() as usize
+    }
+}
+impl IAlloc for BumpAlloc<'_> {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        self.try_alloc(layout)
+            .map_or(core::ptr::null_mut(), NonNull::as_ptr)
+    }
+    fn try_alloc(&mut self, layout: Layout) -> Result<NonNull<()>, TryAllocError> {
+        if !layout.align.is_power_of_two() {
+            return Err(TryAllocError::UnsupportedAlignment);
+        }
+        if layout.size == 0 {
+            return Err(TryAllocError::OutOfMemory);
+        }
+        let tip = self.start.as_ptr() as usize + self.cursor;
+        let padding = tip.wrapping_neg() & (layout.align - 1);
+        match padding.checked_add(layout.size) {
+            Some(needed) if needed <= self.remaining() => {
+                // SAFETY: `cursor + padding` is in bounds of the buffer, as checked above.
+                let ptr = unsafe { self.start.as_ptr().add(self.cursor + padding) };
+                self.cursor += needed;
+                // SAFETY: `ptr` was derived from a non-null pointer without wrapping.
+                Ok(unsafe { NonNull::new_unchecked(ptr.cast()) })
+            }
+            _ => Err(TryAllocError::OutOfMemory),
+        }
+    }
+    unsafe fn free(&mut self, _ptr: *mut ()) {}
+    unsafe fn realloc(&mut self, ptr: *mut (), prev_layout: Layout, new_size: usize) -> *mut () {
+        if new_size == 0 {
+            return core::ptr::null_mut();
+        }
+        let offset = self.offset_of(ptr);
+        if offset + prev_layout.size == self.cursor {
+            // `ptr` is the latest allocation: resize it in place if possible.
+            return if offset + new_size <= self.len {
+                self.cursor = offset + new_size;
+                ptr
+            } else {
+                core::ptr::null_mut()
+            };
+        }
+        let new = self.alloc(Layout {
+            size: new_size,
+            align: prev_layout.align,
+        });
+        if !new.is_null() {
+            // SAFETY: both allocations are valid for the copied size, and distinct since `new` was just handed out.
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    ptr.cast::<u8>(),
+                    new.cast::<u8>(),
+                    prev_layout.size.min(new_size),
+                )
+            }
+        }
+        new
+    }
+    unsafe fn shrink(&mut self, ptr: *mut (), old_layout: Layout, new_layout: Layout) -> *mut () {
+        if new_layout.size == 0 {
+            return core::ptr::null_mut();
+        }
+        let offset = self.offset_of(ptr);
+        if offset + old_layout.size == self.cursor {
+            self.cursor = offset + new_layout.size;
+        }
+        ptr
+    }
+}
+
+#[test]
+fn bump_alloc() {
+    use crate::alloc::{sync::Arc, vec::Vec};
+    let mut buffer = [0u8; 1024];
+    let (arc_buffer, vec_buffer) = buffer.split_at_mut(256);
+
+    let arc = Arc::new_in(42u64, BumpAlloc::new(arc_buffer));
+    let weak = Arc::downgrade(&arc);
+    assert_eq!(*weak.upgrade().unwrap(), 42);
+    drop(arc);
+    assert!(weak.upgrade().is_none());
+
+    let mut vec = Vec::new_in(BumpAlloc::new(vec_buffer));
+    vec.push(0u32);
+    let start = vec.as_ptr();
+    for i in 1..100 {
+        vec.push(i);
+    }
+    // As the vector's allocation is the latest one, it's grown in place.
+    assert_eq!(start, vec.as_ptr());
+    assert!(vec.iter().copied().eq(0..100));
+    let used = vec.allocator().used();
+    vec.truncate(10);
+    vec.shrink_to_fit();
+    assert!(vec.allocator().used() < used);
+    assert!(vec.iter().copied().eq(0..10));
+    assert!(vec.try_reserve(1000).is_err());
+    assert!(vec.iter().copied().eq(0..10));
+}
@@ -0,0 +1,126 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use super::RustAlloc;
+use crate::alloc::{sync::Arc, IAlloc, Layout};
+
+/// An allocator that wraps another, failing deterministically once a given number of allocations have been made.
+///
+/// Each call to `alloc` or `realloc` consumes one of the remaining allocations: once there are none left, they return
+/// null without calling the inner allocator. `free` is always forwarded. This makes it possible to exercise each of
+/// the fallible allocation paths of a container in turn.
+///
+/// As with [`TrackingAlloc`](super::TrackingAlloc), the countdown is shared by all clones of a [`FailAfter`], so that
+/// it may be changed after a clone has been handed to a container.
+/// ```
+/// # use stabby_abi::alloc::{allocators::{FailAfter, RustAlloc}, vec::Vec};
+/// let alloc = FailAfter::new(RustAlloc::default(), 1);
+/// let mut vec = Vec::<u8, _>::new_in(alloc.clone());
+/// assert!(vec.try_reserve(10).is_ok());
+/// assert!(vec.try_reserve(100).is_err());
+/// alloc.set_remaining(1);
+/// assert!(vec.try_reserve(100).is_ok());
+/// ```
+#[crate::stabby]
+pub struct FailAfter<Alloc: IAlloc = RustAlloc> {
+    inner: Alloc,
+    remaining: Arc<AtomicUsize, RustAlloc>,
+}
+impl<Alloc: IAlloc + Clone> Clone for FailAfter<Alloc> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            remaining: self.remaining.clone(),
+        }
+    }
+}
+impl<Alloc: IAlloc> core::fmt::Debug for FailAfter<Alloc> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FailAfter")
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+impl<Alloc: IAlloc> FailAfter<Alloc> {
+    /// Wraps `inner`, letting `remaining` allocations succeed before failing all following ones.
+    pub fn new(inner: Alloc, remaining: usize) -> Self {
+        Self {
+            inner,
+            remaining: Arc::new_in(AtomicUsize::new(remaining), RustAlloc::new()),
+        }
+    }
+    /// The number of allocations that may still succeed.
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::Relaxed)
+    }
+    /// Sets the number of allocations that may still succeed, for `self` and all of its clones.
+    pub fn set_remaining(&self, remaining: usize) {
+        self.remaining.store(remaining, Ordering::Relaxed)
+    }
+    /// Consumes one of the remaining allocations, returning `false` if there were none left.
+    fn tick(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+    }
+}
+impl<Alloc: IAlloc> IAlloc for FailAfter<Alloc> {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        if !self.tick() {
+            return core::ptr::null_mut();
+        }
+        self.inner.alloc(layout)
+    }
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        unsafe { self.inner.free(ptr) }
+    }
+    unsafe fn realloc(&mut self, ptr: *mut (), prev_layout: Layout, new_size: usize) -> *mut () {
+        if !self.tick() {
+            return core::ptr::null_mut();
+        }
+        unsafe { self.inner.realloc(ptr, prev_layout, new_size) }
+    }
+}
+
+#[test]
+fn fail_after() {
+    use crate::alloc::{
+        allocators::TrackingAlloc, boxed::Box, string::String, sync::Arc, vec::Vec,
+    };
+    let tracking = TrackingAlloc::<RustAlloc>::default();
+    let alloc = FailAfter::new(tracking.clone(), 0);
+    assert!(Arc::try_new_in(1u32, alloc.clone()).is_err());
+    assert!(Box::try_new_in(1u32, alloc.clone()).is_err());
+    assert_eq!(tracking.allocations(), 0);
+    // Fail each of the allocations a growing vector makes in turn.
+    for n in 0..8 {
+        alloc.set_remaining(n);
+        let mut vec = Vec::new_in(alloc.clone());
+        let mut pushed = 0;
+        while vec.try_push(pushed).is_ok() {
+            pushed += 1;
+        }
+        assert!(vec.iter().copied().eq(0..pushed));
+        assert_eq!(alloc.remaining(), 0);
+    }
+    alloc.set_remaining(1);
+    let mut string = String::new_in(alloc.clone());
+    assert!(string.try_concat("hello").is_ok());
+    assert!(string.try_concat(", world").is_err());
+    assert_eq!(string.as_str(), "hello");
+    drop(string);
+    assert_eq!((tracking.live_allocations(), tracking.live_bytes()), (0, 0));
+}
@@ -0,0 +1,280 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::ffi::c_void;
+
+use crate::alloc::{IAlloc, Layout};
+
+/// The set of `malloc`-like functions a [`ForeignMalloc`] routes its allocations through.
+///
+/// The implementor's name is part of [`ForeignMalloc`]'s report, ensuring that containers built with different
+/// allocators aren't mistaken for one another across the FFI boundary.
+pub trait IForeignMalloc: crate::IStable + Unpin {
+    /// Allocates `size` bytes, aligned to at least [`Self::MALLOC_ALIGNMENT`].
+    const MALLOC: unsafe extern "C" fn(usize) -> *mut c_void;
+    /// Frees an allocation obtained through [`Self::MALLOC`], [`Self::REALLOC`] or [`Self::ALIGNED_ALLOC`].
+    const FREE: unsafe extern "C" fn(*mut c_void);
+    /// Resizes an allocation obtained through [`Self::MALLOC`] or [`Self::REALLOC`].
+    const REALLOC: unsafe extern "C" fn(*mut c_void, usize) -> *mut c_void;
+    /// Allocates `size` bytes aligned to `align`, with `aligned_alloc`'s signature.
+    ///
+    /// When `None`, allocations are over-allocated through [`Self::MALLOC`] to make room for alignment
+    /// and a header pointing to the start of the actual allocation.
+    const ALIGNED_ALLOC: Option<unsafe extern "C" fn(usize, usize) -> *mut c_void>;
+    /// The alignment guaranteed by [`Self::MALLOC`].
+    const MALLOC_ALIGNMENT: usize = 2 * core::mem::size_of::<usize>();
+}
+
+/// An allocator routing allocations through the `malloc`-like functions provided by `Fns`.
+///
+/// The `mimalloc` and `jemalloc` features provide `Mimalloc` and `Jemalloc` respectively.
+///
+/// Alignments greater than `Fns::MALLOC_ALIGNMENT` are honored through `Fns::ALIGNED_ALLOC`, or by
+/// over-allocating if it's unavailable.
+#[crate::stabby]
+#[derive(Clone, Copy, Default)]
+pub struct ForeignMalloc<Fns: IForeignMalloc> {
+    fns: Fns,
+}
+impl<Fns: IForeignMalloc> core::fmt::Debug for ForeignMalloc<Fns> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ForeignMalloc<")?;
+        f.write_str(core::any::type_name::<Fns>())?;
+        f.write_str(">")
+    }
+}
+impl<Fns: IForeignMalloc + Default> ForeignMalloc<Fns> {
+    /// Constructs the allocator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+impl<Fns: IForeignMalloc> ForeignMalloc<Fns> {
+    /// The size of the header prepended to allocations when `Fns::ALIGNED_ALLOC` is unavailable.
+    const HEADER: usize = core::mem::size_of::<*mut c_void>();
+    /// Returns the pointer to the header storing the actual allocation's start.
+    ///
+    /// # Safety
+    /// `ptr` must have been allocated through the over-allocating strategy.
+    const unsafe fn header(ptr: *mut ()) -> *mut *mut c_void {
+        // SAFETY: the header is stored right before `ptr`, in the same allocation.
+        unsafe { ptr.cast::<*mut c_void>().sub(1) }
+    }
+    /// Allocates through `Fns::MALLOC`, leaving room for alignment and the header.
+    fn alloc_with_header(layout: Layout) -> *mut () {
+        let Some(total) = layout
+            .size
+            .checked_add(layout.align + Fns::MALLOC_ALIGNMENT)
+        else {
+            return core::ptr::null_mut();
+        };
+        // SAFETY: `MALLOC` is always safe to call.
+        let base = unsafe { (Fns::MALLOC)(total) };
+        if base.is_null() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: `ptr` is within the `total` bytes allocated, leaving room for both the header and `layout.size` bytes.
+        unsafe {
+            let ptr = Layout {
+                size: 0,
+                align: layout.align.max(Self::HEADER),
+            }
+            .next_matching(base.cast::<u8>().add(Self::HEADER))
+            .cast::<()>();
+            Self::header(ptr).write(base);
+            ptr
+        }
+    }
+}
+impl<Fns: IForeignMalloc> IAlloc for ForeignMalloc<Fns> {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        if layout.size == 0 || !layout.align.is_power_of_two() {
+            return core::ptr::null_mut();
+        }
+        match Fns::ALIGNED_ALLOC {
+            None => Self::alloc_with_header(layout),
+            // SAFETY: `MALLOC` is always safe to call.
+            Some(_) if layout.align <= Fns::MALLOC_ALIGNMENT => unsafe {
+                (Fns::MALLOC)(layout.size).cast()
+            },
+            Some(aligned_alloc) => {
+                // `aligned_alloc` may require the size to be a multiple of the alignment.
+                let Some(size) = layout.size.checked_add(layout.align - 1) else {
+                    return core::ptr::null_mut();
+                };
+                let size = size & !(layout.align - 1);
+                // SAFETY: `ALIGNED_ALLOC` is always safe to call.
+                unsafe { aligned_alloc(layout.align, size).cast() }
+            }
+        }
+    }
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        let ptr = match Fns::ALIGNED_ALLOC {
+            // SAFETY: `ptr` was allocated by `self`, so it's preceded by its header.
+            None => unsafe { Self::header(ptr).read() },
+            Some(_) => ptr.cast(),
+        };
+        // SAFETY: `ptr` was allocated by `Fns`.
+        unsafe { (Fns::FREE)(ptr) }
+    }
+    unsafe fn realloc(&mut self, ptr: *mut (), prev_layout: Layout, new_size: usize) -> *mut () {
+        if new_size == 0 {
+            return core::ptr::null_mut();
+        }
+        if prev_layout.align <= Fns::MALLOC_ALIGNMENT {
+            match Fns::ALIGNED_ALLOC {
+                Some(_) => {
+                    // SAFETY: `ptr` was allocated through `MALLOC`, as its alignment didn't require `ALIGNED_ALLOC`.
+                    return unsafe { (Fns::REALLOC)(ptr.cast(), new_size) }.cast();
+                }
+                None => {
+                    // Since `MALLOC`'s results are always aligned to `MALLOC_ALIGNMENT`, the header's size is preserved by `REALLOC`.
+                    // SAFETY: `ptr` was allocated by `self`, so it's preceded by its header.
+                    unsafe {
+                        let base = Self::header(ptr).read();
+                        let offset = ptr as usize - base as usize;
+                        let Some(total) = new_size.checked_add(offset) else {
+                            return core::ptr::null_mut();
+                        };
+                        let base = (Fns::REALLOC)(base, total);
+                        if base.is_null() {
+                            return core::ptr::null_mut();
+                        }
+                        let ptr = base.cast::<u8>().add(offset).cast::<()>();
+                        Self::header(ptr).write(base);
+                        return ptr;
+                    }
+                }
+            }
+        }
+        let new = self.alloc(Layout {
+            size: new_size,
+            align: prev_layout.align,
+        });
+        if !new.is_null() {
+            // SAFETY: both allocations are valid for the copied size, and `ptr` is no longer used after being freed.
+            unsafe {
+                core::ptr::copy_nonoverlapping(
+                    ptr.cast::<u8>(),
+                    new.cast::<u8>(),
+                    prev_layout.size.min(new_size),
+                );
+                self.free(ptr);
+            }
+        }
+        new
+    }
+}
+
+#[cfg(feature = "mimalloc")]
+mod mimalloc {
+    use core::ffi::c_void;
+    /// `mi_aligned_alloc` is only exported by `libmimalloc-sys`'s `extended` feature.
+    unsafe extern "C" fn mi_aligned_alloc(align: usize, size: usize) -> *mut c_void {
+        // SAFETY: `mi_malloc_aligned` is always safe to call.
+        unsafe { libmimalloc_sys::mi_malloc_aligned(size, align) }
+    }
+    /// The functions exported by [mimalloc](https://github.com/microsoft/mimalloc), linked through `libmimalloc-sys`.
+    #[crate::stabby]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Mimalloc;
+    impl super::IForeignMalloc for Mimalloc {
+        const MALLOC: unsafe extern "C" fn(usize) -> *mut c_void = libmimalloc_sys::mi_malloc;
+        const FREE: unsafe extern "C" fn(*mut c_void) = libmimalloc_sys::mi_free;
+        const REALLOC: unsafe extern "C" fn(*mut c_void, usize) -> *mut c_void =
+            libmimalloc_sys::mi_realloc;
+        const ALIGNED_ALLOC: Option<unsafe extern "C" fn(usize, usize) -> *mut c_void> =
+            Some(mi_aligned_alloc);
+    }
+}
+#[cfg(feature = "mimalloc")]
+pub use mimalloc::Mimalloc;
+
+#[cfg(feature = "jemalloc")]
+mod jemalloc {
+    use core::ffi::c_void;
+    /// The functions exported by [jemalloc](https://github.com/jemalloc/jemalloc), linked through `tikv-jemalloc-sys`.
+    #[crate::stabby]
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Jemalloc;
+    impl super::IForeignMalloc for Jemalloc {
+        const MALLOC: unsafe extern "C" fn(usize) -> *mut c_void = tikv_jemalloc_sys::malloc;
+        const FREE: unsafe extern "C" fn(*mut c_void) = tikv_jemalloc_sys::free;
+        const REALLOC: unsafe extern "C" fn(*mut c_void, usize) -> *mut c_void =
+            tikv_jemalloc_sys::realloc;
+        const ALIGNED_ALLOC: Option<unsafe extern "C" fn(usize, usize) -> *mut c_void> =
+            Some(tikv_jemalloc_sys::aligned_alloc);
+    }
+}
+#[cfg(feature = "jemalloc")]
+pub use jemalloc::Jemalloc;
+
+#[cfg(all(test, feature = "libc", unix))]
+mod tests {
+    use super::*;
+    use crate::alloc::{sync::Arc, vec::Vec};
+    #[crate::stabby]
+    #[derive(Clone, Copy, Default)]
+    struct Libc;
+    impl IForeignMalloc for Libc {
+        const MALLOC: unsafe extern "C" fn(usize) -> *mut c_void = libc::malloc;
+        const FREE: unsafe extern "C" fn(*mut c_void) = libc::free;
+        const REALLOC: unsafe extern "C" fn(*mut c_void, usize) -> *mut c_void = libc::realloc;
+        const ALIGNED_ALLOC: Option<unsafe extern "C" fn(usize, usize) -> *mut c_void> =
+            Some(libc::aligned_alloc);
+    }
+    #[crate::stabby]
+    #[derive(Clone, Copy, Default)]
+    struct LibcWithoutAlignedAlloc;
+    impl IForeignMalloc for LibcWithoutAlignedAlloc {
+        const MALLOC: unsafe extern "C" fn(usize) -> *mut c_void = libc::malloc;
+        const FREE: unsafe extern "C" fn(*mut c_void) = libc::free;
+        const REALLOC: unsafe extern "C" fn(*mut c_void, usize) -> *mut c_void = libc::realloc;
+        const ALIGNED_ALLOC: Option<unsafe extern "C" fn(usize, usize) -> *mut c_void> = None;
+    }
+    fn exercise<Fns: IForeignMalloc + Default>() {
+        let mut alloc = ForeignMalloc::<Fns>::new();
+        for align in [1, 8, 16, 64, 256, 4096] {
+            let layout = Layout { size: 24, align };
+            let ptr = alloc.alloc(layout);
+            assert_eq!(ptr as usize % align, 0);
+            // SAFETY: `ptr` was just allocated with `layout`.
+            unsafe {
+                ptr.cast::<u8>().write_bytes(42, layout.size);
+                let ptr = alloc.realloc(ptr, layout, 4000);
+                assert_eq!(ptr as usize % align, 0);
+                assert!(core::slice::from_raw_parts(ptr.cast::<u8>(), layout.size)
+                    .iter()
+                    .all(|&x| x == 42));
+                IAlloc::free(&mut alloc, ptr);
+            }
+        }
+        let mut vec = Vec::new_in(ForeignMalloc::<Fns>::new());
+        for i in 0..1000u64 {
+            vec.push(i);
+        }
+        assert!(vec.iter().copied().eq(0..1000));
+        let arc = Arc::new_in(3u64, ForeignMalloc::<Fns>::new());
+        assert_eq!(*arc, 3);
+    }
+    #[test]
+    fn foreign_malloc() {
+        exercise::<Libc>();
+        exercise::<LibcWithoutAlignedAlloc>();
+        #[cfg(feature = "mimalloc")]
+        exercise::<Mimalloc>();
+        #[cfg(feature = "jemalloc")]
+        exercise::<Jemalloc>();
+    }
+}
@@ -12,7 +12,7 @@
 //   Pierre Avital, <pierre.avital@me.com>
 //
 
-use crate::alloc::Layout;
+use crate::alloc::{Layout, TryAllocError};
 
 #[cfg(not(windows))]
 use libc::posix_memalign;
@@ -30,7 +30,7 @@ unsafe fn posix_memalign(this: &mut *mut core::ffi::c_void, size: usize, align:
 use libc::aligned_free;
 #[cfg(not(windows))]
 use libc::free as aligned_free;
-use libc::realloc;
+use libc::{calloc, realloc};
 
 /// An allocator based on `libc::posix_memalign` or `libc::aligned_malloc` depending on the platform.
 ///
@@ -65,6 +65,33 @@ impl crate::alloc::IAlloc for LibcAlloc {
         }
         ptr.cast()
     }
+    fn try_alloc(&mut self, layout: Layout) -> Result<core::ptr::NonNull<()>, TryAllocError> {
+        if layout.size == 0 {
+            return Err(TryAllocError::OutOfMemory);
+        }
+        let mut ptr = core::ptr::null_mut();
+        // SAFETY: `posix_memalign` is always safe.
+        match unsafe { posix_memalign(&mut ptr, layout.align, layout.size) } {
+            0 => core::ptr::NonNull::new(ptr.cast()).ok_or(TryAllocError::OutOfMemory),
+            libc::EINVAL => Err(TryAllocError::UnsupportedAlignment),
+            _ => Err(TryAllocError::OutOfMemory),
+        }
+    }
+    fn alloc_zeroed(&mut self, layout: Layout) -> *mut () {
+        if layout.size == 0 {
+            return core::ptr::null_mut();
+        }
+        if layout.align <= 8 {
+            // SAFETY: `calloc` is always safe, and its result is aligned for any fundamental type.
+            return unsafe { calloc(1, layout.size) }.cast();
+        }
+        let ptr = self.alloc(layout);
+        if !ptr.is_null() {
+            // SAFETY: `alloc` returned an allocation of at least `layout.size` bytes.
+            unsafe { core::ptr::write_bytes(ptr.cast::<u8>(), 0, layout.size) }
+        }
+        ptr
+    }
     unsafe fn free(&mut self, ptr: *mut ()) {
         // SAFETY: `aligned_free` must be called by a pointer allocated by the corresponding allocator, which is already a safety condition of `IAlloc::free`
         unsafe { aligned_free(ptr.cast()) }
@@ -86,7 +113,7 @@ impl crate::alloc::IAlloc for LibcAlloc {
                     core::ptr::copy_nonoverlapping(
                         ptr.cast::<u8>(),
                         new_ptr.cast::<u8>(),
-                        prev.size,
+                        prev.size.min(new_size),
                     )
                 }
                 self.free(ptr.cast());
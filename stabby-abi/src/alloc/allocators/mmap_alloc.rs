@@ -0,0 +1,264 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::ptr::NonNull;
+
+use crate::alloc::{IAlloc, Layout, TryAllocError};
+
+#[cfg(unix)]
+mod os {
+    pub fn page_size() -> usize {
+        // SAFETY: `sysconf` is always safe.
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+    }
+    pub unsafe fn map(len: usize) -> *mut u8 {
+        // SAFETY: anonymous private mappings don't alias any existing memory.
+        let ptr = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            core::ptr::null_mut()
+        } else {
+            ptr.cast()
+        }
+    }
+    pub unsafe fn unmap(ptr: *mut u8, len: usize) {
+        // SAFETY: `ptr` and `len` describe a mapping obtained through `map`.
+        unsafe { libc::munmap(ptr.cast(), len) };
+    }
+    #[cfg(target_os = "linux")]
+    pub unsafe fn remap(ptr: *mut u8, len: usize, new_len: usize) -> *mut u8 {
+        // SAFETY: `ptr` and `len` describe a mapping obtained through `map`.
+        let ptr = unsafe { libc::mremap(ptr.cast(), len, new_len, libc::MREMAP_MAYMOVE) };
+        if ptr == libc::MAP_FAILED {
+            core::ptr::null_mut()
+        } else {
+            ptr.cast()
+        }
+    }
+}
+#[cfg(windows)]
+mod os {
+    use core::ffi::c_void;
+    const MEM_COMMIT: u32 = 0x1000;
+    const MEM_RESERVE: u32 = 0x2000;
+    const MEM_RELEASE: u32 = 0x8000;
+    const PAGE_READWRITE: u32 = 0x04;
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn VirtualAlloc(address: *mut c_void, size: usize, ty: u32, protect: u32) -> *mut c_void;
+        fn VirtualFree(address: *mut c_void, size: usize, ty: u32) -> i32;
+    }
+    pub fn page_size() -> usize {
+        4096
+    }
+    pub unsafe fn map(len: usize) -> *mut u8 {
+        // SAFETY: `VirtualAlloc` is always safe when not passed an address.
+        unsafe {
+            VirtualAlloc(
+                core::ptr::null_mut(),
+                len,
+                MEM_COMMIT | MEM_RESERVE,
+                PAGE_READWRITE,
+            )
+        }
+        .cast()
+    }
+    pub unsafe fn unmap(ptr: *mut u8, _len: usize) {
+        // SAFETY: `ptr` is a mapping obtained through `map`.
+        unsafe { VirtualFree(ptr.cast(), 0, MEM_RELEASE) };
+    }
+}
+
+/// The header stored right before each allocation, keeping track of the mapping it belongs to.
+#[repr(C)]
+struct Header {
+    base: *mut u8,
+    len: usize,
+}
+const HEADER: Layout = Layout::of::<Header>();
+
+/// An allocator that maps memory straight from the OS, using `mmap` on unix and `VirtualAlloc` on windows.
+///
+/// Each allocation gets its own mapping, rounded up to page granularity, which is released back to the OS
+/// as soon as it's freed: this is well suited for huge allocations, but wasteful for small ones.
+/// On Linux, reallocation is done through `mremap`, avoiding copies entirely.
+///
+/// Alignments greater than the page size are not supported.
+#[crate::stabby]
+#[derive(Clone, Copy, Default)]
+pub struct MmapAlloc {
+    inner: [u8; 0],
+}
+impl core::fmt::Debug for MmapAlloc {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("MmapAlloc")
+    }
+}
+impl MmapAlloc {
+    /// Constructs the allocator.
+    pub const fn new() -> Self {
+        Self { inner: [] }
+    }
+    /// Returns the offset of the allocation within its mapping, and the length of said mapping.
+    fn mapping_for(layout: Layout) -> Option<(usize, usize)> {
+        let page = os::page_size();
+        let offset = layout.align.max(HEADER.size);
+        let len = offset.checked_add(layout.size)?.checked_add(page - 1)? & !(page - 1);
+        Some((offset, len))
+    }
+    /// # Safety
+    /// `ptr` must have been allocated by a [`MmapAlloc`].
+    const unsafe fn header(ptr: *mut ()) -> *mut Header {
+        // SAFETY: the header is stored right before the allocation, in the same mapping.
+        unsafe { ptr.cast::<Header>().sub(1) }
+    }
+}
+impl IAlloc for MmapAlloc {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        self.try_alloc(layout)
+            .map_or(core::ptr::null_mut(), NonNull::as_ptr)
+    }
+    fn try_alloc(&mut self, layout: Layout) -> Result<NonNull<()>, TryAllocError> {
+        if !layout.align.is_power_of_two() || layout.align > os::page_size() {
+            return Err(TryAllocError::UnsupportedAlignment);
+        }
+        if layout.size == 0 {
+            return Err(TryAllocError::OutOfMemory);
+        }
+        let (offset, len) = Self::mapping_for(layout).ok_or(TryAllocError::OutOfMemory)?;
+        // SAFETY: `len` is a non-zero multiple of the page size.
+        let base = unsafe { os::map(len) };
+        if base.is_null() {
+            return Err(TryAllocError::OutOfMemory);
+        }
+        // SAFETY: `offset` is at least the header's size, and less than `len`.
+        unsafe {
+            let ptr = base.add(offset).cast::<()>();
+            Self::header(ptr).write(Header { base, len });
+            Ok(NonNull::new_unchecked(ptr))
+        }
+    }
+    fn alloc_zeroed(&mut self, layout: Layout) -> *mut () {
+        // Fresh mappings are always zeroed.
+        self.alloc(layout)
+    }
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        // SAFETY: `ptr` was allocated by `self`, so it's preceded by its header.
+        unsafe {
+            let Header { base, len } = Self::header(ptr).read();
+            os::unmap(base, len)
+        }
+    }
+    unsafe fn realloc(&mut self, ptr: *mut (), prev_layout: Layout, new_size: usize) -> *mut () {
+        if new_size == 0 {
+            return core::ptr::null_mut();
+        }
+        let Some((offset, new_len)) = Self::mapping_for(Layout {
+            size: new_size,
+            align: prev_layout.align,
+        }) else {
+            return core::ptr::null_mut();
+        };
+        // SAFETY: `ptr` was allocated by `self`, so it's preceded by its header.
+        let Header { base, len } = unsafe { Self::header(ptr).read() };
+        if new_len == len {
+            return ptr;
+        }
+        #[cfg(target_os = "linux")]
+        {
+            // SAFETY: `base` and `len` describe `ptr`'s mapping, whose header is updated to match the new mapping.
+            unsafe {
+                let base = os::remap(base, len, new_len);
+                if base.is_null() {
+                    return core::ptr::null_mut();
+                }
+                let ptr = base.add(offset).cast::<()>();
+                Self::header(ptr).write(Header { base, len: new_len });
+                ptr
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = offset;
+            let new = self.alloc(Layout {
+                size: new_size,
+                align: prev_layout.align,
+            });
+            if !new.is_null() {
+                // SAFETY: both allocations are valid for the copied size, and `ptr` is no longer used after being freed.
+                unsafe {
+                    core::ptr::copy_nonoverlapping(
+                        ptr.cast::<u8>(),
+                        new.cast::<u8>(),
+                        prev_layout.size.min(new_size),
+                    );
+                    self.free(ptr);
+                }
+            }
+            new
+        }
+    }
+}
+
+#[test]
+fn mmap_alloc() {
+    use crate::alloc::{sync::Arc, vec::Vec};
+    let page = os::page_size();
+    let arc = Arc::new_in([1u8; 1 << 16], MmapAlloc::new());
+    assert!(arc.len() > page);
+    assert!(arc.iter().all(|&x| x == 1));
+    let weak = Arc::downgrade(&arc);
+    drop(arc);
+    assert!(weak.upgrade().is_none());
+    drop(weak);
+
+    let mut vec = Vec::new_in(MmapAlloc::new());
+    for i in 0..(1u32 << 20) {
+        vec.push(i);
+    }
+    assert!(vec.iter().copied().eq(0..(1 << 20)));
+    vec.truncate(10);
+    vec.shrink_to_fit();
+    assert!(vec.iter().copied().eq(0..10));
+
+    let mut alloc = MmapAlloc::new();
+    let layout = Layout {
+        size: 3 * page,
+        align: page,
+    };
+    let ptr = alloc.alloc_zeroed(layout);
+    assert_eq!(ptr as usize % page, 0);
+    // SAFETY: `ptr` was just allocated with `layout`.
+    unsafe {
+        assert!(core::slice::from_raw_parts(ptr.cast::<u8>(), layout.size)
+            .iter()
+            .all(|&x| x == 0));
+        IAlloc::free(&mut alloc, ptr);
+    }
+    assert_eq!(
+        alloc.try_alloc(Layout {
+            size: 1,
+            align: 2 * page
+        }),
+        Err(TryAllocError::UnsupportedAlignment)
+    );
+}
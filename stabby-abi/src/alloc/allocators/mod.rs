@@ -4,16 +4,59 @@ pub(crate) mod libc_alloc;
 #[cfg(all(feature = "libc", not(target_arch = "wasm32")))]
 pub use libc_alloc::LibcAlloc;
 
+#[cfg(all(feature = "mmap", any(unix, windows)))]
+/// [`IAlloc`](crate::alloc::IAlloc) bindings for `mmap` and `VirtualAlloc`
+mod mmap_alloc;
+#[cfg(all(feature = "mmap", any(unix, windows)))]
+pub use mmap_alloc::MmapAlloc;
+
+/// [`IAlloc`](crate::alloc::IAlloc) bindings for foreign `malloc` implementations.
+mod foreign_malloc;
+#[cfg(feature = "jemalloc")]
+pub use foreign_malloc::Jemalloc;
+#[cfg(feature = "mimalloc")]
+pub use foreign_malloc::Mimalloc;
+pub use foreign_malloc::{ForeignMalloc, IForeignMalloc};
+
+#[cfg(feature = "allocator-api2")]
+/// [`IAlloc`](crate::alloc::IAlloc) adapter for [`allocator_api2::alloc::Allocator`]s.
+mod api_alloc;
+#[cfg(feature = "allocator-api2")]
+pub use api_alloc::ApiAlloc;
+
+/// A bump allocator over a caller-provided buffer.
+mod bump_alloc;
+pub use bump_alloc::BumpAlloc;
+/// A pool allocator handing out fixed-size blocks.
+mod pool_alloc;
+pub use pool_alloc::PoolAlloc;
+/// A zero-sized handle to a global, spin-locked heap.
+mod static_alloc;
+pub use static_alloc::StaticAlloc;
+
 #[cfg(feature = "alloc-rs")]
 /// Rust's GlobalAlloc, accessed through a vtable to ensure no incompatible function calls are performed
 mod rust_alloc;
 #[cfg(feature = "alloc-rs")]
 pub use rust_alloc::RustAlloc;
 
+#[cfg(feature = "alloc-rs")]
+/// An allocator wrapper that keeps count of its allocations.
+mod tracking_alloc;
+#[cfg(feature = "alloc-rs")]
+pub use tracking_alloc::{TrackingAlloc, TrackingCounters};
+#[cfg(feature = "alloc-rs")]
+/// An allocator wrapper that fails on demand.
+mod fail_after;
+#[cfg(feature = "alloc-rs")]
+pub use fail_after::FailAfter;
+
 #[cfg(stabby_default_alloc = "RustAlloc")]
 /// The default allocator, depending on which of the following is available:
 /// - RustAlloc: Rust's `GlobalAlloc`, through a vtable that ensures FFI-safety.
 /// - LibcAlloc: libc::malloc, which is 0-sized.
+/// - MmapAlloc: maps each allocation straight from the OS, only used if selected through `stabby_default_alloc`.
+/// - StaticAlloc: a global heap built from the regions passed to `StaticAlloc::init`, only used if selected through `stabby_default_alloc`.
 /// - None. I _am_ working on getting a 0-dependy allocator working, but you should probably go with `feature = "alloc-rs"` anyway.
 ///
 /// You can also use the `stabby_default_alloc` cfg to override the default allocator regardless of feature flags.
@@ -23,15 +66,41 @@ pub(crate) type DefaultAllocator = RustAlloc;
 /// The default allocator, depending on which of the following is available:
 /// - RustAlloc: Rust's `GlobalAlloc`, through a vtable that ensures FFI-safety.
 /// - LibcAlloc: libc::malloc, which is 0-sized.
+/// - MmapAlloc: maps each allocation straight from the OS, only used if selected through `stabby_default_alloc`.
+/// - StaticAlloc: a global heap built from the regions passed to `StaticAlloc::init`, only used if selected through `stabby_default_alloc`.
 /// - None. I _am_ working on getting a 0-dependy allocator working, but you should probably go with `feature = "alloc-rs"` anyway.
 ///
 /// You can also use the `stabby_default_alloc` cfg to override the default allocator regardless of feature flags.
 pub(crate) type DefaultAllocator = LibcAlloc;
 
+#[cfg(stabby_default_alloc = "MmapAlloc")]
+/// The default allocator, depending on which of the following is available:
+/// - RustAlloc: Rust's `GlobalAlloc`, through a vtable that ensures FFI-safety.
+/// - LibcAlloc: libc::malloc, which is 0-sized.
+/// - MmapAlloc: maps each allocation straight from the OS, only used if selected through `stabby_default_alloc`.
+/// - StaticAlloc: a global heap built from the regions passed to `StaticAlloc::init`, only used if selected through `stabby_default_alloc`.
+/// - None. I _am_ working on getting a 0-dependy allocator working, but you should probably go with `feature = "alloc-rs"` anyway.
+///
+/// You can also use the `stabby_default_alloc` cfg to override the default allocator regardless of feature flags.
+pub(crate) type DefaultAllocator = MmapAlloc;
+
+#[cfg(stabby_default_alloc = "StaticAlloc")]
+/// The default allocator, depending on which of the following is available:
+/// - RustAlloc: Rust's `GlobalAlloc`, through a vtable that ensures FFI-safety.
+/// - LibcAlloc: libc::malloc, which is 0-sized.
+/// - MmapAlloc: maps each allocation straight from the OS, only used if selected through `stabby_default_alloc`.
+/// - StaticAlloc: a global heap built from the regions passed to `StaticAlloc::init`, only used if selected through `stabby_default_alloc`.
+/// - None. I _am_ working on getting a 0-dependy allocator working, but you should probably go with `feature = "alloc-rs"` anyway.
+///
+/// You can also use the `stabby_default_alloc` cfg to override the default allocator regardless of feature flags.
+pub(crate) type DefaultAllocator = StaticAlloc;
+
 #[cfg(stabby_default_alloc = "disabled")]
 /// The default allocator, depending on which of the following is available:
 /// - RustAlloc: Rust's `GlobalAlloc`, through a vtable that ensures FFI-safety.
 /// - LibcAlloc: libc::malloc, which is 0-sized.
+/// - MmapAlloc: maps each allocation straight from the OS, only used if selected through `stabby_default_alloc`.
+/// - StaticAlloc: a global heap built from the regions passed to `StaticAlloc::init`, only used if selected through `stabby_default_alloc`.
 /// - None. I _am_ working on getting a 0-dependy allocator working, but you should probably go with `feature = "alloc-rs"` anyway.
 ///
 /// You can also use the `stabby_default_alloc` cfg to override the default allocator regardless of feature flags.
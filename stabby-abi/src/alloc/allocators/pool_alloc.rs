@@ -0,0 +1,188 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::{
+    marker::PhantomData,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
+};
+
+use crate::alloc::{IAlloc, Layout, TryAllocError};
+
+/// A pool allocator, handing out fixed-size blocks carved from a caller-provided region.
+///
+/// Free blocks are kept in a free-list threaded through the region itself, making allocating and freeing
+/// constant-time. Requests that don't fit in a block fail, as do reallocations beyond the block's size.
+///
+/// The free-list is guarded by a spin-lock, making the pool [`Sync`]: [`IAlloc`] is implemented for `&PoolAlloc`,
+/// which lets any number of containers (on any number of threads) share a single pool.
+/// ```
+/// # use stabby_abi::alloc::{allocators::PoolAlloc, sync::Arc, Layout};
+/// let mut region = [0u8; 1024];
+/// let pool = PoolAlloc::new(&mut region, Layout::of::<[u64; 8]>());
+/// let a = Arc::new_in(1u64, &pool);
+/// let b = Arc::new_in(2u64, &pool);
+/// assert_eq!(*a + *b, 3);
+/// ```
+#[crate::stabby]
+pub struct PoolAlloc<'a> {
+    free: AtomicPtr<u8>,
+    locked: AtomicBool,
+    block: Layout,
+    capacity: usize,
+    marker: PhantomData<&'a mut u8>,
+}
+// SAFETY: the free-list is only accessed while holding the lock.
+unsafe impl Send for PoolAlloc<'_> {}
+// SAFETY: the free-list is only accessed while holding the lock.
+unsafe impl Sync for PoolAlloc<'_> {}
+impl core::fmt::Debug for PoolAlloc<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PoolAlloc")
+            .field("block", &self.block)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+impl<'a> PoolAlloc<'a> {
+    /// Constructs a pool that will hand out blocks of at least `block`'s size and alignment from `region`.
+    ///
+    /// Blocks are at least large and aligned enough to store a pointer, and the beginning of `region` is
+    /// skipped as needed to align the first block.
+    pub fn new(region: &'a mut [u8], block: Layout) -> Self {
+        let ptr_layout = Layout::of::<*mut u8>();
+        let block = Layout {
+            size: block.size.max(ptr_layout.size),
+            align: block.align.max(ptr_layout.align),
+        };
+        let block = block.realign(block.align);
+        let start = block.next_matching(region.as_mut_ptr());
+        let skipped = start as usize - region.as_mut_ptr() as usize;
+        let capacity = region.len().saturating_sub(skipped) / block.size;
+        let mut free = core::ptr::null_mut();
+        for i in (0..capacity).rev() {
+            // SAFETY: the `capacity` blocks starting at `start` are in bounds of `region`, and aligned for pointers.
+            unsafe {
+                let node = start.add(i * block.size);
+                node.cast::<*mut u8>().write(free);
+                free = node;
+            }
+        }
+        Self {
+            free: AtomicPtr::new(free),
+            locked: AtomicBool::new(false),
+            block,
+            capacity,
+            marker: PhantomData,
+        }
+    }
+    /// The layout of the blocks handed out by the pool.
+    pub const fn block_layout(&self) -> Layout {
+        self.block
+    }
+    /// The total number of blocks in the pool.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+    fn with_free_list<R>(&self, f: impl FnOnce(&mut *mut u8) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let mut free = self.free.load(Ordering::Relaxed);
+        let ret = f(&mut free);
+        self.free.store(free, Ordering::Relaxed);
+        self.locked.store(false, Ordering::Release);
+        ret
+    }
+}
+impl IAlloc for &PoolAlloc<'_> {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        self.try_alloc(layout)
+            .map_or(core::ptr::null_mut(), NonNull::as_ptr)
+    }
+    fn try_alloc(&mut self, layout: Layout) -> Result<NonNull<()>, TryAllocError> {
+        if !layout.align.is_power_of_two() || layout.align > self.block.align {
+            return Err(TryAllocError::UnsupportedAlignment);
+        }
+        if layout.size == 0 || layout.size > self.block.size {
+            return Err(TryAllocError::OutOfMemory);
+        }
+        self.with_free_list(|free| {
+            let block = NonNull::new(*free).ok_or(TryAllocError::OutOfMemory)?;
+            // SAFETY: blocks in the free-list store the pointer to the next free block.
+            *free = unsafe { block.as_ptr().cast::<*mut u8>().read() };
+            Ok(block.cast())
+        })
+    }
+    /// Returns the block to the pool.
+    ///
+    /// Note that since all `&PoolAlloc` pointing to the same pool are equivalent, `ptr` may have been allocated by any of them.
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        self.with_free_list(|free| {
+            // SAFETY: `ptr` is a block of the pool, which are large and aligned enough to store a pointer.
+            unsafe { ptr.cast::<*mut u8>().write(*free) };
+            *free = ptr.cast();
+        })
+    }
+    unsafe fn realloc(&mut self, ptr: *mut (), _prev_layout: Layout, new_size: usize) -> *mut () {
+        if new_size == 0 || new_size > self.block.size {
+            core::ptr::null_mut()
+        } else {
+            ptr
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn pool_alloc() {
+    use crate::alloc::{sync::Arc, vec::Vec};
+    let mut region = [0u8; 4096];
+    let pool = PoolAlloc::new(&mut region[1..], Layout::of::<[u64; 8]>());
+    assert_eq!(pool.block_layout(), Layout::of::<[u64; 8]>());
+    let capacity = pool.capacity();
+    assert_eq!(capacity, 4096 / 64 - 1);
+
+    let arcs = (0..capacity)
+        .map(|i| Arc::new_in(i, &pool))
+        .collect::<std::vec::Vec<_>>();
+    assert!(Arc::try_new_in(capacity, &pool).is_err());
+    assert!(arcs.iter().map(|arc| **arc).eq(0..capacity));
+    drop(arcs);
+
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let pool = &pool;
+            s.spawn(move || {
+                for i in 0..1000 {
+                    let arc = Arc::new_in((t, i), pool);
+                    let other = Arc::clone(&arc);
+                    assert_eq!(*other, (t, i));
+                }
+            });
+        }
+    });
+
+    // Vectors may grow in place, but no further than a block's size.
+    let mut vec = Vec::new_in(&pool);
+    vec.push(0u8);
+    let start = vec.as_ptr();
+    assert!(vec.try_reserve(8).is_ok());
+    assert_eq!(start, vec.as_ptr());
+    assert!(vec.try_reserve(64).is_err());
+}
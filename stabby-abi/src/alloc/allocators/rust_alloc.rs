@@ -20,14 +20,46 @@ pub struct RustAllocPrefix {
 }
 
 extern "C" fn alloc(requested: crate::alloc::Layout) -> *mut () {
-    let requested = Layout::of::<RustAllocPrefix>().concat(requested);
-    let Ok(layout) = core::alloc::Layout::from_size_align(requested.size, requested.align) else {
+    alloc_with(requested, alloc_rs::alloc::alloc)
+}
+extern "C" fn alloc_zeroed(requested: crate::alloc::Layout) -> *mut () {
+    alloc_with(requested, alloc_rs::alloc::alloc_zeroed)
+}
+/// Returns the offset between the start of the allocation and the pointer returned to the user,
+/// followed by the layout of an allocation that can fit both the prefix and `requested`.
+///
+/// The prefix's size being a power of 2, the offset is always a multiple of `requested.align`.
+fn layout_for(requested: Layout) -> Option<(usize, Layout)> {
+    let offset = requested.align.max(core::mem::size_of::<RustAllocPrefix>());
+    let layout = Layout {
+        size: offset.checked_add(requested.size)?,
+        align: requested
+            .align
+            .max(core::mem::align_of::<RustAllocPrefix>()),
+    };
+    core::alloc::Layout::try_from(layout)
+        .is_ok()
+        .then_some((offset, layout))
+}
+fn alloc_with(
+    requested: crate::alloc::Layout,
+    allocate: unsafe fn(core::alloc::Layout) -> *mut u8,
+) -> *mut () {
+    let Some((offset, requested)) = layout_for(requested) else {
         return core::ptr::null_mut();
     };
-    // SAFETY: The layout is always non-zero-sized
-    let alloc_start = unsafe { alloc_rs::alloc::alloc(layout) };
+    // SAFETY: The layout is always non-zero-sized, and valid as checked by `layout_for`.
+    let alloc_start = unsafe {
+        allocate(core::alloc::Layout::from_size_align_unchecked(
+            requested.size,
+            requested.align,
+        ))
+    };
+    if alloc_start.is_null() {
+        return core::ptr::null_mut();
+    }
     let ret = // SAFETY: the addition is indeed in-bound.
-        unsafe { alloc_start.add(layout.align().max(core::mem::size_of::<RustAllocPrefix>())) };
+        unsafe { alloc_start.add(offset) };
     // SAFETY: `ret` is allocated and _at least_ one `RustAllocPrefix` greater than the start of the allocation, so writing there is safe.
     unsafe {
         ret.cast::<RustAllocPrefix>().sub(1).write(RustAllocPrefix {
@@ -38,28 +70,25 @@ extern "C" fn alloc(requested: crate::alloc::Layout) -> *mut () {
     ret.cast()
 }
 extern "C" fn realloc(ptr: *mut (), prev_layout: crate::alloc::Layout, new_size: usize) -> *mut () {
-    // SAFETY: The corresponding `alloc` returns the allocation offset by this much (see the line where `ret` is constructed in both the `alloc` and `realloc` functions)
-    let realloc_start = unsafe {
-        ptr.cast::<u8>().sub(
-            prev_layout
-                .align
-                .max(core::mem::size_of::<RustAllocPrefix>()),
-        )
-    };
-    let Ok(layout) = core::alloc::Layout::from_size_align(prev_layout.size, prev_layout.align)
-    else {
-        return core::ptr::null_mut();
-    };
-    let requested = Layout::of::<RustAllocPrefix>().concat(Layout {
+    // `prev_layout` was returned by `layout_for`, whose alignment yields the same offset when passed back to it.
+    let Some((offset, requested)) = layout_for(Layout {
         size: new_size,
         align: prev_layout.align,
-    });
+    }) else {
+        return core::ptr::null_mut();
+    };
     // SAFETY: See each line
     unsafe {
         // If `ptr` was indeed allocated on by this allocator, then `realloc_start` was indeed allocated by _our_ GlobalAlloc.
+        let realloc_start = ptr.cast::<u8>().sub(offset);
+        let layout =
+            core::alloc::Layout::from_size_align_unchecked(prev_layout.size, prev_layout.align);
         let alloc_start = alloc_rs::alloc::realloc(realloc_start, layout, requested.size);
+        if alloc_start.is_null() {
+            return core::ptr::null_mut();
+        }
         // We follow the same return-value shifting as in `alloc`
-        let ret = alloc_start.add(layout.align().max(core::mem::size_of::<RustAllocPrefix>()));
+        let ret = alloc_start.add(offset);
         // And prepend the same prefix
         ret.cast::<RustAllocPrefix>().sub(1).write(RustAllocPrefix {
             layout: requested,
@@ -69,7 +98,7 @@ extern "C" fn realloc(ptr: *mut (), prev_layout: crate::alloc::Layout, new_size:
     }
 }
 extern "C" fn free(ptr: *mut (), prev_layout: crate::alloc::Layout) {
-    // SAFETY: The corresponding `alloc` returns the allocation offset by this much (see the line where `ret` is constructed in both the `alloc` and `realloc` functions)
+    // SAFETY: The corresponding `alloc` returns the allocation offset by this much (see `layout_for`)
     let dealloc_start = unsafe {
         ptr.cast::<u8>().sub(
             prev_layout
@@ -105,6 +134,10 @@ impl IAlloc for RustAlloc {
         alloc(layout)
     }
 
+    fn alloc_zeroed(&mut self, layout: crate::alloc::Layout) -> *mut () {
+        alloc_zeroed(layout)
+    }
+
     unsafe fn free(&mut self, ptr: *mut ()) {
         let RustAllocPrefix { layout, vtable } = // SAFETY: if called with a `ptr` allocated by an instance of `self`, this read is valid.
             unsafe { ptr.cast::<RustAllocPrefix>().sub(1).read() };
@@ -0,0 +1,262 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::{
+    ptr::null_mut,
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
+};
+
+use crate::alloc::{IAlloc, Layout};
+
+/// A zero-sized handle to a global heap, built from the regions passed to [`StaticAlloc::init`].
+///
+/// This lets all containers share a single heap without storing a reference to it, which is useful on targets
+/// with no system allocator, such as firmware. Being zero-sized, it may also be selected as the default
+/// allocator with `--cfg stabby_default_alloc="StaticAlloc"`.
+///
+/// The heap is a first-fit free-list, guarded by a spin-lock: freed blocks are merged with their free neighbours
+/// to limit fragmentation. Allocations fail until a region has been handed to the heap.
+///
+/// Note that the heap is a `static` of this crate: binaries that each link their own copy of `stabby-abi` have
+/// distinct heaps, and must therefore not free each other's allocations.
+/// ```
+/// # use stabby_abi::alloc::{allocators::StaticAlloc, vec::Vec};
+/// static mut REGION: [u8; 4096] = [0; 4096];
+/// // SAFETY: `REGION` is never accessed again.
+/// StaticAlloc::init(unsafe { &mut *core::ptr::addr_of_mut!(REGION) });
+/// let mut vec = Vec::new_in(StaticAlloc::default());
+/// vec.extend(0..100u32);
+/// assert!(vec.iter().copied().eq(0..100));
+/// ```
+#[crate::stabby]
+#[derive(Clone, Copy)]
+pub struct StaticAlloc {
+    inner: [u8; 0],
+}
+impl Default for StaticAlloc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl core::fmt::Debug for StaticAlloc {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("StaticAlloc")
+    }
+}
+
+/// A free block of the heap, stored at its start.
+struct FreeBlock {
+    /// The size of the block, including this header.
+    size: usize,
+    /// The next free block, in address order.
+    next: *mut FreeBlock,
+}
+/// The bookkeeping stored right before each allocation's data.
+struct Header {
+    /// The start of the block the allocation was carved from.
+    block: *mut u8,
+    /// The size of that block.
+    size: usize,
+}
+/// The granularity of the heap: all blocks are aligned to, and sized in multiples of, this.
+const UNIT: usize = {
+    let free = core::mem::size_of::<FreeBlock>();
+    let header = core::mem::size_of::<Header>();
+    if free > header {
+        free
+    } else {
+        header
+    }
+};
+
+static FREE: AtomicPtr<FreeBlock> = AtomicPtr::new(null_mut());
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+const fn align_up(value: usize, align: usize) -> Option<usize> {
+    match value.checked_add(align - 1) {
+        Some(value) => Some(value & !(align - 1)),
+        None => None,
+    }
+}
+
+impl StaticAlloc {
+    /// Constructs a handle to the heap.
+    pub const fn new() -> Self {
+        Self { inner: [] }
+    }
+    /// Hands `region` over to the heap.
+    ///
+    /// This may be called several times, each region extending the heap.
+    pub fn init(region: &'static mut [u8]) {
+        let start = region.as_mut_ptr();
+        let Some(aligned) = align_up(start as usize, UNIT) else {
+            return;
+        };
+        let skipped = aligned - start as usize;
+        let size = region.len().saturating_sub(skipped) & !(UNIT - 1);
+        if size == 0 {
+            return;
+        }
+        // SAFETY: the block is in bounds of `region`, which the heap now owns.
+        unsafe { Self::insert(start.add(skipped), size) }
+    }
+    fn with_free_list<R>(f: impl FnOnce(&mut *mut FreeBlock) -> R) -> R {
+        while LOCKED
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        let mut free = FREE.load(Ordering::Relaxed);
+        let ret = f(&mut free);
+        FREE.store(free, Ordering::Relaxed);
+        LOCKED.store(false, Ordering::Release);
+        ret
+    }
+    /// Inserts the block at `start` in the free-list, merging it with its neighbours if they are free.
+    ///
+    /// # Safety
+    /// The block must be owned by the heap, aligned to `UNIT`, and `size` must be a non-zero multiple of it.
+    unsafe fn insert(start: *mut u8, size: usize) {
+        // SAFETY: the free-list only links free blocks of the heap, which `start` isn't part of yet.
+        Self::with_free_list(|free| unsafe {
+            let block = start.cast::<FreeBlock>();
+            let mut prev: *mut FreeBlock = null_mut();
+            let mut next = *free;
+            while !next.is_null() && next < block {
+                prev = next;
+                next = (*next).next;
+            }
+            block.write(FreeBlock { size, next });
+            if !next.is_null() && start.add(size) == next.cast() {
+                (*block).size += (*next).size;
+                (*block).next = (*next).next;
+            }
+            if prev.is_null() {
+                *free = block;
+            } else if prev.cast::<u8>().add((*prev).size) == start {
+                (*prev).size += (*block).size;
+                (*prev).next = (*block).next;
+            } else {
+                (*prev).next = block;
+            }
+        })
+    }
+}
+impl IAlloc for StaticAlloc {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        if layout.size == 0 || !layout.align.is_power_of_two() {
+            return null_mut();
+        }
+        let align = layout.align.max(UNIT);
+        // SAFETY: the free-list only links free blocks of the heap, which are aligned to `UNIT` and store their size.
+        Self::with_free_list(|free| unsafe {
+            let mut prev: *mut FreeBlock = null_mut();
+            let mut current = *free;
+            while !current.is_null() {
+                let FreeBlock { size, next } = current.read();
+                let start = current as usize;
+                let data = align_up(start + core::mem::size_of::<Header>(), align);
+                let end = data
+                    .and_then(|data| data.checked_add(layout.size))
+                    .and_then(|end| align_up(end, UNIT));
+                if let (Some(data), Some(end)) = (data, end) {
+                    if end <= start + size {
+                        // Any remainder is a multiple of `UNIT`, and can therefore hold a `FreeBlock`.
+                        let used = if end < start + size {
+                            let rest = current.cast::<u8>().add(end - start).cast::<FreeBlock>();
+                            rest.write(FreeBlock {
+                                size: start + size - end,
+                                next,
+                            });
+                            if prev.is_null() {
+                                *free = rest;
+                            } else {
+                                (*prev).next = rest;
+                            }
+                            end - start
+                        } else {
+                            if prev.is_null() {
+                                *free = next;
+                            } else {
+                                (*prev).next = next;
+                            }
+                            size
+                        };
+                        let data = current.cast::<u8>().add(data - start);
+                        data.cast::<Header>().sub(1).write(Header {
+                            block: current.cast(),
+                            size: used,
+                        });
+                        return data.cast();
+                    }
+                }
+                prev = current;
+                current = next;
+            }
+            null_mut()
+        })
+    }
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        // SAFETY: `ptr` was allocated by the heap, and is therefore preceded by its header.
+        unsafe {
+            let Header { block, size } = ptr.cast::<Header>().sub(1).read();
+            Self::insert(block, size)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn static_alloc() {
+    use crate::alloc::{boxed::Box, sync::Arc, vec::Vec};
+    let alloc = StaticAlloc::default();
+    assert!(Arc::try_new_in(0u8, alloc).is_err());
+    let region = std::boxed::Box::leak(std::vec![0u8; 1 << 16].into_boxed_slice());
+    let len = region.len();
+    StaticAlloc::init(region);
+    let arc = Arc::new_in(1u64, alloc);
+    let mut vecs: std::vec::Vec<_> = (0..8)
+        .map(|i| {
+            let mut vec = Vec::new_in(alloc);
+            vec.extend(0..100 * i);
+            vec
+        })
+        .collect();
+    for (i, vec) in vecs.iter().enumerate() {
+        assert!(vec.iter().copied().eq(0..100 * i));
+    }
+    // Free every other vector, leaving holes in the heap.
+    vecs.retain(|vec| vec.len() % 200 == 0);
+    #[repr(align(256))]
+    struct Align256(#[allow(dead_code)] u8);
+    let aligned = Box::new_in(Align256(1), alloc);
+    assert_eq!(&*aligned as *const Align256 as usize % 256, 0);
+    drop((vecs, aligned, arc));
+    // Once everything is freed, the blocks are merged back together.
+    let half = Vec::<u8, _>::try_with_capacity_in(len / 2, alloc);
+    assert!(half.is_ok());
+    drop(half);
+    assert!(Vec::<u8, _>::try_with_capacity_in(len, alloc).is_err());
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            s.spawn(move || {
+                for i in 0..1000 {
+                    let arc = Arc::new_in((t, i), alloc);
+                    assert_eq!(*Arc::clone(&arc), (t, i));
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,227 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use super::RustAlloc;
+use crate::alloc::{sync::Arc, IAlloc, Layout};
+
+/// The counters shared by all clones of a [`TrackingAlloc`].
+#[crate::stabby]
+pub struct TrackingCounters {
+    live_bytes: AtomicUsize,
+    live_allocations: AtomicUsize,
+    allocations: AtomicUsize,
+}
+
+/// An allocator that wraps another, keeping count of the allocations made through it.
+///
+/// `stabby`'s containers store their allocator alongside (or inside) their allocation, so the counters are
+/// reference-counted: every clone of a [`TrackingAlloc`] shares them, letting a test keep a clone around to
+/// observe the allocations of the containers it handed other clones to.
+///
+/// To know how many bytes are freed, each allocation is prefixed with its size, which costs
+/// `max(align, 2 * size_of::<usize>())` extra bytes in the inner allocator.
+/// ```
+/// # use stabby_abi::alloc::{allocators::{RustAlloc, TrackingAlloc}, vec::Vec};
+/// let alloc = TrackingAlloc::<RustAlloc>::default();
+/// let mut vec = Vec::new_in(alloc.clone());
+/// vec.extend(0..100u32);
+/// assert_eq!(alloc.live_allocations(), 1);
+/// assert!(alloc.live_bytes() >= 400);
+/// drop(vec);
+/// assert_eq!((alloc.live_allocations(), alloc.live_bytes()), (0, 0));
+/// ```
+#[crate::stabby]
+pub struct TrackingAlloc<Alloc: IAlloc = RustAlloc> {
+    inner: Alloc,
+    counters: Arc<TrackingCounters, RustAlloc>,
+}
+impl<Alloc: IAlloc + Clone> Clone for TrackingAlloc<Alloc> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            counters: self.counters.clone(),
+        }
+    }
+}
+impl<Alloc: IAlloc + Default> Default for TrackingAlloc<Alloc> {
+    fn default() -> Self {
+        Self::new(Alloc::default())
+    }
+}
+impl<Alloc: IAlloc> core::fmt::Debug for TrackingAlloc<Alloc> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TrackingAlloc")
+            .field("live_bytes", &self.live_bytes())
+            .field("live_allocations", &self.live_allocations())
+            .field("allocations", &self.allocations())
+            .finish()
+    }
+}
+impl<Alloc: IAlloc> TrackingAlloc<Alloc> {
+    /// Wraps `inner`, with all counters starting at 0.
+    pub fn new(inner: Alloc) -> Self {
+        Self {
+            inner,
+            counters: Arc::new_in(
+                TrackingCounters {
+                    live_bytes: AtomicUsize::new(0),
+                    live_allocations: AtomicUsize::new(0),
+                    allocations: AtomicUsize::new(0),
+                },
+                RustAlloc::new(),
+            ),
+        }
+    }
+    /// The number of bytes currently allocated, as requested by the callers.
+    pub fn live_bytes(&self) -> usize {
+        self.counters.live_bytes.load(Ordering::Relaxed)
+    }
+    /// The number of allocations that haven't been freed yet.
+    pub fn live_allocations(&self) -> usize {
+        self.counters.live_allocations.load(Ordering::Relaxed)
+    }
+    /// The number of successful calls to `alloc` so far, not counting reallocations.
+    pub fn allocations(&self) -> usize {
+        self.counters.allocations.load(Ordering::Relaxed)
+    }
+    /// Returns the offset of the user's data in the inner allocation, and that allocation's layout.
+    ///
+    /// The data is preceded by its [`Header`], and the offset is a multiple of `layout.align`.
+    fn inner_layout(layout: Layout) -> Option<(usize, Layout)> {
+        let offset = layout.align.max(core::mem::size_of::<Header>());
+        let inner = Layout {
+            size: offset.checked_add(layout.size)?,
+            align: offset,
+        };
+        Some((offset, inner))
+    }
+}
+/// The bookkeeping stored right before each allocation's data.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct Header {
+    /// The distance between the start of the inner allocation and the data.
+    offset: usize,
+    /// The size requested by the caller.
+    size: usize,
+}
+impl Header {
+    /// # Safety
+    /// `ptr` must have been allocated by a [`TrackingAlloc`].
+    const unsafe fn of(ptr: *mut ()) -> *mut Header {
+        unsafe { ptr.cast::<Header>().sub(1) }
+    }
+}
+impl<Alloc: IAlloc> IAlloc for TrackingAlloc<Alloc> {
+    fn alloc(&mut self, layout: Layout) -> *mut () {
+        let Some((offset, inner)) = Self::inner_layout(layout).filter(|_| layout.size != 0) else {
+            return core::ptr::null_mut();
+        };
+        let start = self.inner.alloc(inner).cast::<u8>();
+        if start.is_null() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: the header fits between the start of the allocation and the data, which is aligned for it.
+        let ptr = unsafe {
+            let ptr = start.add(offset).cast::<()>();
+            Header::of(ptr).write(Header {
+                offset,
+                size: layout.size,
+            });
+            ptr
+        };
+        self.counters
+            .live_bytes
+            .fetch_add(layout.size, Ordering::Relaxed);
+        self.counters
+            .live_allocations
+            .fetch_add(1, Ordering::Relaxed);
+        self.counters.allocations.fetch_add(1, Ordering::Relaxed);
+        ptr
+    }
+    unsafe fn free(&mut self, ptr: *mut ()) {
+        // SAFETY: `ptr` was allocated by `self`, and is therefore preceded by its header.
+        let Header { offset, size } = unsafe { Header::of(ptr).read() };
+        self.counters.live_bytes.fetch_sub(size, Ordering::Relaxed);
+        self.counters
+            .live_allocations
+            .fetch_sub(1, Ordering::Relaxed);
+        // SAFETY: `ptr - offset` is the start of the inner allocation.
+        unsafe { self.inner.free(ptr.cast::<u8>().sub(offset).cast()) }
+    }
+    unsafe fn realloc(&mut self, ptr: *mut (), _prev_layout: Layout, new_size: usize) -> *mut () {
+        // SAFETY: `ptr` was allocated by `self`, and is therefore preceded by its header.
+        let Header { offset, size } = unsafe { Header::of(ptr).read() };
+        let Some(new_inner_size) = offset.checked_add(new_size).filter(|_| new_size != 0) else {
+            return core::ptr::null_mut();
+        };
+        let prev_inner = Layout {
+            size: offset + size,
+            align: offset,
+        };
+        // SAFETY: `ptr - offset` is the start of the inner allocation, whose layout is `prev_inner`.
+        let start = unsafe {
+            self.inner.realloc(
+                ptr.cast::<u8>().sub(offset).cast(),
+                prev_inner,
+                new_inner_size,
+            )
+        };
+        if start.is_null() {
+            return core::ptr::null_mut();
+        }
+        // SAFETY: the header was moved along with the data, and is still at the same offset.
+        let ptr = unsafe {
+            let ptr = start.cast::<u8>().add(offset).cast::<()>();
+            (*Header::of(ptr)).size = new_size;
+            ptr
+        };
+        self.counters
+            .live_bytes
+            .fetch_add(new_size, Ordering::Relaxed);
+        self.counters.live_bytes.fetch_sub(size, Ordering::Relaxed);
+        ptr
+    }
+}
+
+#[test]
+fn tracking_alloc() {
+    use crate::alloc::{boxed::Box, sync::Arc, vec::Vec};
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    let boxed = Box::new_in([0u8; 100], alloc.clone());
+    let arc = Arc::new_in(0u64, alloc.clone());
+    assert_eq!(alloc.live_allocations(), 2);
+    let live = alloc.live_bytes();
+    assert!(live >= 108);
+    let mut vec = Vec::new_in(alloc.clone());
+    vec.extend(0..1000u64);
+    assert!(alloc.live_bytes() >= live + 8000);
+    vec.truncate(10);
+    vec.shrink_to_fit();
+    assert!(alloc.live_bytes() < live + 8000);
+    assert!(vec.iter().copied().eq(0..10));
+    assert_eq!(alloc.live_allocations(), 3);
+    drop((boxed, arc, vec));
+    assert_eq!((alloc.live_allocations(), alloc.live_bytes()), (0, 0));
+    assert!(alloc.allocations() >= 3);
+    // Over-aligned allocations make room for the header without misaligning the data.
+    #[repr(align(64))]
+    struct Align64(#[allow(dead_code)] u8);
+    let aligned = Box::new_in(Align64(1), alloc.clone());
+    assert_eq!(&*aligned as *const Align64 as usize % 64, 0);
+    drop(aligned);
+    assert_eq!(alloc.live_allocations(), 0);
+}
@@ -19,7 +19,6 @@ use core::{
     fmt::Debug,
     marker::PhantomData,
     mem::{ManuallyDrop, MaybeUninit},
-    ptr::NonNull,
 };
 
 /// An ABI-stable Box, provided `Alloc` is ABI-stable.
@@ -67,6 +66,13 @@ impl<T> Box<T> {
     pub fn new(value: T) -> Self {
         Self::new_in(value, super::DefaultAllocator::new())
     }
+    /// Allocates a [`Self`], stores `value` in it, and pins it.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn pin(value: T) -> core::pin::Pin<Self> {
+        Self::pin_in(value, super::DefaultAllocator::new())
+    }
 }
 impl<T, Alloc: IAlloc> Box<T, Alloc> {
     /// Attempts to allocate [`Self`], initializing it with `constructor`.
@@ -167,6 +173,20 @@ impl<T, Alloc: IAlloc> Box<T, Alloc> {
         // SAFETY: `constructor` is infallible.
         unsafe { this.unwrap_unchecked() }
     }
+    /// Allocates a [`Self`] in `alloc`, stores `value` in it, and pins it.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn pin_in(value: T, alloc: Alloc) -> core::pin::Pin<Self> {
+        Self::into_pin(Self::new_in(value, alloc))
+    }
+    /// Pins `this`.
+    ///
+    /// Since the value lives in its own allocation, it will never be moved until it's dropped in place.
+    pub const fn into_pin(this: Self) -> core::pin::Pin<Self> {
+        // SAFETY: the value can't be moved out of its allocation without unpinning it first.
+        unsafe { core::pin::Pin::new_unchecked(this) }
+    }
     /// Extracts the value from the allocation, freeing said allocation.
     pub fn into_inner(this: Self) -> T {
         let mut this = core::mem::ManuallyDrop::new(this);
@@ -176,20 +196,71 @@ impl<T, Alloc: IAlloc> Box<T, Alloc> {
         unsafe { this.free() };
         ManuallyDrop::into_inner(ret)
     }
+    /// Leaks `this`, returning a mutable reference to its contents.
+    ///
+    /// Neither the allocation nor the allocator are ever freed, unless the reference is passed back to [`Self::from_raw`].
+    #[rustversion::attr(since(1.83), const)]
+    pub fn leak<'a>(this: Self) -> &'a mut T
+    where
+        T: 'a,
+        Alloc: 'a,
+    {
+        // SAFETY: the allocation is never freed, and `T` outlives `'a`.
+        unsafe { Self::into_raw(this).ptr.as_mut() }
+    }
     /// Returns the pointer to the inner raw allocation, leaking `this`.
     ///
+    /// The allocator is left in the allocation's prefix, so [`Self::from_raw`] only needs the pointer to
+    /// reconstruct `this`: this makes it the simplest way to pass a [`Box`] through FFI as a raw pointer.
+    ///
     /// Note that the pointer may be dangling if `T` is zero-sized.
     pub const fn into_raw(this: Self) -> AllocPtr<T, Alloc> {
         let inner = this.ptr;
         core::mem::forget(this);
         inner
     }
-    /// Constructs `Self` from a raw allocation.
+    /// Constructs `Self` from a raw allocation, as returned by [`Self::into_raw`].
     /// # Safety
-    /// No other container must own (even partially) `this`.
+    /// No other container must own (even partially) `this`, whose prefix must hold the allocator `this` was
+    /// allocated with.
     pub const unsafe fn from_raw(this: AllocPtr<T, Alloc>) -> Self {
         Self { ptr: this }
     }
+    /// Returns the pointer to the inner raw allocation and the allocator it was allocated with, leaking `this`.
+    ///
+    /// Unlike [`Self::into_raw`], the allocator is moved out of the allocation's prefix: the pointer must therefore
+    /// be passed back to [`Self::from_raw_in`] along with the allocator, rather than to [`Self::from_raw`].
+    /// ```
+    /// # use stabby_abi::alloc::{allocators::RustAlloc, boxed::Box, AllocPtr};
+    /// # use core::{marker::PhantomData, ptr::NonNull};
+    /// let boxed = Box::new_in(3u32, RustAlloc::new());
+    /// let (ptr, alloc) = Box::into_raw_with_allocator(boxed);
+    /// let raw: *mut u32 = ptr.ptr.as_ptr();
+    /// // `raw` may travel through FFI, as long as it comes back unchanged.
+    /// let ptr = AllocPtr { ptr: NonNull::new(raw).unwrap(), marker: PhantomData };
+    /// let boxed = unsafe { Box::from_raw_in(ptr, alloc) };
+    /// assert_eq!(*boxed, 3);
+    /// ```
+    #[rustversion::attr(since(1.75), const)]
+    pub fn into_raw_with_allocator(this: Self) -> (AllocPtr<T, Alloc>, Alloc) {
+        let ptr = Self::into_raw(this);
+        // SAFETY: `Box` guarantees that `alloc` is stored in the prefix: it is moved out of it, and `from_raw_in`
+        // is the only way to reconstruct the box.
+        let alloc = unsafe { ptr.prefix().alloc.assume_init_read() };
+        (ptr, alloc)
+    }
+    /// Constructs `Self` from a raw allocation and its allocator, as returned by [`Self::into_raw_with_allocator`].
+    ///
+    /// `alloc` is stored back in the allocation's prefix.
+    /// # Safety
+    /// No other container must own (even partially) `ptr`, which must have been allocated with `alloc`, and must
+    /// not hold an allocator in its prefix anymore.
+    #[rustversion::attr(since(1.86), const)]
+    pub unsafe fn from_raw_in(mut ptr: AllocPtr<T, Alloc>, alloc: Alloc) -> Self {
+        // SAFETY: the caller guarantees that `ptr` is owned by the new box, and that its prefix is vacant.
+        unsafe { ptr.prefix_mut() }.alloc.write(alloc);
+        Self { ptr }
+    }
 }
 
 impl<T, Alloc: IAlloc> Box<T, Alloc> {
@@ -224,6 +295,43 @@ impl<T, Alloc: IAlloc> core::ops::DerefMut for Box<T, Alloc> {
         unsafe { self.ptr.as_mut() }
     }
 }
+fmt_passthrough!(Box);
+impl<T, Alloc: IAlloc> AsRef<T> for Box<T, Alloc> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+impl<T, Alloc: IAlloc> AsMut<T> for Box<T, Alloc> {
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+impl<T, Alloc: IAlloc> core::borrow::Borrow<T> for Box<T, Alloc> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+impl<T, Alloc: IAlloc> core::borrow::BorrowMut<T> for Box<T, Alloc> {
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+// A `Box` may be moved around freely without moving its contents.
+impl<T, Alloc: IAlloc> Unpin for Box<T, Alloc> {}
+impl<T, Alloc: IAlloc> From<Box<T, Alloc>> for core::pin::Pin<Box<T, Alloc>> {
+    fn from(value: Box<T, Alloc>) -> Self {
+        Box::into_pin(value)
+    }
+}
+impl<F: core::future::Future + Unpin, Alloc: IAlloc> core::future::Future for Box<F, Alloc> {
+    type Output = F::Output;
+    fn poll(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Self::Output> {
+        F::poll(core::pin::Pin::new(&mut **self), cx)
+    }
+}
 impl<T, Alloc: IAlloc> crate::IPtr for Box<T, Alloc> {
     unsafe fn as_ref(&self) -> AnonymRef<'_> {
         AnonymRef {
@@ -247,7 +355,7 @@ impl<T, Alloc: IAlloc> crate::IPtrOwned for Box<T, Alloc> {
     ) {
         // SAFETY: This is evil casting shenanigans, but `IPtrOwned` is a type anonimization primitive.
         unsafe {
-            drop(Self::as_mut(this));
+            drop(IPtrMut::as_mut(&mut **this));
         }
         // SAFETY: `this` is immediately forgotten.
         unsafe { this.free() }
@@ -267,12 +375,9 @@ impl<T, Alloc: IAlloc> IntoDyn for Box<T, Alloc> {
     type Anonymized = Box<(), Alloc>;
     type Target = T;
     fn anonimize(self) -> Self::Anonymized {
-        let original_prefix = self.ptr.prefix_ptr();
-        // SAFETY: Evil anonimization.
-        let anonymized = unsafe { core::mem::transmute::<Self, Self::Anonymized>(self) };
-        let anonymized_prefix = anonymized.ptr.prefix_ptr();
-        assert_eq!(anonymized_prefix, original_prefix, "The allocation prefix was lost in anonimization, this is definitely a bug, please report it.");
-        anonymized
+        // SAFETY: The prefix is always located right before the value, regardless of its type,
+        // so casting the pointer keeps it reachable.
+        unsafe { Box::from_raw(Box::into_raw(self).cast()) }
     }
 }
 
@@ -295,7 +400,7 @@ impl<T, Alloc: IAlloc> BoxedSlice<T, Alloc> {
     }
     /// The number of elements in the boxed slice.
     pub const fn len(&self) -> usize {
-        ptr_diff(self.slice.end, self.slice.start.ptr)
+        self.slice.len()
     }
     /// Returns `true` if the slice is empty.
     pub const fn is_empty(&self) -> bool {
@@ -317,18 +422,22 @@ impl<T, Alloc: IAlloc> BoxedSlice<T, Alloc> {
     /// # Errors
     /// Returns the value if pushing would require reallocating.
     pub fn try_push(&mut self, value: T) -> Result<(), T> {
-        // SAFETY: the prefix must be initialized for this type to exist.
-        if self.slice.len()
-            >= unsafe { self.slice.start.prefix() }
+        let capacity = if core::mem::size_of::<T>() == 0 {
+            // Zero-sized elements may not have an allocation, but never run out of room until the end of the address space.
+            usize::MAX - addr(self.slice.start.ptr)
+        } else {
+            // SAFETY: the prefix must be initialized for this type to exist.
+            unsafe { self.slice.start.prefix() }
                 .capacity
                 .load(core::sync::atomic::Ordering::Relaxed)
-        {
+        };
+        if self.slice.len() >= capacity {
             return Err(value);
         }
         // SAFETY: we've acertained that we have enough space to push an element.
         unsafe {
             core::ptr::write(self.slice.end.as_ptr(), value);
-            self.slice.end = NonNull::new_unchecked(self.slice.end.as_ptr().add(1));
+            self.slice.end = ptr_add(self.slice.end, 1);
         }
         Ok(())
     }
@@ -364,6 +473,26 @@ impl<T, Alloc: IAlloc> core::ops::DerefMut for BoxedSlice<T, Alloc> {
         self.as_slice_mut()
     }
 }
+impl<T, Alloc: IAlloc> AsRef<[T]> for BoxedSlice<T, Alloc> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T, Alloc: IAlloc> AsMut<[T]> for BoxedSlice<T, Alloc> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_slice_mut()
+    }
+}
+impl<T, Alloc: IAlloc> core::borrow::Borrow<[T]> for BoxedSlice<T, Alloc> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T, Alloc: IAlloc> core::borrow::BorrowMut<[T]> for BoxedSlice<T, Alloc> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_slice_mut()
+    }
+}
 impl<T: Eq, Alloc: IAlloc> Eq for BoxedSlice<T, Alloc> {}
 impl<T: PartialEq, Alloc: IAlloc> PartialEq for BoxedSlice<T, Alloc> {
     fn eq(&self, other: &Self) -> bool {
@@ -413,7 +542,8 @@ impl<T, Alloc: IAlloc> From<BoxedSlice<T, Alloc>> for Vec<T, Alloc> {
                 inner: VecInner {
                     start: slice.start,
                     end: slice.end,
-                    capacity: ptr_add(slice.start.ptr, capacity),
+                    // SAFETY: `capacity` is that of the allocation `slice` comes from.
+                    capacity: unsafe { ptr_add(slice.start.ptr, capacity) },
                     alloc,
                 },
             }
@@ -423,7 +553,8 @@ impl<T, Alloc: IAlloc> From<BoxedSlice<T, Alloc>> for Vec<T, Alloc> {
                     start: slice.start,
                     end: slice.end,
                     capacity: if core::mem::size_of::<T>() == 0 {
-                        unsafe { core::mem::transmute::<usize, NonNull<T>>(usize::MAX) }
+                        AllocSlice::from_zst_len(slice.start, usize::MAX - addr(slice.start.ptr))
+                            .end
                     } else {
                         slice.start.ptr
                     },
@@ -460,28 +591,12 @@ impl<T: Debug, Alloc: IAlloc> Debug for BoxedSlice<T, Alloc> {
 }
 impl<T: core::fmt::LowerHex, Alloc: IAlloc> core::fmt::LowerHex for BoxedSlice<T, Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut first = true;
-        for item in self {
-            if !first {
-                f.write_str(":")?;
-            }
-            first = false;
-            core::fmt::LowerHex::fmt(item, f)?;
-        }
-        Ok(())
+        super::fmt_hex(self.as_slice(), f, core::fmt::LowerHex::fmt)
     }
 }
 impl<T: core::fmt::UpperHex, Alloc: IAlloc> core::fmt::UpperHex for BoxedSlice<T, Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut first = true;
-        for item in self {
-            if !first {
-                f.write_str(":")?;
-            }
-            first = false;
-            core::fmt::UpperHex::fmt(item, f)?;
-        }
-        Ok(())
+        super::fmt_hex(self.as_slice(), f, core::fmt::UpperHex::fmt)
     }
 }
 impl<'a, T, Alloc: IAlloc> IntoIterator for &'a BoxedSlice<T, Alloc> {
@@ -508,11 +623,148 @@ impl<T, Alloc: IAlloc> IntoIterator for BoxedSlice<T, Alloc> {
 }
 pub use super::string::BoxedStr;
 
+#[cfg(feature = "std")]
+#[test]
+fn box_leak_into_inner() {
+    use std::sync::Arc;
+    let counter = Arc::new(());
+    let boxed = Box::new(Arc::clone(&counter));
+    let inner = Box::into_inner(boxed);
+    assert_eq!(Arc::strong_count(&counter), 2);
+    drop(inner);
+    assert_eq!(Arc::strong_count(&counter), 1);
+
+    let leaked: &'static mut (u32, Arc<()>) = Box::leak(Box::new((1, Arc::clone(&counter))));
+    leaked.0 += 1;
+    assert_eq!(leaked.0, 2);
+    assert_eq!(Arc::strong_count(&counter), 2);
+    // SAFETY: `leaked` comes from `Box::leak`, and isn't used after this.
+    drop(unsafe {
+        Box::<_, super::DefaultAllocator>::from_raw(AllocPtr {
+            ptr: core::ptr::NonNull::from(leaked),
+            marker: PhantomData,
+        })
+    });
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn box_pin() {
+    use core::{
+        future::Future,
+        marker::PhantomPinned,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    /// Checks that it's never moved once it's been polled.
+    struct AddressSensitive {
+        address: Option<usize>,
+        polls: u8,
+        _pinned: PhantomPinned,
+    }
+    impl AddressSensitive {
+        fn check(&mut self) {
+            let address = self as *const Self as usize;
+            assert_eq!(*self.address.get_or_insert(address), address);
+        }
+    }
+    impl Future for AddressSensitive {
+        type Output = u8;
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u8> {
+            // SAFETY: `self` is never moved out of.
+            let this = unsafe { self.get_unchecked_mut() };
+            this.check();
+            this.polls += 1;
+            if this.polls < 3 {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            } else {
+                Poll::Ready(this.polls)
+            }
+        }
+    }
+    impl Drop for AddressSensitive {
+        fn drop(&mut self) {
+            self.check();
+        }
+    }
+    struct Noop;
+    impl std::task::Wake for Noop {
+        fn wake(self: std::sync::Arc<Self>) {}
+    }
+    let waker = std::task::Waker::from(std::sync::Arc::new(Noop));
+    let mut cx = Context::from_waker(&waker);
+    let mut pinned = Box::pin(AddressSensitive {
+        address: None,
+        polls: 0,
+        _pinned: PhantomPinned,
+    });
+    assert!(pinned.as_mut().poll(&mut cx).is_pending());
+    // Moving the pinned box doesn't move its contents.
+    let mut moved = [pinned];
+    assert!(moved[0].as_mut().poll(&mut cx).is_pending());
+    let [mut pinned] = moved;
+    assert_eq!(pinned.as_mut().poll(&mut cx), Poll::Ready(3));
+    drop(pinned);
+
+    // `Pin<Box<F>>` and `Box<F: Unpin>` are futures.
+    let mut unpin = Box::new(core::future::ready(4));
+    assert_eq!(Pin::new(&mut unpin).poll(&mut cx), Poll::Ready(4));
+    let mut pinned: Pin<Box<_>> = Box::new(async { 5 }).into();
+    assert_eq!(pinned.as_mut().poll(&mut cx), Poll::Ready(5));
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn raw_with_allocator() {
+    use super::allocators::{RustAlloc, TrackingAlloc};
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    let boxed = Box::new_in((1u32, Box::new_in(2u8, alloc.clone())), alloc.clone());
+    // The allocator travels alongside the pointer.
+    let (ptr, inner_alloc) = Box::into_raw_with_allocator(boxed);
+    assert_eq!(alloc.live_allocations(), 2);
+    // SAFETY: `ptr` and `inner_alloc` come from `into_raw_with_allocator`.
+    let boxed = unsafe { Box::from_raw_in(ptr, inner_alloc) };
+    assert_eq!((boxed.0, *boxed.1), (1, 2));
+    // The allocator stays in the prefix.
+    let ptr = Box::into_raw(boxed);
+    // SAFETY: `ptr` comes from `into_raw`.
+    let boxed = unsafe { Box::from_raw(ptr) };
+    assert_eq!((boxed.0, *boxed.1), (1, 2));
+    drop(boxed);
+    assert_eq!(alloc.live_allocations(), 0);
+    // Zero-sized values still have their allocator stored in their prefix.
+    let (ptr, inner_alloc) = Box::into_raw_with_allocator(Box::new_in((), alloc.clone()));
+    // SAFETY: `ptr` and `inner_alloc` come from `into_raw_with_allocator`.
+    drop(unsafe { Box::from_raw_in(ptr, inner_alloc) });
+    assert_eq!(alloc.live_allocations(), 0);
+}
+
 #[cfg(feature = "serde")]
 mod serde_impl {
     use super::*;
     use crate::alloc::IAlloc;
     use serde::{Deserialize, Serialize};
+    impl<T: Serialize, Alloc: IAlloc> Serialize for Box<T, Alloc> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let value: &T = self;
+            value.serialize(serializer)
+        }
+    }
+    impl<'a, T: Deserialize<'a>, Alloc: IAlloc + Default> Deserialize<'a> for Box<T, Alloc> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'a>,
+        {
+            let value = T::deserialize(deserializer)?;
+            Box::try_new_in(value, Alloc::default())
+                .map_err(|_| serde::de::Error::custom(crate::alloc::AllocationError()))
+        }
+    }
     impl<T: Serialize, Alloc: IAlloc> Serialize for BoxedSlice<T, Alloc> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -548,3 +800,17 @@ mod serde_impl {
         }
     }
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn fmt_passthrough() {
+    use crate::alloc::string::String;
+    let boxed: Box<String> = Box::new(String::from("hello"));
+    assert_eq!(format!("{boxed} {boxed:?}"), "hello \"hello\"");
+    let arc: crate::alloc::sync::Arc<String> = crate::alloc::sync::Arc::new("world".into());
+    assert_eq!(format!("{arc} {arc:?}"), "world \"world\"");
+    let n = Box::new(0xabu8);
+    assert_eq!(format!("{n:x} {n:#X} {n:010b}"), "ab 0xAB 0010101011");
+    let n = crate::alloc::sync::Arc::new(5u8);
+    assert_eq!(format!("{n:>3} {n:02x} {n:b}"), "  5 05 101");
+}
@@ -74,8 +74,18 @@ where
 #[crate::stabby]
 #[derive(Debug, Clone)]
 pub struct Entry<K, V> {
-    key: K,
-    value: V,
+    pub(crate) key: K,
+    pub(crate) value: V,
+}
+impl<K, V> Entry<K, V> {
+    /// The entry's key.
+    pub const fn key(&self) -> &K {
+        &self.key
+    }
+    /// The entry's value.
+    pub const fn value(&self) -> &V {
+        &self.value
+    }
 }
 
 impl<K: Ord, V> PartialEq for Entry<K, V> {
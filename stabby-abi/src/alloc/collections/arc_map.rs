@@ -0,0 +1,305 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::{borrow::Borrow, cmp::Ordering};
+
+use super::arc_btree::Entry;
+use crate::alloc::{sync::ArcSlice, vec::Vec, DefaultAllocator, IAlloc};
+
+/// An immutable map, storing its entries in a sorted [`ArcSlice`].
+///
+/// Lookups are done by binary search, and cloning the map only increments a reference count:
+/// all clones share the same entries, making [`ArcMap`] well suited for read-only tables that
+/// are distributed to many threads.
+///
+/// Maps are built from iterators, which are sorted by key on construction. Should a key appear
+/// several times, only the last value associated to it is kept.
+/// ```
+/// # use stabby_abi::alloc::collections::arc_map::ArcMap;
+/// let map: ArcMap<&str, u32> = [("b", 2), ("a", 1), ("b", 3)].into_iter().collect();
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map.get("b"), Some(&3));
+/// let shared = map.clone();
+/// assert!(ArcMap::ptr_eq(&map, &shared));
+/// assert!(shared.keys().copied().eq(["a", "b"]));
+/// ```
+#[crate::stabby]
+pub struct ArcMap<K, V, Alloc: IAlloc = DefaultAllocator> {
+    entries: ArcSlice<Entry<K, V>, Alloc>,
+}
+impl<K, V, Alloc: IAlloc> Clone for ArcMap<K, V, Alloc> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+impl<K: Ord, V, Alloc: IAlloc> ArcMap<K, V, Alloc> {
+    /// Constructs a map from `iter`'s entries, in `alloc`.
+    ///
+    /// # Panics
+    /// In case of allocation failure.
+    pub fn from_iter_in<I: IntoIterator<Item = (K, V)>>(iter: I, alloc: Alloc) -> Self {
+        let iter = iter.into_iter();
+        let mut entries = Vec::with_capacity_in(iter.size_hint().0, alloc);
+        for (key, value) in iter {
+            entries.push(Entry { key, value });
+        }
+        merge_sort(entries.as_mut_slice());
+        let len = entries.len();
+        // SAFETY: until `len` is restored, entries are moved (or dropped) exactly once, and leaked should a comparison panic.
+        unsafe {
+            entries.set_len(0);
+            let start = entries.as_mut_ptr();
+            let mut kept = 0;
+            for i in 0..len {
+                let entry = start.add(i);
+                if i + 1 < len && (*entry).key == (*start.add(i + 1)).key {
+                    // A later value is associated to the same key.
+                    entry.drop_in_place();
+                } else {
+                    start.add(kept).write(entry.read());
+                    kept += 1;
+                }
+            }
+            entries.set_len(kept);
+        }
+        Self {
+            entries: entries.into(),
+        }
+    }
+    /// Returns the index of the entry associated to `key` if it exists, or the index at which it would be inserted otherwise.
+    fn search<Q: Ord + ?Sized>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.entries
+            .binary_search_by(|entry| entry.key.borrow().cmp(key))
+    }
+    /// Returns the value associated to `key`.
+    pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+    /// Returns the entry associated to `key`.
+    pub fn get_key_value<Q: Ord + ?Sized>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+    {
+        let entry = &self.entries[self.search(key).ok()?];
+        Some((&entry.key, &entry.value))
+    }
+    /// Returns `true` if the map contains a value for `key`.
+    pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.search(key).is_ok()
+    }
+}
+impl<K, V, Alloc: IAlloc> ArcMap<K, V, Alloc> {
+    /// Returns the number of entries in the map.
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Returns `true` if the map contains no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Returns the entries of the map, sorted by key.
+    #[rustversion::attr(since(1.86), const)]
+    pub fn entries(&self) -> &[Entry<K, V>] {
+        self.entries.as_slice()
+    }
+    /// Returns an iterator over the entries of the map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: self.entries().iter(),
+        }
+    }
+    /// Returns an iterator over the keys of the map, in order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+        self.entries().iter().map(|entry| &entry.key)
+    }
+    /// Returns an iterator over the values of the map, sorted by key.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + '_ {
+        self.entries().iter().map(|entry| &entry.value)
+    }
+    /// Returns `true` if `this` and `other` share their entries.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        ArcSlice::ptr_eq(&this.entries, &other.entries)
+    }
+}
+#[cfg(not(stabby_default_alloc = "disabled"))]
+impl<K: Ord, V> ArcMap<K, V> {
+    /// Constructs an empty map in the default allocator.
+    ///
+    /// # Panics
+    /// In case of allocation failure.
+    pub fn new() -> Self {
+        Self::from_iter_in(core::iter::empty(), DefaultAllocator::new())
+    }
+}
+impl<K: Ord, V, Alloc: IAlloc + Default> Default for ArcMap<K, V, Alloc> {
+    fn default() -> Self {
+        Self::from_iter_in(core::iter::empty(), Alloc::default())
+    }
+}
+impl<K: Ord, V, Alloc: IAlloc + Default> FromIterator<(K, V)> for ArcMap<K, V, Alloc> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from_iter_in(iter, Alloc::default())
+    }
+}
+impl<K: PartialEq, V: PartialEq, Alloc: IAlloc> PartialEq for ArcMap<K, V, Alloc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+impl<K: Eq, V: Eq, Alloc: IAlloc> Eq for ArcMap<K, V, Alloc> {}
+impl<K: core::fmt::Debug, V: core::fmt::Debug, Alloc: IAlloc> core::fmt::Debug
+    for ArcMap<K, V, Alloc>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<'a, K, V, Alloc: IAlloc> IntoIterator for &'a ArcMap<K, V, Alloc> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of an [`ArcMap`], sorted by key.
+pub struct Iter<'a, K, V> {
+    entries: core::slice::Iter<'a, Entry<K, V>>,
+}
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| (&entry.key, &entry.value))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// A stable in-place merge sort, merging through rotations so that it doesn't need to allocate.
+///
+/// Elements are only ever swapped, so a panicking comparison leaves `slice` in a valid (albeit unsorted) state.
+fn merge_sort<T: Ord>(slice: &mut [T]) {
+    if slice.windows(2).all(|pair| pair[0] <= pair[1]) {
+        return;
+    }
+    let mid = slice.len() / 2;
+    merge_sort(&mut slice[..mid]);
+    merge_sort(&mut slice[mid..]);
+    merge(slice, mid);
+}
+/// Merges the sorted `slice[..mid]` and `slice[mid..]`, keeping equal elements in order.
+fn merge<T: Ord>(slice: &mut [T], mid: usize) {
+    let len = slice.len();
+    if mid == 0 || mid == len || slice[mid - 1] <= slice[mid] {
+        return;
+    }
+    if len == 2 {
+        slice.swap(0, 1);
+        return;
+    }
+    // Split the larger half in its middle, and the other where that middle element would be inserted,
+    // so that rotating the inner parts leaves two smaller merges to do.
+    let (left_cut, right_cut) = if mid >= len - mid {
+        let left_cut = mid / 2;
+        let pivot = &slice[left_cut];
+        let right_cut = mid
+            + slice[mid..]
+                .binary_search_by(|x| x.cmp(pivot).then(Ordering::Greater))
+                .unwrap_or_else(|i| i);
+        (left_cut, right_cut)
+    } else {
+        let right_cut = mid + (len - mid) / 2;
+        let pivot = &slice[right_cut];
+        let left_cut = slice[..mid]
+            .binary_search_by(|x| x.cmp(pivot).then(Ordering::Less))
+            .unwrap_or_else(|i| i);
+        (left_cut, right_cut)
+    };
+    slice[left_cut..right_cut].rotate_left(mid - left_cut);
+    let new_mid = left_cut + (right_cut - mid);
+    let (left, right) = slice.split_at_mut(new_mid);
+    merge(left, left_cut);
+    merge(right, right_cut - new_mid);
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn arc_map() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    for len in [0, 1, 2, 3, 10, 100, 1000] {
+        let entries = (0..len)
+            .map(|_| (rng.gen_range(0..len as u32 / 2 + 1), rng.gen::<u32>()))
+            .collect::<std::vec::Vec<_>>();
+        let reference = entries
+            .iter()
+            .copied()
+            .collect::<std::collections::BTreeMap<_, _>>();
+        let map = entries.iter().copied().collect::<ArcMap<_, _>>();
+        assert_eq!(map.len(), reference.len());
+        assert!(map.iter().eq(reference.iter()));
+        for key in 0..len as u32 {
+            assert_eq!(map.get(&key), reference.get(&key));
+        }
+        let clone = map.clone();
+        assert!(ArcMap::ptr_eq(&map, &clone));
+        assert_eq!(map, clone);
+    }
+    // Duplicate keys keep their last value, and the other values are dropped.
+    let value = crate::alloc::sync::Arc::new(0);
+    let map = (0..10)
+        .map(|i| (i % 3, value.clone()))
+        .collect::<ArcMap<_, _>>();
+    assert_eq!(map.len(), 3);
+    assert_eq!(crate::alloc::sync::Arc::strong_count(&value), 4);
+    drop(map);
+    assert_eq!(crate::alloc::sync::Arc::strong_count(&value), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn merge_sort_is_stable() {
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let mut values = (0..1000u32)
+        .map(|i| Entry {
+            key: rng.gen_range(0..50u8),
+            value: i,
+        })
+        .collect::<std::vec::Vec<_>>();
+    merge_sort(&mut values);
+    assert!(values
+        .windows(2)
+        .all(|pair| (pair[0].key, pair[0].value) < (pair[1].key, pair[1].value)));
+}
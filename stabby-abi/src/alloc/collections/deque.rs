@@ -0,0 +1,511 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::{fmt::Debug, mem::MaybeUninit};
+
+use crate::alloc::{AllocPtr, AllocationError, DefaultAllocator, IAlloc};
+
+/// A double-ended queue, implemented as a growable ring buffer.
+///
+/// Pushing and popping at either end is done in amortized constant time. Since the elements may wrap around
+/// the end of the buffer, they are exposed as two slices by [`Deque::as_slices`], unless [`Deque::make_contiguous`]
+/// is called first.
+/// ```
+/// # use stabby_abi::alloc::collections::Deque;
+/// let mut deque = Deque::new();
+/// deque.push_back(2);
+/// deque.push_back(3);
+/// deque.push_front(1);
+/// assert_eq!(deque.pop_front(), Some(1));
+/// assert_eq!(deque.make_contiguous(), &[2, 3]);
+/// ```
+#[crate::stabby]
+pub struct Deque<T, Alloc: IAlloc = DefaultAllocator> {
+    start: AllocPtr<T, Alloc>,
+    capacity: usize,
+    head: usize,
+    len: usize,
+    alloc: Alloc,
+}
+// SAFETY: `Deque` owns its elements, just like a `Vec`.
+unsafe impl<T: Send, Alloc: IAlloc + Send> Send for Deque<T, Alloc> {}
+// SAFETY: `Deque` owns its elements, just like a `Vec`.
+unsafe impl<T: Sync, Alloc: IAlloc + Sync> Sync for Deque<T, Alloc> {}
+
+#[cfg(not(stabby_default_alloc = "disabled"))]
+impl<T> Deque<T> {
+    /// Constructs an empty deque with the default allocator. This doesn't actually allocate.
+    pub const fn new() -> Self {
+        Self::new_in(DefaultAllocator::new())
+    }
+    /// Constructs an empty deque with the default allocator, with room for `capacity` elements.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide an appropriate allocation.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, DefaultAllocator::new())
+    }
+}
+impl<T, Alloc: IAlloc + Default> Default for Deque<T, Alloc> {
+    fn default() -> Self {
+        Self::new_in(Alloc::default())
+    }
+}
+impl<T, Alloc: IAlloc> Deque<T, Alloc> {
+    const ZST: bool = core::mem::size_of::<T>() == 0;
+    /// Constructs an empty deque in `alloc`. This doesn't actually allocate.
+    pub const fn new_in(alloc: Alloc) -> Self {
+        Self {
+            start: AllocPtr::dangling(),
+            capacity: if Self::ZST { usize::MAX } else { 0 },
+            head: 0,
+            len: 0,
+            alloc,
+        }
+    }
+    /// Constructs an empty deque in `alloc`, with room for `capacity` elements.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide an appropriate allocation.
+    pub fn with_capacity_in(capacity: usize, alloc: Alloc) -> Self {
+        let mut this = Self::new_in(alloc);
+        this.reserve(capacity);
+        this
+    }
+    /// Returns the number of elements in the deque.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the deque is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The number of elements the deque can hold without reallocating.
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Returns a reference to the deque's allocator.
+    pub const fn allocator(&self) -> &Alloc {
+        &self.alloc
+    }
+    /// Returns the index in the buffer of the `index`th element.
+    const fn physical(&self, index: usize) -> usize {
+        let until_wrap = self.capacity - self.head;
+        if index >= until_wrap {
+            index - until_wrap
+        } else {
+            self.head + index
+        }
+    }
+    const fn slot(&self, index: usize) -> *mut T {
+        // SAFETY: `physical` always returns an index within the buffer.
+        unsafe { self.start.ptr.as_ptr().add(self.physical(index)) }
+    }
+    /// Ensures that `additional` more elements can be pushed on `self` without reallocating.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide an appropriate allocation.
+    pub fn reserve(&mut self, additional: usize) {
+        self.try_reserve(additional).unwrap();
+    }
+    /// Ensures that `additional` more elements can be pushed on `self` without reallocating.
+    ///
+    /// This may reallocate once to provide this guarantee.
+    ///
+    /// # Errors
+    /// Returns an [`AllocationError`] if the allocator failed to provide an appropriate allocation,
+    /// in which case `self` is left untouched.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocationError> {
+        if self.capacity - self.len >= additional {
+            return Ok(());
+        }
+        let new_capacity = self.len.checked_add(additional).ok_or(AllocationError())?;
+        let old_capacity = self.capacity;
+        let start = if old_capacity != 0 {
+            // SAFETY: the buffer isn't dangling since it has some capacity.
+            unsafe { self.start.grow(&mut self.alloc, old_capacity, new_capacity) }
+        } else {
+            AllocPtr::alloc_array(&mut self.alloc, new_capacity)
+        };
+        self.start = start.ok_or(AllocationError())?;
+        self.capacity = new_capacity;
+        let head_len = old_capacity - self.head;
+        if self.len > head_len {
+            // The elements used to wrap around the end of the buffer: move the smallest part that can be moved.
+            let tail_len = self.len - head_len;
+            let buffer = self.start.ptr.as_ptr();
+            // SAFETY: both moves stay within the new buffer, and only move initialized elements to unused slots.
+            unsafe {
+                if tail_len <= new_capacity - old_capacity {
+                    core::ptr::copy_nonoverlapping(buffer, buffer.add(old_capacity), tail_len);
+                } else {
+                    let new_head = new_capacity - head_len;
+                    core::ptr::copy(buffer.add(self.head), buffer.add(new_head), head_len);
+                    self.head = new_head;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn try_grow(&mut self) -> Result<(), AllocationError> {
+        if self.len == self.capacity {
+            self.try_reserve(self.capacity.max(4))
+        } else {
+            Ok(())
+        }
+    }
+    /// Pushes `value` at the back of the deque.
+    ///
+    /// # Panics
+    /// If the deque was full, and the allocator failed to provide a new allocation.
+    pub fn push_back(&mut self, value: T) {
+        if self.try_push_back(value).is_err() {
+            panic!("Failed to push because reallocation failed.")
+        }
+    }
+    /// Pushes `value` at the back of the deque.
+    ///
+    /// # Errors
+    /// Gives back `value` if the deque was full, and the allocator failed to provide a new allocation.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), T> {
+        if self.try_grow().is_err() {
+            return Err(value);
+        }
+        // SAFETY: the slot after the last element is free, since the deque isn't full.
+        unsafe { self.slot(self.len).write(value) };
+        self.len += 1;
+        Ok(())
+    }
+    /// Pushes `value` at the front of the deque.
+    ///
+    /// # Panics
+    /// If the deque was full, and the allocator failed to provide a new allocation.
+    pub fn push_front(&mut self, value: T) {
+        if self.try_push_front(value).is_err() {
+            panic!("Failed to push because reallocation failed.")
+        }
+    }
+    /// Pushes `value` at the front of the deque.
+    ///
+    /// # Errors
+    /// Gives back `value` if the deque was full, and the allocator failed to provide a new allocation.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), T> {
+        if self.try_grow().is_err() {
+            return Err(value);
+        }
+        self.head = self.physical(self.capacity - 1);
+        // SAFETY: the slot before the first element is free, since the deque isn't full.
+        unsafe { self.slot(0).write(value) };
+        self.len += 1;
+        Ok(())
+    }
+    /// Removes the last element of the deque, returning it if it wasn't empty.
+    #[rustversion::attr(since(1.83), const)]
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the slot held the last element, which is no longer part of the deque.
+        Some(unsafe { self.slot(self.len).read() })
+    }
+    /// Removes the first element of the deque, returning it if it wasn't empty.
+    #[rustversion::attr(since(1.83), const)]
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: the slot held the first element, which is no longer part of the deque.
+        let value = unsafe { self.slot(0).read() };
+        self.head = self.physical(1);
+        self.len -= 1;
+        Some(value)
+    }
+    /// Returns a reference to the `index`th element of the deque.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        // SAFETY: the slots of elements within the deque's length are initialized.
+        (index < self.len).then(|| unsafe { &*self.slot(index) })
+    }
+    /// Returns a mutable reference to the `index`th element of the deque.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // SAFETY: the slots of elements within the deque's length are initialized.
+        (index < self.len).then(|| unsafe { &mut *self.slot(index) })
+    }
+    /// Returns a reference to the first element of the deque.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+    /// Returns a mutable reference to the first element of the deque.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+    /// Returns a reference to the last element of the deque.
+    pub fn back(&self) -> Option<&T> {
+        self.get(self.len.wrapping_sub(1))
+    }
+    /// Returns a mutable reference to the last element of the deque.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.len.wrapping_sub(1))
+    }
+    /// Returns the ranges of the buffer occupied by the front and the back of the deque.
+    const fn ranges(&self) -> (core::ops::Range<usize>, core::ops::Range<usize>) {
+        let head_len = self.capacity - self.head;
+        if self.len <= head_len {
+            (self.head..self.head + self.len, 0..0)
+        } else {
+            (self.head..self.capacity, 0..self.len - head_len)
+        }
+    }
+    /// Returns the elements of the deque, in order, as two slices.
+    ///
+    /// The second slice is only non-empty if the elements wrap around the end of the buffer.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.ranges();
+        let start = self.start.ptr.as_ptr();
+        // SAFETY: both ranges are within the buffer, and only contain initialized elements.
+        unsafe {
+            (
+                core::slice::from_raw_parts(start.add(front.start), front.len()),
+                core::slice::from_raw_parts(start.add(back.start), back.len()),
+            )
+        }
+    }
+    /// Returns the elements of the deque, in order, as two mutable slices.
+    ///
+    /// The second slice is only non-empty if the elements wrap around the end of the buffer.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.ranges();
+        let start = self.start.ptr.as_ptr();
+        // SAFETY: both ranges are within the buffer, disjoint, and only contain initialized elements.
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(start.add(front.start), front.len()),
+                core::slice::from_raw_parts_mut(start.add(back.start), back.len()),
+            )
+        }
+    }
+    /// Moves the elements of the deque so that they're contiguous in its buffer, returning them as a single slice.
+    ///
+    /// This doesn't allocate, and doesn't move anything if the elements didn't wrap around the end of the buffer.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if Self::ZST {
+            self.head = 0;
+        } else if self.len > self.capacity - self.head {
+            // SAFETY: the buffer is viewed as potentially uninitialized memory, so rotating it is sound.
+            unsafe {
+                core::slice::from_raw_parts_mut(
+                    self.start.ptr.as_ptr().cast::<MaybeUninit<T>>(),
+                    self.capacity,
+                )
+            }
+            .rotate_left(self.head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+    /// Returns an iterator over the elements of the deque, from front to back.
+    pub fn iter(&self) -> core::iter::Chain<core::slice::Iter<'_, T>, core::slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+    /// Returns an iterator over mutable references to the elements of the deque, from front to back.
+    pub fn iter_mut(
+        &mut self,
+    ) -> core::iter::Chain<core::slice::IterMut<'_, T>, core::slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back)
+    }
+    /// Removes all elements from the deque, keeping its allocation.
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        // The length is updated first so that a panicking destructor can't cause double drops.
+        self.len = 0;
+        self.head = 0;
+        // SAFETY: the elements are no longer part of the deque.
+        unsafe {
+            core::ptr::drop_in_place(front);
+            core::ptr::drop_in_place(back);
+        }
+    }
+}
+impl<T, Alloc: IAlloc> Drop for Deque<T, Alloc> {
+    fn drop(&mut self) {
+        self.clear();
+        if !Self::ZST && self.capacity != 0 {
+            // SAFETY: the buffer isn't dangling since it has some capacity.
+            unsafe { self.start.free(&mut self.alloc) }
+        }
+    }
+}
+impl<T: Clone, Alloc: IAlloc + Clone> Clone for Deque<T, Alloc> {
+    fn clone(&self) -> Self {
+        let mut clone = Self::with_capacity_in(self.len, self.alloc.clone());
+        clone.extend(self.iter().cloned());
+        clone
+    }
+}
+impl<T: Debug, Alloc: IAlloc> Debug for Deque<T, Alloc> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T: PartialEq, Alloc: IAlloc> PartialEq for Deque<T, Alloc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+impl<T: Eq, Alloc: IAlloc> Eq for Deque<T, Alloc> {}
+impl<T, Alloc: IAlloc> core::ops::Index<usize> for Deque<T, Alloc> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("Index out of bounds")
+    }
+}
+impl<T, Alloc: IAlloc> core::ops::IndexMut<usize> for Deque<T, Alloc> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("Index out of bounds")
+    }
+}
+impl<T, Alloc: IAlloc> Extend<T> for Deque<T, Alloc> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+impl<T, Alloc: IAlloc + Default> FromIterator<T> for Deque<T, Alloc> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::default();
+        deque.extend(iter);
+        deque
+    }
+}
+impl<'a, T, Alloc: IAlloc> IntoIterator for &'a Deque<T, Alloc> {
+    type Item = &'a T;
+    type IntoIter = core::iter::Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T, Alloc: IAlloc> IntoIterator for &'a mut Deque<T, Alloc> {
+    type Item = &'a mut T;
+    type IntoIter = core::iter::Chain<core::slice::IterMut<'a, T>, core::slice::IterMut<'a, T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+impl<T, Alloc: IAlloc> IntoIterator for Deque<T, Alloc> {
+    type Item = T;
+    type IntoIter = IntoIter<T, Alloc>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { deque: self }
+    }
+}
+
+/// An owning iterator over the elements of a [`Deque`].
+pub struct IntoIter<T, Alloc: IAlloc> {
+    deque: Deque<T, Alloc>,
+}
+impl<T, Alloc: IAlloc> Iterator for IntoIter<T, Alloc> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len, Some(self.deque.len))
+    }
+}
+impl<T, Alloc: IAlloc> DoubleEndedIterator for IntoIter<T, Alloc> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+impl<T, Alloc: IAlloc> ExactSizeIterator for IntoIter<T, Alloc> {}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn deque() {
+    use crate::alloc::sync::Arc;
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let mut deque = Deque::new();
+    let mut reference = std::collections::VecDeque::new();
+    for i in 0..10_000 {
+        match rng.gen_range(0..5) {
+            0 | 1 => {
+                deque.push_back(Arc::new(i));
+                reference.push_back(i);
+            }
+            2 => {
+                deque.push_front(Arc::new(i));
+                reference.push_front(i);
+            }
+            3 => assert_eq!(deque.pop_front().map(|v| *v), reference.pop_front()),
+            _ => assert_eq!(deque.pop_back().map(|v| *v), reference.pop_back()),
+        }
+        assert_eq!(deque.len(), reference.len());
+        if i % 100 == 0 {
+            let (front, back) = deque.as_slices();
+            assert!(front
+                .iter()
+                .chain(back)
+                .map(|v| **v)
+                .eq(reference.iter().copied()));
+        }
+    }
+    assert_eq!(deque.front().map(|v| **v), reference.front().copied());
+    assert_eq!(deque.back().map(|v| **v), reference.back().copied());
+    assert!(deque.iter().map(|v| **v).eq(reference.iter().copied()));
+
+    // Wrap the elements around the end of the buffer, and check that growing and `make_contiguous` preserve them.
+    let mut deque = Deque::with_capacity(8);
+    for i in 0..6 {
+        deque.push_back(i);
+    }
+    for i in 0..4 {
+        assert_eq!(deque.pop_front(), Some(i));
+    }
+    for i in 6..12 {
+        deque.push_back(i);
+    }
+    assert_eq!(deque.capacity(), 8);
+    assert!(!deque.as_slices().1.is_empty());
+    let clone = deque.clone();
+    deque.reserve(1);
+    assert!(deque.iter().copied().eq(4..12));
+    assert_eq!(deque, clone);
+    let mut clone = clone;
+    assert_eq!(clone.make_contiguous(), &[4, 5, 6, 7, 8, 9, 10, 11]);
+    assert!(clone.as_slices().1.is_empty());
+    assert_eq!(clone[3], 7);
+    assert!(clone.into_iter().rev().eq((4..12).rev()));
+
+    let mut zsts = (0..100).map(|_| ()).collect::<Deque<_>>();
+    assert_eq!(zsts.len(), 100);
+    assert_eq!(zsts.pop_front(), Some(()));
+    assert_eq!(zsts.make_contiguous().len(), 99);
+
+    let value = Arc::new(0);
+    let mut deque = Deque::new();
+    for _ in 0..10 {
+        deque.push_front(value.clone());
+        deque.push_back(value.clone());
+    }
+    assert_eq!(Arc::strong_count(&value), 21);
+    drop(deque);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
@@ -0,0 +1,703 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash, Hasher},
+    mem::MaybeUninit,
+};
+
+use crate::alloc::{vec::Vec, AllocationError, DefaultAllocator, IAlloc};
+
+/// The state of a [SipHash](https://en.wikipedia.org/wiki/SipHash) hasher, with `C` compression rounds and `D` finalization rounds.
+#[derive(Clone, Debug)]
+struct Sip<const C: usize, const D: usize> {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    tail: u64,
+    ntail: usize,
+    length: usize,
+}
+impl<const C: usize, const D: usize> Sip<C, D> {
+    const fn new(k0: u64, k1: u64) -> Self {
+        Self {
+            v0: k0 ^ 0x736f6d6570736575,
+            v1: k1 ^ 0x646f72616e646f6d,
+            v2: k0 ^ 0x6c7967656e657261,
+            v3: k1 ^ 0x7465646279746573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+    #[rustversion::attr(since(1.83), const)]
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+    fn compress(&mut self, m: u64) {
+        self.v3 ^= m;
+        for _ in 0..C {
+            self.round();
+        }
+        self.v0 ^= m;
+    }
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len());
+        while self.ntail != 0 {
+            let Some((&byte, rest)) = bytes.split_first() else {
+                return;
+            };
+            self.tail |= u64::from(byte) << (8 * self.ntail);
+            bytes = rest;
+            self.ntail += 1;
+            if self.ntail == 8 {
+                self.compress(self.tail);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0; 8];
+            word.copy_from_slice(chunk);
+            self.compress(u64::from_le_bytes(word));
+        }
+        for &byte in chunks.remainder() {
+            self.tail |= u64::from(byte) << (8 * self.ntail);
+            self.ntail += 1;
+        }
+    }
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        state.compress(((self.length as u64 & 0xff) << 56) | self.tail);
+        state.v2 ^= 0xff;
+        for _ in 0..D {
+            state.round();
+        }
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
+
+/// The SipHash-1-3 hasher, which is the default hasher for [`HashMap`].
+///
+/// Unlike [`core::hash::SipHasher`], this hasher's algorithm is fixed: it is guaranteed to hash a given stream of
+/// bytes the same way across versions.
+///
+/// This doesn't extend to hashing values: their [`Hash`] implementations decide which bytes they
+/// write, which may change across Rust versions, and integers such as `usize` are written with the target's native
+/// width and endianness.
+#[derive(Clone, Debug)]
+pub struct SipHasher13 {
+    state: Sip<1, 3>,
+}
+impl SipHasher13 {
+    /// Constructs a hasher keyed with `k0` and `k1`.
+    pub const fn new_with_keys(k0: u64, k1: u64) -> Self {
+        Self {
+            state: Sip::new(k0, k1),
+        }
+    }
+}
+impl Hasher for SipHasher13 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.state.write(bytes)
+    }
+    fn finish(&self) -> u64 {
+        self.state.finish()
+    }
+}
+
+/// A [`BuildHasher`] for [`SipHasher13`], storing the seed the hashers are keyed with.
+///
+/// The [`Default`] seed is a constant, making hashes deterministic: if your keys may be picked by an attacker,
+/// use [`SipBuildHasher::new`] with a random seed to protect your maps against HashDoS.
+#[crate::stabby]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SipBuildHasher {
+    k0: u64,
+    k1: u64,
+}
+impl SipBuildHasher {
+    /// Constructs a [`BuildHasher`] whose hashers will be keyed with `k0` and `k1`.
+    pub const fn new(k0: u64, k1: u64) -> Self {
+        Self { k0, k1 }
+    }
+}
+impl BuildHasher for SipBuildHasher {
+    type Hasher = SipHasher13;
+    fn build_hasher(&self) -> Self::Hasher {
+        SipHasher13::new_with_keys(self.k0, self.k1)
+    }
+}
+
+/// The marker for slots that have never been occupied, which end probe sequences.
+const EMPTY: usize = 0;
+/// The marker for slots whose entry has been removed, which don't end probe sequences.
+const TOMBSTONE: usize = 1;
+
+mod seal {
+    use super::{EMPTY, TOMBSTONE};
+    use core::mem::MaybeUninit;
+    /// A slot of a [`HashMap`](super::HashMap): `key` and `value` are initialized iff `hash` is neither [`EMPTY`] nor [`TOMBSTONE`].
+    #[crate::stabby]
+    pub struct Slot<K, V> {
+        pub(super) hash: usize,
+        pub(super) key: MaybeUninit<K>,
+        pub(super) value: MaybeUninit<V>,
+    }
+    impl<K, V> Slot<K, V> {
+        pub(super) const fn empty() -> Self {
+            Self {
+                hash: EMPTY,
+                key: MaybeUninit::uninit(),
+                value: MaybeUninit::uninit(),
+            }
+        }
+        pub(super) const fn is_occupied(&self) -> bool {
+            self.hash > TOMBSTONE
+        }
+        /// # Safety
+        /// `self` must be occupied.
+        pub(super) const unsafe fn key(&self) -> &K {
+            unsafe { self.key.assume_init_ref() }
+        }
+        /// # Safety
+        /// `self` must be occupied.
+        #[rustversion::attr(since(1.83), const)]
+        pub(super) unsafe fn entry_mut(&mut self) -> (&K, &mut V) {
+            unsafe { (self.key.assume_init_ref(), self.value.assume_init_mut()) }
+        }
+    }
+}
+use seal::Slot;
+
+/// An ABI-stable hash map, using open-addressing with linear probing.
+///
+/// Entries are stored in a single [`Vec`] of slots whose length is always a power of 2,
+/// which is grown (and cleaned up from removed entries) whenever it would become more than 7/8th full.
+///
+/// Keys are hashed using `S`, which defaults to [`SipBuildHasher`]. Unlike [`std::collections::HashMap`],
+/// the default hasher isn't randomly seeded: if your keys may be picked by an attacker, use
+/// [`HashMap::with_seed_in`] with a random seed to protect against HashDoS.
+/// ```
+/// # use stabby_abi::alloc::collections::hash_map::HashMap;
+/// let mut map = HashMap::<u32, u32>::with_seed(0xdead, 0xbeef);
+/// assert_eq!(map.insert(1, 2), None);
+/// assert_eq!(map.insert(1, 3), Some(2));
+/// assert_eq!(map.get(&1), Some(&3));
+/// assert_eq!(map.remove(&1), Some(3));
+/// assert!(map.is_empty());
+/// ```
+#[crate::stabby]
+pub struct HashMap<K, V, Alloc: IAlloc = DefaultAllocator, S = SipBuildHasher> {
+    slots: Vec<Slot<K, V>, Alloc>,
+    len: usize,
+    tombstones: usize,
+    hasher: S,
+}
+
+#[cfg(not(stabby_default_alloc = "disabled"))]
+impl<K, V> HashMap<K, V> {
+    /// Constructs an empty map in the default allocator, using the default seed. This doesn't allocate.
+    pub const fn new() -> Self {
+        Self::with_hasher_in(
+            SipBuildHasher::new(0, 0),
+            crate::alloc::DefaultAllocator::new(),
+        )
+    }
+    /// Constructs an empty map in the default allocator, seeding its hasher with `k0` and `k1`. This doesn't allocate.
+    pub const fn with_seed(k0: u64, k1: u64) -> Self {
+        Self::with_seed_in(k0, k1, crate::alloc::DefaultAllocator::new())
+    }
+}
+impl<K, V, Alloc: IAlloc> HashMap<K, V, Alloc> {
+    /// Constructs an empty map in `alloc`, seeding its hasher with `k0` and `k1`. This doesn't allocate.
+    pub const fn with_seed_in(k0: u64, k1: u64, alloc: Alloc) -> Self {
+        Self::with_hasher_in(SipBuildHasher::new(k0, k1), alloc)
+    }
+}
+impl<K, V, Alloc: IAlloc, S: Default> HashMap<K, V, Alloc, S> {
+    /// Constructs an empty map in `alloc`. This doesn't allocate.
+    pub fn new_in(alloc: Alloc) -> Self {
+        Self::with_hasher_in(S::default(), alloc)
+    }
+}
+impl<K, V, Alloc: IAlloc + Default, S: Default> Default for HashMap<K, V, Alloc, S> {
+    fn default() -> Self {
+        Self::new_in(Alloc::default())
+    }
+}
+impl<K, V, Alloc: IAlloc, S> HashMap<K, V, Alloc, S> {
+    /// Constructs an empty map in `alloc`, which will hash its keys using `hasher`. This doesn't allocate.
+    pub const fn with_hasher_in(hasher: S, alloc: Alloc) -> Self {
+        Self {
+            slots: Vec::new_in(alloc),
+            len: 0,
+            tombstones: 0,
+            hasher,
+        }
+    }
+    /// Returns the number of entries in the map.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+    /// Returns `true` if the map contains no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the number of entries the map can hold before growing.
+    pub const fn capacity(&self) -> usize {
+        self.slots.len() / 8 * 7
+    }
+    /// Returns a reference to the map's [`BuildHasher`].
+    pub const fn hasher(&self) -> &S {
+        &self.hasher
+    }
+    /// Returns a reference to the map's allocator.
+    pub const fn allocator(&self) -> &Alloc {
+        self.slots.allocator()
+    }
+    /// Removes all entries from the map, keeping its allocation.
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            if slot.is_occupied() {
+                // SAFETY: the slot is occupied, and is marked empty right away.
+                unsafe {
+                    slot.key.assume_init_drop();
+                    slot.value.assume_init_drop();
+                }
+            }
+            slot.hash = EMPTY;
+        }
+        self.len = 0;
+        self.tombstones = 0;
+    }
+    /// Returns an iterator over the entries of the map, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            remaining: self.len,
+        }
+    }
+    /// Returns an iterator over the entries of the map, in arbitrary order, with mutable access to the values.
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            remaining: self.len,
+        }
+    }
+    /// Returns an iterator over the keys of the map, in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+    /// Returns an iterator over the values of the map, in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+    const fn mask(&self) -> usize {
+        self.slots.len().wrapping_sub(1)
+    }
+    /// Returns the index of the first slot from `hash`'s ideal slot which doesn't hold an entry.
+    fn free_slot(&self, hash: usize) -> usize {
+        let mask = self.mask();
+        let mut index = hash & mask;
+        while self.slots[index].is_occupied() {
+            index = (index + 1) & mask;
+        }
+        index
+    }
+}
+impl<K: Hash + Eq, V, Alloc: IAlloc, S: BuildHasher> HashMap<K, V, Alloc, S> {
+    fn hash<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+        // Hashes that would collide with the markers are moved out of the way.
+        (self.hasher.hash_one(key) as usize).max(TOMBSTONE + 1)
+    }
+    /// Returns the index of the slot holding `key`.
+    fn find<Q: Hash + Eq + ?Sized>(&self, hash: usize, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        if self.slots.is_empty() {
+            return None;
+        }
+        let mask = self.mask();
+        let mut index = hash & mask;
+        // The map is never full, so probing will always run into an empty slot eventually.
+        loop {
+            let slot = &self.slots[index];
+            match slot.hash {
+                EMPTY => return None,
+                // SAFETY: the slot is occupied, since its hash isn't a marker.
+                h if h == hash && unsafe { slot.key() }.borrow() == key => return Some(index),
+                _ => index = (index + 1) & mask,
+            }
+        }
+    }
+    /// Returns the value associated to `key`.
+    pub fn get<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.find(self.hash(key), key)?;
+        // SAFETY: `find` only returns occupied slots.
+        Some(unsafe { self.slots[index].value.assume_init_ref() })
+    }
+    /// Returns a mutable reference to the value associated to `key`.
+    pub fn get_mut<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.find(self.hash(key), key)?;
+        // SAFETY: `find` only returns occupied slots.
+        Some(unsafe { self.slots[index].value.assume_init_mut() })
+    }
+    /// Returns `true` if the map contains a value for `key`.
+    pub fn contains_key<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.find(self.hash(key), key).is_some()
+    }
+    /// Associates `value` to `key`, returning the value previously associated to `key` if any.
+    ///
+    /// # Panics
+    /// In case of allocation failure.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        Alloc: Clone,
+    {
+        match self.try_insert(key, value) {
+            Ok(previous) => previous,
+            Err(_) => panic!("Failed to insert because reallocation failed."),
+        }
+    }
+    /// Associates `value` to `key`, returning the value previously associated to `key` if any.
+    ///
+    /// # Errors
+    /// Gives back `key` and `value` if the map needed to grow, and the allocator failed to provide a new allocation.
+    ///
+    /// `self` is still valid should that happen.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)>
+    where
+        Alloc: Clone,
+    {
+        let hash = self.hash(&key);
+        if let Some(index) = self.find(hash, &key) {
+            // SAFETY: `find` only returns occupied slots.
+            let previous = unsafe { self.slots[index].value.assume_init_mut() };
+            return Ok(Some(core::mem::replace(previous, value)));
+        }
+        if self.try_reserve(1).is_err() {
+            return Err((key, value));
+        }
+        let index = self.free_slot(hash);
+        let slot = &mut self.slots[index];
+        if slot.hash == TOMBSTONE {
+            self.tombstones -= 1;
+        }
+        *slot = Slot {
+            hash,
+            key: MaybeUninit::new(key),
+            value: MaybeUninit::new(value),
+        };
+        self.len += 1;
+        Ok(None)
+    }
+    /// Removes the entry for `key` from the map, returning its value if it was present.
+    pub fn remove<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+    /// Removes the entry for `key` from the map, returning it if it was present.
+    pub fn remove_entry<Q: Hash + Eq + ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+    {
+        let index = self.find(self.hash(key), key)?;
+        // Probe sequences going through this slot only need to keep going if the next slot isn't empty.
+        let marker = if self.slots[(index + 1) & self.mask()].hash == EMPTY {
+            EMPTY
+        } else {
+            self.tombstones += 1;
+            TOMBSTONE
+        };
+        let slot = &mut self.slots[index];
+        slot.hash = marker;
+        self.len -= 1;
+        // SAFETY: `find` only returns occupied slots, and the slot has just been marked as free.
+        unsafe { Some((slot.key.assume_init_read(), slot.value.assume_init_read())) }
+    }
+    /// Ensures that `additional` more entries can be inserted in `self` without reallocating.
+    ///
+    /// # Panics
+    /// In case of allocation failure.
+    pub fn reserve(&mut self, additional: usize)
+    where
+        Alloc: Clone,
+    {
+        self.try_reserve(additional).unwrap();
+    }
+    /// Ensures that `additional` more entries can be inserted in `self` without reallocating.
+    ///
+    /// If the map is cluttered by removed entries, this may also rehash it in a new allocation no larger than the
+    /// current one.
+    ///
+    /// # Errors
+    /// Returns an [`AllocationError`] if the map needed to reallocate and the allocator failed to provide a new allocation.
+    ///
+    /// `self` is still valid should that happen.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocationError>
+    where
+        Alloc: Clone,
+    {
+        let needed = self.len.checked_add(additional).ok_or(AllocationError())?;
+        let used = needed
+            .checked_add(self.tombstones)
+            .ok_or(AllocationError())?;
+        if used <= self.capacity() {
+            return Ok(());
+        }
+        let mut slot_count = 8usize;
+        while slot_count / 8 * 7 < needed {
+            slot_count = slot_count.checked_mul(2).ok_or(AllocationError())?;
+        }
+        let mut slots = Vec::try_with_capacity_in(slot_count, self.allocator().clone())
+            .map_err(|_| AllocationError())?;
+        for _ in 0..slot_count {
+            slots.push(Slot::empty());
+        }
+        let previous = core::mem::replace(&mut self.slots, slots);
+        for slot in previous.iter().filter(|slot| slot.is_occupied()) {
+            let index = self.free_slot(slot.hash);
+            // SAFETY: the entry is moved to its new slot, and `previous` doesn't drop the entries of its slots.
+            self.slots[index] = unsafe { core::ptr::read(slot) };
+        }
+        self.tombstones = 0;
+        Ok(())
+    }
+}
+impl<K, V, Alloc: IAlloc, S> Drop for HashMap<K, V, Alloc, S> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+impl<K: Clone, V: Clone, Alloc: IAlloc + Clone, S: Clone> Clone for HashMap<K, V, Alloc, S> {
+    fn clone(&self) -> Self {
+        let mut slots = Vec::with_capacity_in(self.slots.len(), self.allocator().clone());
+        for slot in self.slots.iter() {
+            slots.push(if slot.is_occupied() {
+                // SAFETY: the slot is occupied.
+                let (key, value) =
+                    unsafe { (slot.key.assume_init_ref(), slot.value.assume_init_ref()) };
+                Slot {
+                    hash: slot.hash,
+                    key: MaybeUninit::new(key.clone()),
+                    value: MaybeUninit::new(value.clone()),
+                }
+            } else {
+                Slot {
+                    hash: slot.hash,
+                    ..Slot::empty()
+                }
+            });
+        }
+        Self {
+            slots,
+            len: self.len,
+            tombstones: self.tombstones,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+impl<K: core::fmt::Debug, V: core::fmt::Debug, Alloc: IAlloc, S> core::fmt::Debug
+    for HashMap<K, V, Alloc, S>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<K: Hash + Eq, V: PartialEq, Alloc: IAlloc, S: BuildHasher> PartialEq
+    for HashMap<K, V, Alloc, S>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key).is_some_and(|other| value == other))
+    }
+}
+impl<K: Hash + Eq, V: Eq, Alloc: IAlloc, S: BuildHasher> Eq for HashMap<K, V, Alloc, S> {}
+impl<K: Hash + Eq, V, Alloc: IAlloc + Clone, S: BuildHasher> Extend<(K, V)>
+    for HashMap<K, V, Alloc, S>
+{
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+impl<K: Hash + Eq, V, Alloc: IAlloc + Clone + Default, S: BuildHasher + Default>
+    FromIterator<(K, V)> for HashMap<K, V, Alloc, S>
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+impl<'a, K, V, Alloc: IAlloc, S> IntoIterator for &'a HashMap<K, V, Alloc, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, K, V, Alloc: IAlloc, S> IntoIterator for &'a mut HashMap<K, V, Alloc, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the entries of a [`HashMap`].
+pub struct Iter<'a, K, V> {
+    slots: core::slice::Iter<'a, Slot<K, V>>,
+    remaining: usize,
+}
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.slots.find(|slot| slot.is_occupied())?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied.
+        unsafe { Some((slot.key(), slot.value.assume_init_ref())) }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// An iterator over the entries of a [`HashMap`], with mutable access to the values.
+pub struct IterMut<'a, K, V> {
+    slots: core::slice::IterMut<'a, Slot<K, V>>,
+    remaining: usize,
+}
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+    fn next(&mut self) -> Option<Self::Item> {
+        let slot = self.slots.find(|slot| slot.is_occupied())?;
+        self.remaining -= 1;
+        // SAFETY: the slot is occupied.
+        Some(unsafe { slot.entry_mut() })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+#[test]
+#[allow(deprecated)]
+fn siphash() {
+    let inputs: [&[u8]; 4] = [b"", b"a", b"0123456789abcdef", b"the quick brown fox jumps"];
+    for input in inputs {
+        let mut reference = core::hash::SipHasher::new_with_keys(1, 2);
+        reference.write(input);
+        let mut sip = Sip::<2, 4>::new(1, 2);
+        sip.write(input);
+        assert_eq!(sip.finish(), reference.finish());
+        // Feeding the input in several writes must not change the hash.
+        for split in 0..input.len() {
+            let mut hasher = SipHasher13::new_with_keys(1, 2);
+            hasher.write(&input[..split]);
+            hasher.write(&input[split..]);
+            let mut whole = SipHasher13::new_with_keys(1, 2);
+            whole.write(input);
+            assert_eq!(hasher.finish(), whole.finish());
+        }
+    }
+    let hash = |seed: SipBuildHasher| seed.hash_one(42u64);
+    assert_ne!(
+        hash(SipBuildHasher::new(0, 0)),
+        hash(SipBuildHasher::new(0, 1))
+    );
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn hash_map() {
+    use crate::alloc::sync::Arc;
+    use rand::Rng;
+    let mut rng = rand::thread_rng();
+    let mut map = HashMap::<u16, Arc<u16>>::with_seed(rng.gen(), rng.gen());
+    let mut reference = std::collections::HashMap::new();
+    for _ in 0..10_000 {
+        let key = rng.gen_range(0..512);
+        if rng.gen_bool(0.6) {
+            let value = Arc::new(rng.gen::<u16>());
+            assert_eq!(
+                map.insert(key, value.clone()).map(|v| *v),
+                reference.insert(key, value).map(|v| *v)
+            );
+        } else {
+            assert_eq!(
+                map.remove(&key).map(|v| *v),
+                reference.remove(&key).map(|v| *v)
+            );
+        }
+        assert_eq!(map.len(), reference.len());
+    }
+    for key in 0..512 {
+        assert_eq!(map.get(&key).map(|v| **v), reference.get(&key).map(|v| **v));
+    }
+    assert_eq!(map.iter().len(), reference.len());
+    for (key, value) in map.iter() {
+        assert!(Arc::ptr_eq(value, &reference[key]));
+    }
+    for (_, value) in map.iter_mut() {
+        *value = Arc::new(0);
+    }
+    assert!(map.values().all(|value| **value == 0));
+    let clone = map.clone();
+    assert_eq!(clone.len(), map.len());
+    assert!(clone
+        .iter()
+        .all(|(key, value)| map.get(key).is_some_and(|other| Arc::ptr_eq(value, other))));
+    // Values are only referenced by `reference` once `map` and `clone` have been cleared.
+    map.clear();
+    drop(clone);
+    assert!(map.is_empty());
+    assert!(reference
+        .values()
+        .all(|value| Arc::strong_count(value) == 1));
+    let map = (0..100u16)
+        .map(|key| (key, Arc::new(key)))
+        .collect::<HashMap<_, _>>();
+    assert!((0..100).all(|key| map.get(&key).is_some_and(|value| **value == key)));
+}
@@ -5,3 +5,21 @@
 /// Mutating an ArcBTree that whose ownership is shared will result in every node from the insertion spot
 /// to the root to be copied, while the remaining nodes will just see their reference counts increase.
 pub mod arc_btree;
+
+/// An ABI-stable hash map
+///
+/// [`HashMap`](hash_map::HashMap) uses open-addressing over a single [`Vec`](crate::alloc::vec::Vec) of slots,
+/// and hashes its keys with a seedable SipHash-1-3 that doesn't depend on `std`.
+pub mod hash_map;
+
+/// Immutable maps built for sharing
+///
+/// [`ArcMap`](arc_map::ArcMap) stores its entries in a sorted [`ArcSlice`](crate::alloc::sync::ArcSlice),
+/// making lookups a binary search away, and clones a reference count increment.
+pub mod arc_map;
+
+/// A double-ended queue
+///
+/// [`Deque`] is a growable ring buffer, allowing to push and pop elements at both ends in amortized constant time.
+pub mod deque;
+pub use deque::Deque;
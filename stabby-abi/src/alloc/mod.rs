@@ -15,7 +15,7 @@
 #![allow(deprecated)]
 use core::{marker::PhantomData, mem::MaybeUninit, ptr::NonNull, sync::atomic::AtomicUsize};
 
-use self::vec::ptr_diff;
+pub use self::vec::{ptr_add, ptr_diff};
 
 /// Allocators provided by `stabby`
 pub mod allocators;
@@ -29,9 +29,104 @@ impl core::fmt::Display for AllocationError {
         f.write_str("AllocationError")
     }
 }
+#[rustversion::since(1.81)]
+impl core::error::Error for AllocationError {}
+#[rustversion::before(1.81)]
 #[cfg(feature = "std")]
 impl std::error::Error for AllocationError {}
 
+/// The reason why [`IAlloc::try_alloc`] failed.
+#[crate::stabby]
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TryAllocError {
+    /// The allocator couldn't provide enough memory.
+    OutOfMemory,
+    /// The allocator can't provide memory with the requested alignment.
+    UnsupportedAlignment,
+}
+impl core::fmt::Display for TryAllocError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::OutOfMemory => "AllocationError: out of memory",
+            Self::UnsupportedAlignment => "AllocationError: unsupported alignment",
+        })
+    }
+}
+#[rustversion::since(1.81)]
+impl core::error::Error for TryAllocError {}
+#[rustversion::before(1.81)]
+#[cfg(feature = "std")]
+impl std::error::Error for TryAllocError {}
+impl From<TryAllocError> for AllocationError {
+    fn from(_: TryAllocError) -> Self {
+        AllocationError()
+    }
+}
+
+/// Formats each of `items` with `fmt`, in the manner of a hex-dump.
+///
+/// Items are written contiguously, unless the alternate flag (`#`) is set, in which case they are separated by `:`.
+fn fmt_hex<T>(
+    items: &[T],
+    f: &mut core::fmt::Formatter<'_>,
+    fmt: fn(&T, &mut core::fmt::Formatter<'_>) -> core::fmt::Result,
+) -> core::fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 && f.alternate() {
+            f.write_str(":")?;
+        }
+        fmt(item, f)?;
+    }
+    Ok(())
+}
+
+/// Implements the formatting traits for a smart-pointer by forwarding them to its pointee.
+macro_rules! fmt_passthrough {
+    ($ptr: ident: $($trait: ident),*) => {
+        $(impl<T: core::fmt::$trait, Alloc: IAlloc> core::fmt::$trait for $ptr<T, Alloc> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::$trait::fmt(&**self, f)
+            }
+        })*
+    };
+    ($ptr: ident) => {
+        fmt_passthrough!($ptr: Debug, Display, LowerHex, UpperHex, Binary);
+    };
+}
+
+/// Implements the comparisons between `$slice` and slices or arrays that its `PartialEq<Rhs: AsRef<[T]>>` impl can't
+/// cover: against unsized slices, and with `$slice` on the right-hand side.
+macro_rules! slice_eq {
+    ($slice: ident) => {
+        impl<T: PartialEq, Alloc: IAlloc> PartialEq<[T]> for $slice<T, Alloc> {
+            fn eq(&self, other: &[T]) -> bool {
+                self.as_slice() == other
+            }
+        }
+        impl<T: PartialEq, Alloc: IAlloc> PartialEq<$slice<T, Alloc>> for [T] {
+            fn eq(&self, other: &$slice<T, Alloc>) -> bool {
+                self == other.as_slice()
+            }
+        }
+        impl<T: PartialEq, Alloc: IAlloc> PartialEq<$slice<T, Alloc>> for &[T] {
+            fn eq(&self, other: &$slice<T, Alloc>) -> bool {
+                *self == other.as_slice()
+            }
+        }
+        impl<T: PartialEq, Alloc: IAlloc> PartialEq<$slice<T, Alloc>> for &mut [T] {
+            fn eq(&self, other: &$slice<T, Alloc>) -> bool {
+                *self == other.as_slice()
+            }
+        }
+        impl<T: PartialEq, Alloc: IAlloc, const N: usize> PartialEq<$slice<T, Alloc>> for [T; N] {
+            fn eq(&self, other: &$slice<T, Alloc>) -> bool {
+                self.as_slice() == other.as_slice()
+            }
+        }
+    };
+}
+
 /// [`alloc::boxed`](https://doc.rust-lang.org/stable/alloc/boxed/), but ABI-stable.
 pub mod boxed;
 /// Allocated collections, including immutable ones.
@@ -81,6 +176,14 @@ impl Layout {
             align,
         }
     }
+    /// Returns the [`Layout`] corresponding to `[T; n]`, or `None` if its size would overflow `usize`.
+    pub const fn array_checked<T: Sized>(n: usize) -> Option<Self> {
+        let Self { size, align } = Self::of::<T>();
+        match size.checked_mul(n) {
+            Some(size) => Some(Layout { size, align }),
+            None => None,
+        }
+    }
     /// Concatenates a layout to `self`, ensuring that alignment padding is taken into account.
     pub const fn concat(mut self, other: Self) -> Self {
         self.size += other.size;
@@ -90,6 +193,29 @@ impl Layout {
             self.align
         })
     }
+    /// Concatenates a layout to `self` like [`Self::concat`].
+    ///
+    /// Returns `None` if the resulting size would overflow `usize`, or if the resulting alignment isn't a power of 2.
+    pub const fn concat_checked(self, other: Self) -> Option<Self> {
+        let align = if self.align < other.align {
+            other.align
+        } else {
+            self.align
+        };
+        if !align.is_power_of_two() {
+            return None;
+        }
+        let Some(size) = self.size.checked_add(other.size) else {
+            return None;
+        };
+        match size.checked_add(align - 1) {
+            Some(padded) => Some(Layout {
+                size: padded & !(align - 1),
+                align,
+            }),
+            None => None,
+        }
+    }
     /// Returns the first pointer where `output >= ptr` such that `output % self.align == 0`.
     #[inline]
     pub fn next_matching<T>(self, ptr: *mut T) -> *mut T {
@@ -99,12 +225,51 @@ impl Layout {
         next_matching(self.align, ptr.cast()).cast()
     }
     /// Changes the alignment of the layout, adding padding if necessary.
+    ///
+    /// # Panics
+    /// If `new_align` isn't a power of 2.
     pub const fn realign(mut self, new_align: usize) -> Self {
+        self.size += self.padding_needed_for(new_align);
         self.align = new_align;
-        self.size = self.size
-            + (new_align - (self.size % new_align)) * (((self.size % new_align) != 0) as usize);
         self
     }
+    /// Returns the number of bytes needed to round `self.size` up to a multiple of `align`.
+    ///
+    /// # Panics
+    /// If `align` isn't a power of 2.
+    pub const fn padding_needed_for(&self, align: usize) -> usize {
+        assert!(
+            align.is_power_of_two(),
+            "Layout alignments must be non-zero powers of 2"
+        );
+        self.size.wrapping_neg() & (align - 1)
+    }
+    /// Returns a layout aligned to at least `align`, whose size is padded to a multiple of its new alignment.
+    ///
+    /// # Panics
+    /// If `align` isn't a power of 2.
+    pub const fn align_to(self, align: usize) -> Self {
+        self.realign(if self.align < align {
+            align
+        } else {
+            self.align
+        })
+    }
+}
+impl TryFrom<Layout> for core::alloc::Layout {
+    type Error = core::alloc::LayoutError;
+    /// Fails if `value.align` isn't a power of 2, or if `value.size` overflows `isize` once rounded up to `value.align`.
+    fn try_from(value: Layout) -> Result<Self, Self::Error> {
+        core::alloc::Layout::from_size_align(value.size, value.align)
+    }
+}
+impl From<core::alloc::Layout> for Layout {
+    fn from(value: core::alloc::Layout) -> Self {
+        Layout {
+            size: value.size(),
+            align: value.align(),
+        }
+    }
 }
 
 /// An interface to an allocator.
@@ -118,6 +283,35 @@ pub trait IAlloc: Unpin {
     ///
     /// If the requested size is 0, or allocation failed, then a null pointer is returned.
     fn alloc(&mut self, layout: Layout) -> *mut ();
+    /// Allocates at least as much memory as requested by layout, ensuring the requested alignment is respected.
+    ///
+    /// The default implementation rejects alignments that aren't powers of 2, and otherwise reports any null
+    /// pointer returned by [`Self::alloc`] as [`TryAllocError::OutOfMemory`]. Allocators that may fail for other
+    /// reasons, such as those that can't provide large alignments, should override it to report them.
+    ///
+    /// # Errors
+    /// Returns the reason why allocation failed. As with [`Self::alloc`], zero-sized requests fail.
+    fn try_alloc(&mut self, layout: Layout) -> Result<NonNull<()>, TryAllocError> {
+        if !layout.align.is_power_of_two() {
+            return Err(TryAllocError::UnsupportedAlignment);
+        }
+        NonNull::new(self.alloc(layout)).ok_or(TryAllocError::OutOfMemory)
+    }
+    /// Allocates at least as much memory as requested by layout, ensuring the requested alignment is respected,
+    /// and that the memory is zeroed.
+    ///
+    /// If the requested size is 0, or allocation failed, then a null pointer is returned.
+    ///
+    /// The default implementation zeroes the memory obtained from [`Self::alloc`]: allocators that can
+    /// provide zeroed memory more cheaply (such as those backed by `calloc` or `mmap`) should override it.
+    fn alloc_zeroed(&mut self, layout: Layout) -> *mut () {
+        let ptr = self.alloc(layout);
+        if !ptr.is_null() {
+            // SAFETY: `alloc` returned an allocation of at least `layout.size` bytes.
+            unsafe { core::ptr::write_bytes(ptr.cast::<u8>(), 0, layout.size) }
+        }
+        ptr
+    }
     /// Frees the allocation
     ///
     /// # Safety
@@ -136,12 +330,42 @@ pub trait IAlloc: Unpin {
         });
         if !ret.is_null() {
             unsafe {
-                core::ptr::copy_nonoverlapping(ptr.cast::<u8>(), ret.cast(), prev_layout.size);
+                core::ptr::copy_nonoverlapping(
+                    ptr.cast::<u8>(),
+                    ret.cast(),
+                    prev_layout.size.min(new_size),
+                );
                 self.free(ptr);
             }
         }
         ret
     }
+    /// Reallocates `ptr` to a larger allocation, fitting `new_layout`.
+    ///
+    /// This lets allocators that can cheaply grow some allocations in place (such as the latest allocation of
+    /// an arena) do so. The default implementation forwards to [`Self::realloc`].
+    ///
+    /// If allocation failed, then a null pointer is returned, and `ptr` is not freed.
+    ///
+    /// # Safety
+    /// `ptr` MUST have been allocated through a succesful call to `Self::alloc` with the same instance of `Self`,
+    /// and `new_layout.size` must be greater than or equal to `old_layout.size`.
+    unsafe fn grow(&mut self, ptr: *mut (), old_layout: Layout, new_layout: Layout) -> *mut () {
+        unsafe { self.realloc(ptr, old_layout, new_layout.size) }
+    }
+    /// Reallocates `ptr` to a smaller allocation, fitting `new_layout`.
+    ///
+    /// This lets allocators that can't reclaim part of an allocation avoid a copy by returning `ptr` unchanged.
+    /// The default implementation forwards to [`Self::realloc`].
+    ///
+    /// If the requested size is 0, or allocation failed, then a null pointer is returned, and `ptr` is not freed.
+    ///
+    /// # Safety
+    /// `ptr` MUST have been allocated through a succesful call to `Self::alloc` with the same instance of `Self`,
+    /// and `new_layout.size` must be smaller than or equal to `old_layout.size`.
+    unsafe fn shrink(&mut self, ptr: *mut (), old_layout: Layout, new_layout: Layout) -> *mut () {
+        unsafe { self.realloc(ptr, old_layout, new_layout.size) }
+    }
 }
 
 /// An ABI stable equivalent to [`IAlloc`].
@@ -175,7 +399,11 @@ pub trait IStableAlloc: Unpin {
         });
         if !ret.is_null() {
             unsafe {
-                core::ptr::copy_nonoverlapping(ptr.cast::<u8>(), ret.cast(), prev_layout.size);
+                core::ptr::copy_nonoverlapping(
+                    ptr.cast::<u8>(),
+                    ret.cast(),
+                    prev_layout.size.min(new_size),
+                );
                 self.free(ptr);
             }
         }
@@ -238,15 +466,12 @@ pub struct AllocPrefix<Alloc> {
 }
 impl<Alloc> AllocPrefix<Alloc> {
     /// The offset between the prefix and a field of type `T`.
+    ///
+    /// This is the prefix's size, rounded up to a multiple of `T`'s alignment: no padding is added
+    /// if the prefix's size is already a multiple of it.
     pub const fn skip_to<T>() -> usize {
-        let mut size = core::mem::size_of::<Self>();
-        let align = core::mem::align_of::<T>();
-        let sizemodalign = size % align;
-        if sizemodalign != 0 {
-            size += align;
-            size -= sizemodalign;
-        }
-        size
+        let prefix = Layout::of::<Self>();
+        prefix.size + prefix.padding_needed_for(core::mem::align_of::<T>())
     }
 }
 
@@ -302,7 +527,10 @@ impl<T, Alloc> AllocPtr<T, Alloc> {
             marker: PhantomData,
         }
     }
-    ///The pointer to the prefix for this allocation
+    /// The pointer to the prefix for this allocation.
+    ///
+    /// Since `self.ptr` is derived from the allocation's origin by [`Self::init`], it keeps the provenance of the
+    /// whole allocation, and stepping back to the prefix stays in bounds of it.
     const fn prefix_ptr(&self) -> NonNull<AllocPrefix<Alloc>> {
         unsafe { NonNull::new_unchecked(self.ptr.as_ptr().cast::<AllocPrefix<Alloc>>().sub(1)) }
     }
@@ -333,24 +561,26 @@ impl<T, Alloc> AllocPtr<T, Alloc> {
     /// Initializes any given pointer:
     /// - The returned pointer is guaranteed to be correctly aligned for `T`
     /// - It is guaranteed to preceded without padding by an `AllocPrefix<Alloc>`
+    /// - It is always [`AllocPrefix::skip_to`] bytes past `ptr`, so that reallocations keep the data in place.
     /// # Safety
-    /// `ptr` MUST be word-aligned, and MUST be valid for writes for at least the size of
+    /// `ptr` MUST be aligned for both `AllocPrefix<Alloc>` and `T`, and MUST be valid for writes for at least the size of
     /// `#[repr(C)] struct { prefix: AllocPrefix<Alloc>, data: [T; capacity] }`
+    ///
+    /// Both the data and prefix pointers are offset from `ptr` itself, rather than reconstructed from addresses,
+    /// so that they keep the provenance of the whole allocation.
+    #[rustversion::attr(since(1.83), const)]
     pub unsafe fn init(ptr: NonNull<()>, capacity: usize) -> Self {
-        let shifted_for_prefix = ptr
-            .as_ptr()
-            .cast::<AllocPrefix<Alloc>>()
-            .add(1)
-            .cast::<u8>();
-        let inited = shifted_for_prefix
-            .cast::<u8>()
-            .add(shifted_for_prefix.align_offset(core::mem::align_of::<T>()))
-            .cast::<T>();
+        let skip = AllocPrefix::<Alloc>::skip_to::<T>();
+        let origin = ptr.as_ptr().cast::<u8>();
         let this: Self = AllocPtr {
-            ptr: NonNull::new_unchecked(inited),
+            ptr: NonNull::new_unchecked(origin.add(skip).cast::<T>()),
             marker: core::marker::PhantomData,
         };
-        this.prefix_ptr().as_ptr().write(AllocPrefix {
+        // The prefix ends where the data starts, which `skip_to` guarantees is at least the prefix's size past `ptr`.
+        let prefix = origin
+            .add(skip - core::mem::size_of::<AllocPrefix<Alloc>>())
+            .cast::<AllocPrefix<Alloc>>();
+        prefix.write(AllocPrefix {
             strong: AtomicUsize::new(1),
             weak: AtomicUsize::new(1),
             capacity: AtomicUsize::new(capacity),
@@ -366,12 +596,50 @@ impl<T, Alloc: IAlloc> AllocPtr<T, Alloc> {
         Self::alloc_array(alloc, 1)
     }
     /// Allocates a pointer to an array of `capacity` `T`, prefixed by an [`AllocPrefix`]
+    ///
+    /// Returns `None` if allocation failed, or if the size of the allocation would overflow `usize`.
     pub fn alloc_array(alloc: &mut Alloc, capacity: usize) -> Option<Self> {
-        let mut layout = Layout::of::<AllocPrefix<Alloc>>().concat(Layout::array::<T>(capacity));
-        layout.align = core::mem::align_of::<AllocPrefix<Alloc>>();
-        let ptr = alloc.alloc(layout);
+        let ptr = alloc.alloc(Self::array_layout(capacity)?);
         NonNull::new(ptr).map(|ptr| unsafe { Self::init(ptr, capacity) })
     }
+    /// Allocates a pointer to a single element of `T`, prefixed by an [`AllocPrefix`].
+    /// # Errors
+    /// Returns the reason reported by [`IAlloc::try_alloc`] if allocation failed.
+    pub fn try_alloc(alloc: &mut Alloc) -> Result<Self, TryAllocError> {
+        Self::try_alloc_array(alloc, 1)
+    }
+    /// Allocates a pointer to an array of `capacity` `T`, prefixed by an [`AllocPrefix`].
+    /// # Errors
+    /// Returns the reason reported by [`IAlloc::try_alloc`] if allocation failed, or [`TryAllocError::OutOfMemory`]
+    /// if the size of the allocation would overflow `usize`.
+    pub fn try_alloc_array(alloc: &mut Alloc, capacity: usize) -> Result<Self, TryAllocError> {
+        let layout = Self::array_layout(capacity).ok_or(TryAllocError::OutOfMemory)?;
+        let ptr = alloc.try_alloc(layout)?;
+        Ok(unsafe { Self::init(ptr, capacity) })
+    }
+    /// Allocates a pointer to a single element of `T`, prefixed by an [`AllocPrefix`], whose memory is zeroed.
+    pub fn alloc_zeroed(alloc: &mut Alloc) -> Option<Self> {
+        Self::alloc_array_zeroed(alloc, 1)
+    }
+    /// Allocates a pointer to an array of `capacity` `T`, prefixed by an [`AllocPrefix`], whose memory is zeroed.
+    pub fn alloc_array_zeroed(alloc: &mut Alloc, capacity: usize) -> Option<Self> {
+        let ptr = alloc.alloc_zeroed(Self::array_layout(capacity)?);
+        NonNull::new(ptr).map(|ptr| unsafe { Self::init(ptr, capacity) })
+    }
+    /// The layout of an allocation for `capacity` `T`, or `None` if its size would overflow `usize`.
+    ///
+    /// The allocation is aligned for both the prefix and `T`, so that the data can always start [`AllocPrefix::skip_to`]
+    /// bytes after the allocation's start.
+    const fn array_layout(capacity: usize) -> Option<Layout> {
+        let Some(array) = Layout::array_checked::<T>(capacity) else {
+            return None;
+        };
+        let prefix = Layout {
+            size: AllocPrefix::<Alloc>::skip_to::<T>(),
+            align: core::mem::align_of::<AllocPrefix<Alloc>>(),
+        };
+        prefix.concat_checked(array)
+    }
     /// Reallocates a pointer to an array of `capacity` `T`, prefixed by an [`AllocPrefix`].
     ///
     /// In case of failure of the allocator, this will return `None` and `self` will not have been freed.
@@ -384,15 +652,48 @@ impl<T, Alloc: IAlloc> AllocPtr<T, Alloc> {
         prev_capacity: usize,
         new_capacity: usize,
     ) -> Option<Self> {
-        let mut layout =
-            Layout::of::<AllocPrefix<Alloc>>().concat(Layout::array::<T>(prev_capacity));
-        layout.align = core::mem::align_of::<AllocPrefix<Alloc>>();
         let ptr = alloc.realloc(
-            self.prefix_ptr().cast().as_ptr(),
-            layout,
-            Layout::of::<AllocPrefix<Alloc>>()
-                .concat(Layout::array::<T>(new_capacity))
-                .size,
+            self.prefix().origin.as_ptr(),
+            Self::array_layout(prev_capacity)?,
+            Self::array_layout(new_capacity)?.size,
+        );
+        NonNull::new(ptr).map(|ptr| unsafe { Self::init(ptr, new_capacity) })
+    }
+    /// Grows a pointer to an array of `prev_capacity` `T` into one of `new_capacity` `T`, using [`IAlloc::grow`].
+    ///
+    /// In case of failure of the allocator, this will return `None` and `self` will not have been freed.
+    ///
+    /// # Safety
+    /// `self` must not be dangling, and `new_capacity >= prev_capacity`
+    pub unsafe fn grow(
+        self,
+        alloc: &mut Alloc,
+        prev_capacity: usize,
+        new_capacity: usize,
+    ) -> Option<Self> {
+        let ptr = alloc.grow(
+            self.prefix().origin.as_ptr(),
+            Self::array_layout(prev_capacity)?,
+            Self::array_layout(new_capacity)?,
+        );
+        NonNull::new(ptr).map(|ptr| unsafe { Self::init(ptr, new_capacity) })
+    }
+    /// Shrinks a pointer to an array of `prev_capacity` `T` into one of `new_capacity` `T`, using [`IAlloc::shrink`].
+    ///
+    /// In case of failure of the allocator, this will return `None` and `self` will not have been freed.
+    ///
+    /// # Safety
+    /// `self` must not be dangling, and `new_capacity <= prev_capacity`
+    pub unsafe fn shrink(
+        self,
+        alloc: &mut Alloc,
+        prev_capacity: usize,
+        new_capacity: usize,
+    ) -> Option<Self> {
+        let ptr = alloc.shrink(
+            self.prefix().origin.as_ptr(),
+            Self::array_layout(prev_capacity)?,
+            Self::array_layout(new_capacity)?,
         );
         NonNull::new(ptr).map(|ptr| unsafe { Self::init(ptr, new_capacity) })
     }
@@ -414,24 +715,291 @@ pub struct AllocSlice<T, Alloc> {
     pub end: NonNull<T>,
 }
 impl<T, Alloc> AllocSlice<T, Alloc> {
+    /// Constructs a slice of `len` zero-sized elements starting at `start`.
+    ///
+    /// Zero-sized elements take no room, so `end` only serves as a counter: it is `start` offset by `len` bytes,
+    /// using wrapping arithmetic so that it keeps `start`'s provenance even though it lies outside of its allocation.
+    ///
+    /// # Panics
+    /// In debug builds, if `T` isn't zero-sized, or if `end` would wrap around the address space.
+    pub const fn from_zst_len(start: AllocPtr<T, Alloc>, len: usize) -> Self {
+        debug_assert!(core::mem::size_of::<T>() == 0);
+        debug_assert!(len <= usize::MAX - vec::addr(start.ptr));
+        // SAFETY: `start` is non-null, and `start + len` doesn't overflow.
+        let end =
+            unsafe { NonNull::new_unchecked(start.ptr.as_ptr().cast::<u8>().wrapping_add(len)) };
+        Self {
+            start,
+            end: end.cast(),
+        }
+    }
+    /// Returns the number of zero-sized elements in a slice constructed by [`Self::from_zst_len`].
+    ///
+    /// This is computed from the addresses of `start` and `end`, as `end` may lie outside of `start`'s allocation.
+    pub const fn zst_len(&self) -> usize {
+        vec::addr(self.end) - vec::addr(self.start.ptr)
+    }
     /// Returns the number of elements in the slice.
     pub const fn len(&self) -> usize {
-        ptr_diff(self.end, self.start.ptr)
+        if core::mem::size_of::<T>() == 0 {
+            return self.zst_len();
+        }
+        // SAFETY: `end` is always `len` elements past `start`.
+        unsafe { ptr_diff(self.end, self.start.ptr) }
     }
     /// Returns `true` if the slice is empty.
     pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
     /// Returns this slice.
+    ///
+    /// The slice is built from `start`, which keeps the provenance of its allocation; for zero-sized types, `end`
+    /// is only used for its address.
     /// # Safety
     /// `self` must be valid.
     pub const unsafe fn as_slice(&self) -> &[T] {
-        core::slice::from_raw_parts(self.start.ptr.as_ptr(), ptr_diff(self.end, self.start.ptr))
+        core::slice::from_raw_parts(self.start.ptr.as_ptr(), self.len())
     }
 }
+/// Reinterprets `slice` as a slice of `U`.
+///
+/// Empty slices are always accepted, as the pointer of an empty container may be dangling, and thus only aligned for `T`.
+#[cfg(feature = "bytemuck")]
+pub(crate) fn try_cast_slice<T: bytemuck::Pod, U: bytemuck::Pod>(
+    slice: &[T],
+) -> Result<&[U], bytemuck::PodCastError> {
+    if slice.is_empty() {
+        return Ok(&[]);
+    }
+    bytemuck::try_cast_slice(slice)
+}
 impl<T, Alloc> Copy for AllocSlice<T, Alloc> {}
 impl<T, Alloc> Clone for AllocSlice<T, Alloc> {
     fn clone(&self) -> Self {
         *self
     }
 }
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn try_alloc() {
+    let mut alloc = allocators::RustAlloc::new();
+    let ptr = alloc.try_alloc(Layout::of::<u64>()).unwrap();
+    unsafe { IAlloc::free(&mut alloc, ptr.as_ptr()) };
+    let misaligned = Layout { size: 8, align: 3 };
+    assert_eq!(
+        alloc.try_alloc(misaligned),
+        Err(TryAllocError::UnsupportedAlignment)
+    );
+    let huge = Layout {
+        size: usize::MAX / 2,
+        align: 8,
+    };
+    assert_eq!(alloc.try_alloc(huge), Err(TryAllocError::OutOfMemory));
+    assert_eq!(
+        AllocPtr::<u64, allocators::RustAlloc>::try_alloc_array(&mut alloc, usize::MAX / 32).err(),
+        Some(TryAllocError::OutOfMemory)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn default_realloc_shrinks() {
+    /// Hands out 16-byte blocks that are followed by 16 bytes that must stay untouched.
+    struct Blocks {
+        buffer: NonNull<[u8; 64]>,
+        next: usize,
+    }
+    impl IAlloc for Blocks {
+        fn alloc(&mut self, layout: Layout) -> *mut () {
+            if layout.size == 0 || layout.size > 16 || self.next == 2 {
+                return core::ptr::null_mut();
+            }
+            self.next += 1;
+            unsafe { self.buffer.as_ptr().cast::<u8>().add(32 * (self.next - 1)) }.cast()
+        }
+        unsafe fn free(&mut self, _ptr: *mut ()) {}
+    }
+    let buffer = NonNull::from(std::boxed::Box::leak(std::boxed::Box::new([0xaa; 64])));
+    let mut alloc = Blocks { buffer, next: 0 };
+    let layout = Layout { size: 16, align: 1 };
+    unsafe {
+        let ptr = IAlloc::alloc(&mut alloc, layout);
+        ptr.cast::<u8>().write_bytes(1, 16);
+        let shrunk = IAlloc::realloc(&mut alloc, ptr, layout, 8).cast::<u8>();
+        assert_eq!(shrunk, buffer.as_ptr().cast::<u8>().add(32));
+        let buffer = std::boxed::Box::from_raw(buffer.as_ptr());
+        assert_eq!(buffer[32..40], [1; 8]);
+        assert_eq!(buffer[40..], [0xaa; 24]);
+    }
+}
+#[rustversion::since(1.81)]
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn core_errors() {
+    fn check(error: &dyn core::error::Error, display: &str) {
+        assert_eq!(alloc_rs::format!("{error}"), display);
+    }
+    check(&AllocationError(), "AllocationError");
+    check(
+        &TryAllocError::OutOfMemory,
+        "AllocationError: out of memory",
+    );
+}
+#[test]
+fn skip_to() {
+    #[repr(align(64))]
+    struct Align64(#[allow(dead_code)] u8);
+    fn check<Alloc>() {
+        let prefix = core::mem::size_of::<AllocPrefix<Alloc>>();
+        for (align, skip) in [
+            (1, AllocPrefix::<Alloc>::skip_to::<u8>()),
+            (2, AllocPrefix::<Alloc>::skip_to::<u16>()),
+            (8, AllocPrefix::<Alloc>::skip_to::<u64>()),
+            (64, AllocPrefix::<Alloc>::skip_to::<Align64>()),
+        ] {
+            // The smallest offset past the prefix that's aligned for the data.
+            assert_eq!(skip % align, 0);
+            assert!(skip >= prefix && skip - prefix < align);
+        }
+    }
+    check::<()>();
+    check::<u8>();
+    check::<[u64; 3]>();
+    check::<[u8; 33]>();
+    let prefix = core::mem::size_of::<AllocPrefix<()>>();
+    assert_eq!(AllocPrefix::<()>::skip_to::<u64>(), prefix);
+    assert_eq!(AllocPrefix::<()>::skip_to::<Align64>(), 64);
+}
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn init_matches_skip_to() {
+    fn check<T>() {
+        let mut alloc = allocators::RustAlloc::new();
+        let ptr = AllocPtr::<T, allocators::RustAlloc>::alloc_array(&mut alloc, 3).unwrap();
+        let origin = unsafe { ptr.prefix() }.origin.as_ptr() as usize;
+        assert_eq!(
+            ptr.as_ptr() as usize - origin,
+            AllocPrefix::<allocators::RustAlloc>::skip_to::<T>()
+        );
+        assert_eq!(ptr.as_ptr() as usize % core::mem::align_of::<T>(), 0);
+        // The prefix ends right where the data starts.
+        let prefix = unsafe { ptr.prefix() } as *const AllocPrefix<allocators::RustAlloc>;
+        assert_eq!(
+            unsafe { prefix.add(1) }.cast::<T>(),
+            ptr.as_ptr().cast_const()
+        );
+        let size = 3 * core::mem::size_of::<T>();
+        unsafe { ptr.as_ptr().cast::<u8>().write_bytes(0xAB, size) };
+        // Reallocating keeps the data at the same offset.
+        let ptr = unsafe { ptr.realloc(&mut alloc, 3, 300) }.unwrap();
+        let origin = unsafe { ptr.prefix() }.origin.as_ptr() as usize;
+        assert_eq!(
+            ptr.as_ptr() as usize - origin,
+            AllocPrefix::<allocators::RustAlloc>::skip_to::<T>()
+        );
+        // The data can be accessed through the new pointer, whose provenance is that of the new allocation.
+        let data = unsafe { core::slice::from_raw_parts(ptr.as_ptr().cast::<u8>(), size) };
+        assert!(data.iter().all(|&byte| byte == 0xAB));
+        assert_eq!(
+            unsafe { ptr.prefix() }
+                .capacity
+                .load(core::sync::atomic::Ordering::Relaxed),
+            300
+        );
+        unsafe { ptr.free(&mut alloc) };
+    }
+    #[repr(align(64))]
+    struct Align64(#[allow(dead_code)] u8);
+    check::<u8>();
+    check::<u16>();
+    check::<u64>();
+    check::<[u8; 3]>();
+    check::<Align64>();
+}
+#[test]
+fn layout_overflow() {
+    assert_eq!(
+        Layout::array_checked::<u8>(usize::MAX),
+        Some(Layout {
+            size: usize::MAX,
+            align: 1
+        })
+    );
+    assert_eq!(Layout::array_checked::<u64>(usize::MAX / 4), None);
+    assert_eq!(Layout::array_checked::<u64>(usize::MAX / 8 + 1), None);
+    let prefix = Layout::of::<[usize; 5]>();
+    assert_eq!(
+        prefix.concat_checked(Layout::of::<u64>()),
+        Some(prefix.concat(Layout::of::<u64>()))
+    );
+    assert_eq!(
+        prefix.concat_checked(Layout {
+            size: usize::MAX - 16,
+            align: 8
+        }),
+        None
+    );
+    assert_eq!(
+        Layout { size: 1, align: 1 }.concat_checked(Layout {
+            size: usize::MAX - 4,
+            align: 8
+        }),
+        None
+    );
+}
+#[test]
+fn layout_padding() {
+    let layout = Layout { size: 13, align: 1 };
+    assert_eq!(layout.padding_needed_for(1), 0);
+    assert_eq!(layout.padding_needed_for(8), 3);
+    assert_eq!(layout.padding_needed_for(16), 3);
+    assert_eq!(Layout::of::<u64>().padding_needed_for(8), 0);
+    assert_eq!(Layout::of::<u64>().padding_needed_for(32), 24);
+    assert_eq!(layout.align_to(8), Layout { size: 16, align: 8 });
+    assert_eq!(
+        Layout::of::<u64>().align_to(2),
+        Layout { size: 8, align: 8 }
+    );
+    assert_eq!(
+        Layout { size: 0, align: 1 }.align_to(64),
+        Layout { size: 0, align: 64 }
+    );
+}
+#[test]
+fn layout_conversions() {
+    let layout = Layout { size: 24, align: 8 };
+    let core_layout = core::alloc::Layout::try_from(layout).unwrap();
+    assert_eq!(core_layout, core::alloc::Layout::new::<[u64; 3]>());
+    assert_eq!(Layout::from(core_layout), layout);
+    assert!(core::alloc::Layout::try_from(Layout { size: 8, align: 0 }).is_err());
+    assert!(core::alloc::Layout::try_from(Layout { size: 8, align: 3 }).is_err());
+    assert!(core::alloc::Layout::try_from(Layout {
+        size: usize::MAX,
+        align: 8
+    })
+    .is_err());
+}
+#[test]
+#[should_panic(expected = "Layout alignments must be non-zero powers of 2")]
+fn layout_realign_non_power_of_2() {
+    let _ = Layout::of::<u64>().realign(12);
+}
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn alloc_array_overflow() {
+    let mut alloc = allocators::RustAlloc::new();
+    assert!(
+        AllocPtr::<u64, allocators::RustAlloc>::alloc_array(&mut alloc, usize::MAX / 4).is_none()
+    );
+    assert_eq!(
+        AllocPtr::<u64, allocators::RustAlloc>::try_alloc_array(&mut alloc, usize::MAX / 8).err(),
+        Some(TryAllocError::OutOfMemory)
+    );
+    assert!(vec::Vec::<u64>::try_with_capacity(usize::MAX / 4).is_err());
+    let mut vec = vec::Vec::<u64>::new();
+    vec.push(1);
+    assert!(vec.try_reserve(usize::MAX).is_err());
+    assert!(vec.try_reserve(usize::MAX / 8).is_err());
+    assert_eq!(vec.as_slice(), &[1]);
+}
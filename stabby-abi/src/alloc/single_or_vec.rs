@@ -108,6 +108,10 @@ where
     pub fn is_empty(&self) -> bool {
         self.inner.match_ref(|_| false, |vec| vec.is_empty())
     }
+    /// Returns `true` if the vector's single element is currently stored inline, rather than in a heap allocation.
+    pub fn is_inlined(&self) -> bool {
+        self.inner.is_ok()
+    }
     /// Adds `value` at the end of `self`.
     ///
     /// # Panics
@@ -186,7 +190,9 @@ where
     }
     /// Ensures that `additional` more elements can be pushed on `self` without reallocating.
     ///
-    /// This may reallocate once to provide this guarantee.
+    /// This may reallocate once to provide this guarantee. If `self` is inlined, a non-zero `additional`
+    /// moves its element to a heap allocation large enough for `additional` more elements, which lets you
+    /// spill ahead of a known burst of pushes.
     ///
     /// # Panics
     /// This function panics if the allocator failed to provide an appropriate allocation.
@@ -201,29 +207,35 @@ where
     /// Returns Ok(new_capacity) if succesful (including if no reallocation was needed),
     /// otherwise returns Err(AllocationError)
     pub fn try_reserve(&mut self, additional: usize) -> Result<NonMaxUsize, AllocationError> {
-        let inner = &mut self.inner as *mut _;
-        self.inner.match_mut(
+        let spilled = self.inner.match_mut(
             |value| unsafe {
-                let new_capacity = 1 + additional;
+                if additional == 0 {
+                    return Ok(None);
+                }
+                let new_capacity = additional.checked_add(1).ok_or(AllocationError())?;
                 // either `inner` must be leaked and overwritten by the new owner of `value` and `alloc`,
                 // or these two must be leaked to prevent double frees.
                 let Single { value, alloc } = core::ptr::read(&*value);
                 match Vec::try_with_capacity_in(new_capacity, alloc) {
                     Ok(mut vec) => {
                         vec.push(value);
-                        // overwrite `inner` with `value` and `alloc` with their new owner without freeing `inner`.
-                        core::ptr::write(inner, crate::Result::Err(vec));
-                        NonMaxUsize::new(new_capacity).ok_or(AllocationError())
+                        Ok(Some(vec))
                     }
                     Err(alloc) => {
                         // leak both `value` and `alloc` since `inner` can't be overwritten
-                        core::mem::drop((value, alloc));
+                        core::mem::forget((value, alloc));
                         Err(AllocationError())
                     }
                 }
             },
-            |mut vec| vec.try_reserve(additional),
-        )
+            |mut vec| vec.try_reserve(additional).map(|_| None),
+        )?;
+        if let Some(vec) = spilled {
+            // overwrite `inner` with the new owner of `value` and `alloc` without freeing it.
+            // This must happen after the guard given by `match_mut` is dropped, as it may otherwise restore `inner`'s determinant.
+            unsafe { core::ptr::write(&mut self.inner, crate::Result::Err(vec)) };
+        }
+        NonMaxUsize::new(self.capacity()).ok_or(AllocationError())
     }
     /// Removes all elements from `self` from the `len`th onward.
     ///
@@ -232,16 +244,21 @@ where
         if self.len() <= len {
             return;
         }
-        let inner = &mut self.inner as *mut _;
-        self.inner.match_mut(
+        let emptied = self.inner.match_mut(
             |value| unsafe {
                 let Single { value, alloc } = core::ptr::read(&*value);
                 core::mem::drop(value); // drop `value` to prevent leaking it since we'll overwrite `inner` with something that doesn't own it
-                                        // overwrite `inner` with the new owner of `alloc`
-                core::ptr::write(inner, crate::Result::Err(Vec::new_in(alloc)))
+                Some(alloc)
             },
-            |mut vec| vec.truncate(len),
-        )
+            |mut vec| {
+                vec.truncate(len);
+                None
+            },
+        );
+        if let Some(alloc) = emptied {
+            // overwrite `inner` with the new owner of `alloc`, once the guard given by `match_mut` is dropped.
+            unsafe { core::ptr::write(&mut self.inner, crate::Result::Err(Vec::new_in(alloc))) }
+        }
     }
     /// Returns a slice of the elements in the vector.
     pub fn as_slice(&self) -> &[T] {
@@ -261,6 +278,12 @@ where
             },
         )
     }
+    /// Returns a mutable slice of the elements in the vector.
+    ///
+    /// This is an alias of [`Self::as_slice_mut`], matching [`Vec::as_mut_slice`].
+    pub fn as_mut_slice(&mut self) -> SliceGuardMut<'_, T, Alloc> {
+        self.as_slice_mut()
+    }
     // pub fn iter(&self) -> core::slice::Iter<'_, T> {
     //     self.into_iter()
     // }
@@ -456,6 +479,58 @@ where
     }
 }
 
+impl<T, Alloc: IAlloc> IntoIterator for SingleOrVec<T, Alloc>
+where
+    T: IStable,
+    Alloc: IStable,
+    Single<T, Alloc>: IDeterminantProvider<Vec<T, Alloc>>,
+    Vec<T, Alloc>: IStable,
+    crate::Result<Single<T, Alloc>, Vec<T, Alloc>>: IStable,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, Alloc>;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            inner: self.inner.match_owned(
+                |Single { value, alloc }| Ok((Some(value).into_iter(), alloc)),
+                |vec| Err(vec.into_iter()),
+            ),
+        }
+    }
+}
+
+/// An owning iterator over the elements of a [`SingleOrVec`].
+///
+/// Iterating over an inlined element doesn't require any allocation.
+pub struct IntoIter<T, Alloc: IAlloc> {
+    #[allow(clippy::type_complexity)]
+    inner: Result<(core::option::IntoIter<T>, Alloc), super::vec::IntoIter<T, Alloc>>,
+}
+impl<T, Alloc: IAlloc> Iterator for IntoIter<T, Alloc> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            Ok((single, _)) => single.next(),
+            Err(vec) => vec.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.inner {
+            Ok((single, _)) => single.size_hint(),
+            Err(vec) => vec.size_hint(),
+        }
+    }
+}
+impl<T, Alloc: IAlloc> DoubleEndedIterator for IntoIter<T, Alloc> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            Ok((single, _)) => single.next_back(),
+            Err(vec) => vec.next_back(),
+        }
+    }
+}
+impl<T, Alloc: IAlloc> ExactSizeIterator for IntoIter<T, Alloc> {}
+
 /// An iterator over a [`SliceGuardMut`].
 pub struct IterMut<'a, T, Alloc>
 where
@@ -608,3 +683,110 @@ fn test() {
     let clone = new.clone();
     assert_eq!(new.as_slice(), clone.as_slice());
 }
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn slice_api() {
+    use crate::alloc::{sync::Arc, Layout};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    #[crate::stabby]
+    #[derive(Default)]
+    pub struct Counting {
+        inner: crate::alloc::allocators::RustAlloc,
+    }
+    impl IAlloc for Counting {
+        fn alloc(&mut self, layout: Layout) -> *mut () {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            self.inner.alloc(layout)
+        }
+        unsafe fn free(&mut self, ptr: *mut ()) {
+            unsafe { IAlloc::free(&mut self.inner, ptr) }
+        }
+    }
+    let value = Arc::new(1u8);
+    let mut vec = SingleOrVec::<Arc<u8>, Counting>::default();
+    assert!(vec.is_empty());
+    vec.push(value.clone());
+    assert_eq!(vec.len(), 1);
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 0);
+    assert_eq!(*vec[0], 1);
+    assert_eq!(vec.iter().count(), 1);
+    vec.as_mut_slice()[0] = Arc::new(2);
+    assert_eq!(*vec.as_slice()[0], 2);
+    vec.as_mut_slice()[0] = value.clone();
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 0);
+    assert_eq!(Arc::strong_count(&value), 2);
+    drop(vec);
+    assert_eq!(Arc::strong_count(&value), 1);
+
+    let mut vec = SingleOrVec::<Arc<u8>, Counting>::default();
+    vec.push(value.clone());
+    let inlined = vec.into_iter().collect::<std::vec::Vec<_>>();
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 0);
+    assert_eq!(inlined.len(), 1);
+    drop(inlined);
+
+    let mut vec = SingleOrVec::<Arc<u8>, Counting>::default();
+    vec.push(value.clone());
+    vec.push(Arc::new(3));
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 1);
+    assert_eq!(vec.len(), 2);
+    assert!(Arc::ptr_eq(&vec[0], &value));
+    assert_eq!(*vec[1], 3);
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(*iter.next_back().unwrap(), 3);
+    assert_eq!(Arc::strong_count(&value), 2);
+    drop(iter);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn spill() {
+    use crate::alloc::{sync::Arc, Layout};
+    let value = Arc::new(1u8);
+    let mut vec = SingleOrVec::<Arc<u8>>::new();
+    vec.push(value.clone());
+    assert!(vec.is_inlined());
+    vec.reserve(0);
+    assert!(vec.is_inlined());
+    vec.reserve(16);
+    assert!(!vec.is_inlined());
+    assert!(vec.capacity() >= 17);
+    let start = vec.as_ptr();
+    for i in 0..16 {
+        vec.push(Arc::new(i));
+    }
+    assert_eq!(start, vec.as_ptr());
+    assert!(Arc::ptr_eq(&vec[0], &value));
+    drop(vec);
+    assert_eq!(Arc::strong_count(&value), 1);
+
+    #[crate::stabby]
+    #[derive(Default)]
+    pub struct Refusing {
+        inner: [u8; 0],
+    }
+    impl IAlloc for Refusing {
+        fn alloc(&mut self, _layout: Layout) -> *mut () {
+            core::ptr::null_mut()
+        }
+        unsafe fn free(&mut self, _ptr: *mut ()) {}
+    }
+    let mut vec = SingleOrVec::<Arc<u8>, Refusing>::default();
+    vec.push(value.clone());
+    assert!(vec.try_reserve(1).is_err());
+    assert!(vec.is_inlined());
+    assert!(Arc::ptr_eq(&vec[0], &value));
+    assert_eq!(Arc::strong_count(&value), 2);
+    vec.truncate(0);
+    assert!(vec.is_empty());
+    assert!(!vec.is_inlined());
+    assert_eq!(Arc::strong_count(&value), 1);
+    vec.push(value.clone());
+    assert!(vec.is_inlined());
+    drop(vec);
+    assert_eq!(Arc::strong_count(&value), 1);
+}
@@ -4,6 +4,7 @@ use super::{
     vec::Vec,
     AllocationError, IAlloc,
 };
+use crate::num::NonMaxUsize;
 use core::hash::Hash;
 
 /// A growable owned string.
@@ -15,18 +16,94 @@ pub struct String<Alloc: IAlloc = super::DefaultAllocator> {
 
 #[cfg(not(stabby_default_alloc = "disabled"))]
 impl String {
-    /// Constructs a new string using the default allocator.
+    /// Constructs a new string using the default allocator. This doesn't actually allocate.
+    ///
+    /// This is a `const fn`, so empty strings may be stored in `static`s:
+    /// ```
+    /// # use stabby_abi::alloc::string::String;
+    /// static EMPTY: String = String::new();
+    /// assert_eq!(EMPTY.as_str(), "");
+    /// ```
     pub const fn new() -> Self {
         Self { inner: Vec::new() }
     }
 }
 impl<Alloc: IAlloc> String<Alloc> {
-    /// Constructs a new string using the provided allocator.
+    /// Constructs a new string using the provided allocator. This doesn't actually allocate.
     pub const fn new_in(alloc: Alloc) -> Self {
         Self {
             inner: Vec::new_in(alloc),
         }
     }
+    /// Constructs a new string in `alloc`, allocating sufficient space for `capacity` bytes.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide a large enough allocation.
+    pub fn with_capacity_in(capacity: usize, alloc: Alloc) -> Self {
+        Self {
+            inner: Vec::with_capacity_in(capacity, alloc),
+        }
+    }
+    /// Constructs a new string, allocating sufficient space for `capacity` bytes.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide a large enough allocation.
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        Alloc: Default,
+    {
+        Self::with_capacity_in(capacity, Alloc::default())
+    }
+    /// Returns the length of the string, in bytes.
+    pub const fn len(&self) -> usize {
+        self.inner.len()
+    }
+    /// Returns `true` if the string is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+    /// The total capacity of the string, in bytes.
+    pub const fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+    /// Ensures that `additional` more bytes can be pushed on `self` without reallocating.
+    ///
+    /// This may reallocate once to provide this guarantee.
+    ///
+    /// # Panics
+    /// This function panics if the allocator failed to provide an appropriate allocation.
+    pub fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+    /// Ensures that `additional` more bytes can be pushed on `self` without reallocating.
+    ///
+    /// This may reallocate once to provide this guarantee.
+    ///
+    /// # Errors
+    /// Returns Ok(new_capacity) if succesful (including if no reallocation was needed),
+    /// otherwise returns Err(AllocationError)
+    pub fn try_reserve(&mut self, additional: usize) -> Result<NonMaxUsize, AllocationError> {
+        self.inner.try_reserve(additional)
+    }
+    /// Shrinks the capacity of `self` to match its length, freeing the allocation entirely if `self` is empty.
+    ///
+    /// Should the allocator fail to provide the smaller allocation, `self` keeps its current allocation.
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit()
+    }
+    /// Converts a vector of bytes into a string without copying, provided it contains valid UTF-8.
+    /// # Errors
+    /// If `vec` isn't valid UTF-8, it's returned as part of the [`FromUtf8Error`].
+    pub fn from_utf8(vec: Vec<u8, Alloc>) -> Result<Self, FromUtf8Error<Alloc>> {
+        match core::str::from_utf8(vec.as_slice()) {
+            Ok(_) => Ok(Self { inner: vec }),
+            Err(e) => Err(FromUtf8Error {
+                bytes: vec,
+                valid_up_to: e.valid_up_to(),
+                error_len: e.error_len().map_or(0, |len| len as u8),
+            }),
+        }
+    }
     /// Returns self as a borrowed string
     #[rustversion::attr(since(1.86), const)]
     pub fn as_str(&self) -> &str {
@@ -37,6 +114,74 @@ impl<Alloc: IAlloc> String<Alloc> {
     pub fn as_str_mut(&mut self) -> &mut str {
         unsafe { core::str::from_utf8_unchecked_mut(self.inner.as_slice_mut()) }
     }
+    /// Appends `s` to the end of `self`.
+    /// # Panics
+    /// If reallocation was needed and the allocator failed to provide a new allocation.
+    pub fn push_str(&mut self, s: &str) {
+        self.inner.copy_extend(s.as_bytes())
+    }
+    /// Appends `c` to the end of `self`.
+    /// # Panics
+    /// If reallocation was needed and the allocator failed to provide a new allocation.
+    pub fn push(&mut self, c: char) {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+    /// Attempts to append `s` to the end of `self`.
+    /// # Errors
+    /// Returns an [`AllocationError`] if reallocation was needed and failed, leaving `self` unchanged.
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), AllocationError> {
+        self.try_concat_str(s)
+    }
+    /// Attempts to append `c` to the end of `self`.
+    /// # Errors
+    /// Returns an [`AllocationError`] if reallocation was needed and failed, leaving `self` unchanged.
+    pub fn try_push(&mut self, c: char) -> Result<(), AllocationError> {
+        self.try_push_str(c.encode_utf8(&mut [0; 4]))
+    }
+    /// Inserts `c` at the byte index `idx`.
+    /// # Panics
+    /// If `idx` is larger than `self.len()` or doesn't lie on a `char` boundary,
+    /// or if reallocation was needed and the allocator failed to provide a new allocation.
+    pub fn insert(&mut self, idx: usize, c: char) {
+        assert!(
+            self.is_char_boundary(idx),
+            "assertion failed: self.is_char_boundary(idx)"
+        );
+        let mut buffer = [0; 4];
+        let bytes = c.encode_utf8(&mut buffer).as_bytes();
+        let len = self.inner.len();
+        self.inner.reserve(bytes.len());
+        unsafe {
+            let at = self.inner.as_mut_ptr().add(idx);
+            core::ptr::copy(at, at.add(bytes.len()), len - idx);
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), at, bytes.len());
+            self.inner.set_len(len + bytes.len());
+        }
+    }
+    /// Removes the `char` at the byte index `idx` and returns it.
+    /// # Panics
+    /// If `idx` is larger than or equal to `self.len()`, or doesn't lie on a `char` boundary.
+    pub fn remove(&mut self, idx: usize) -> char {
+        let Some(c) = self[idx..].chars().next() else {
+            panic!("cannot remove a char from the end of a string")
+        };
+        self.inner.drain(idx..idx + c.len_utf8());
+        c
+    }
+    /// Shortens `self` to `new_len` bytes.
+    ///
+    /// Does nothing if `new_len >= self.len()`.
+    /// # Panics
+    /// If `new_len` doesn't lie on a `char` boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(
+                self.is_char_boundary(new_len),
+                "assertion failed: self.is_char_boundary(new_len)"
+            );
+            self.inner.truncate(new_len)
+        }
+    }
     fn try_concat_str(&mut self, s: &str) -> Result<(), AllocationError> {
         self.inner.try_copy_extend(s.as_bytes())
     }
@@ -47,6 +192,56 @@ impl<Alloc: IAlloc> String<Alloc> {
         self.try_concat_str(s.as_ref())
     }
 }
+/// The error returned by [`String::from_utf8`], which gives back the vector that was passed to it.
+#[crate::stabby]
+pub struct FromUtf8Error<Alloc: IAlloc = super::DefaultAllocator> {
+    bytes: Vec<u8, Alloc>,
+    valid_up_to: usize,
+    error_len: u8,
+}
+impl<Alloc: IAlloc> FromUtf8Error<Alloc> {
+    /// Returns the bytes that failed to convert.
+    #[rustversion::attr(since(1.86), const)]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+    /// Returns the vector that failed to convert.
+    pub fn into_bytes(self) -> Vec<u8, Alloc> {
+        self.bytes
+    }
+    /// The index up to which the bytes were valid UTF-8.
+    pub const fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+    /// See [`core::str::Utf8Error::error_len`].
+    pub const fn error_len(&self) -> Option<usize> {
+        match self.error_len {
+            0 => None,
+            len => Some(len as usize),
+        }
+    }
+    /// Returns the [`core::str::Utf8Error`] that caused the conversion to fail.
+    pub fn utf8_error(&self) -> core::str::Utf8Error {
+        match core::str::from_utf8(self.as_bytes()) {
+            Err(e) => e,
+            Ok(_) => unreachable!("FromUtf8Error always contains invalid UTF-8"),
+        }
+    }
+}
+impl<Alloc: IAlloc> core::fmt::Debug for FromUtf8Error<Alloc> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FromUtf8Error")
+            .field("bytes", &self.bytes)
+            .field("error", &self.utf8_error())
+            .finish()
+    }
+}
+impl<Alloc: IAlloc> core::fmt::Display for FromUtf8Error<Alloc> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.utf8_error(), f)
+    }
+}
+
 impl<Alloc: IAlloc + Default> Default for String<Alloc> {
     fn default() -> Self {
         Self {
@@ -93,6 +288,11 @@ impl<Alloc: IAlloc> core::convert::AsRef<str> for String<Alloc> {
         self.as_str()
     }
 }
+impl<Alloc: IAlloc> core::borrow::Borrow<str> for String<Alloc> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
 impl<Alloc: IAlloc> core::ops::DerefMut for String<Alloc> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_str_mut()
@@ -148,12 +348,62 @@ impl<Alloc: IAlloc + Default> From<crate::str::Str<'_>> for String<Alloc> {
 pub struct ArcStr<Alloc: IAlloc = super::DefaultAllocator> {
     inner: ArcSlice<u8, Alloc>,
 }
+impl<Alloc: IAlloc + Default> ArcStr<Alloc> {
+    /// Concatenates `parts` into a new string.
+    ///
+    /// See [`Self::concat_in`].
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn concat<S: AsRef<str>>(parts: &[S]) -> Self {
+        Self::concat_in(parts, Alloc::default())
+    }
+}
 impl<Alloc: IAlloc> ArcStr<Alloc> {
+    /// Concatenates `parts` into a new string in `alloc`, allocating exactly for their total length.
+    /// ```
+    /// # use stabby_abi::alloc::{allocators::RustAlloc, string::ArcStr};
+    /// let url = ArcStr::concat_in(&["https://", "example.com", "/path"], RustAlloc::new());
+    /// assert_eq!(url.as_str(), "https://example.com/path");
+    /// ```
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation, or if the total length overflows `usize`.
+    pub fn concat_in<S: AsRef<str>>(parts: &[S], alloc: Alloc) -> Self {
+        let len = parts
+            .iter()
+            .try_fold(0usize, |len, part| len.checked_add(part.as_ref().len()))
+            .expect("capacity overflow");
+        let mut inner = Vec::with_capacity_in(len, alloc);
+        for part in parts {
+            inner.copy_extend(part.as_ref().as_bytes());
+        }
+        String { inner }.into()
+    }
+    /// Converts a reference counted slice of bytes into a string without copying, provided it contains valid UTF-8.
+    /// # Errors
+    /// Returns `bytes` unchanged if it isn't valid UTF-8.
+    #[rustversion::attr(since(1.86), const)]
+    pub fn from_utf8(bytes: ArcSlice<u8, Alloc>) -> Result<Self, ArcSlice<u8, Alloc>> {
+        match core::str::from_utf8(bytes.as_slice()) {
+            Ok(_) => Ok(Self { inner: bytes }),
+            Err(_) => Err(bytes),
+        }
+    }
     /// Returns a borrow to the inner string.
     #[rustversion::attr(since(1.86), const)]
     pub fn as_str(&self) -> &str {
         unsafe { core::str::from_utf8_unchecked(self.inner.as_slice()) }
     }
+    /// Returns a borrow to the inner string's bytes.
+    #[rustversion::attr(since(1.86), const)]
+    pub fn as_bytes(this: &Self) -> &[u8] {
+        this.inner.as_slice()
+    }
+    /// Converts `this` into a reference counted slice of bytes, sharing the same allocation.
+    pub fn into_bytes(this: Self) -> ArcSlice<u8, Alloc> {
+        this.inner
+    }
     /// Returns a mutably borrow to the inner str.
     /// # Safety
     /// [`Self::is_unique`] must be true.
@@ -170,11 +420,37 @@ impl<Alloc: IAlloc> ArcStr<Alloc> {
         ArcSlice::is_unique(&this.inner)
     }
 }
+/// Appends `rhs` to the string.
+///
+/// Since [`ArcStr`] is immutable, this generally allocates a new string, using a clone of `self`'s allocator.
+/// If `self` is the sole owner of its data however, its buffer is reused through [`String`], only
+/// reallocating if it lacks the capacity to fit `rhs`.
+impl<S: AsRef<str> + ?Sized, Alloc: IAlloc + Clone> core::ops::Add<&S> for ArcStr<Alloc> {
+    type Output = Self;
+    fn add(self, rhs: &S) -> Self::Output {
+        let rhs = rhs.as_ref();
+        if rhs.is_empty() {
+            return self;
+        }
+        match String::try_from(self) {
+            Ok(string) => (string + rhs).into(),
+            Err(this) => {
+                let alloc = ArcSlice::allocator(&this.inner).clone();
+                Self::concat_in(&[this.as_str(), rhs], alloc)
+            }
+        }
+    }
+}
 impl<Alloc: IAlloc> AsRef<str> for ArcStr<Alloc> {
     fn as_ref(&self) -> &str {
         self.as_str()
     }
 }
+impl<Alloc: IAlloc> core::borrow::Borrow<str> for ArcStr<Alloc> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
 
 impl<Alloc: IAlloc> core::fmt::Debug for ArcStr<Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -293,6 +569,11 @@ impl<Alloc: IAlloc> AsRef<str> for BoxedStr<Alloc> {
         self.as_str()
     }
 }
+impl<Alloc: IAlloc> core::borrow::Borrow<str> for BoxedStr<Alloc> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
 impl<Alloc: IAlloc> core::ops::Deref for BoxedStr<Alloc> {
     type Target = str;
     fn deref(&self) -> &Self::Target {
@@ -352,19 +633,232 @@ impl core::fmt::Write for String {
     }
 }
 
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn push() {
+    let mut s = String::new();
+    s.push_str("Hello");
+    s.push(',');
+    s.push(' ');
+    s.push('🦀');
+    s.push_str("");
+    assert_eq!(s.as_str(), "Hello, 🦀");
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn try_push() {
+    use super::allocators::{FailAfter, RustAlloc};
+    let alloc = FailAfter::new(RustAlloc::new(), 1);
+    let mut s = String::new_in(alloc.clone());
+    assert!(s.try_push_str("Hello").is_ok());
+    let capacity = s.capacity();
+    while s.len() < capacity {
+        assert!(s.try_push('!').is_ok());
+    }
+    assert!(s.try_push_str(", world").is_err());
+    assert!(s.try_push('🦀').is_err());
+    assert_eq!((s.len(), s.capacity()), (capacity, capacity));
+    assert!(s.starts_with("Hello"));
+    // Vectors give the value back instead.
+    let mut vec = Vec::new_in(alloc.clone());
+    assert_eq!(
+        vec.try_push(Some(s)).map_err(|s| s.map(|s| s.len())),
+        Err(Some(capacity))
+    );
+    alloc.set_remaining(1);
+    assert!(vec.try_push(None).is_ok());
+    assert_eq!(vec.len(), 1);
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn reserve() {
+    let mut s: String = String::with_capacity(4);
+    assert!(s.is_empty());
+    assert!(s.capacity() >= 4);
+    s.push_str("🦀");
+    assert_eq!((s.len(), s.is_empty()), (4, false));
+    s.reserve(100);
+    let capacity = s.capacity();
+    assert!(capacity >= 104);
+    let ptr = s.as_ptr();
+    for _ in 0..16 {
+        s.push_str("ab🦀");
+    }
+    assert!(core::ptr::eq(s.as_ptr(), ptr));
+    assert_eq!((s.len(), s.capacity()), (100, capacity));
+    assert!(s.try_reserve(usize::MAX).is_err());
+    assert_eq!(s.try_reserve(0).ok().map(|c| c.get()), Some(capacity));
+    s.truncate(4);
+    s.shrink_to_fit();
+    assert_eq!((s.as_str(), s.capacity()), ("🦀", 4));
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn from_utf8() {
+    let bytes: Vec<u8> = Vec::from("héllo".as_bytes());
+    assert_eq!(String::from_utf8(bytes).unwrap().as_str(), "héllo");
+    let bytes: Vec<u8> = Vec::from([b'a', 0xC3, b'b'].as_slice());
+    let err = String::from_utf8(bytes).unwrap_err();
+    assert_eq!(err.valid_up_to(), 1);
+    assert_eq!(err.error_len(), Some(1));
+    assert_eq!(
+        err.utf8_error(),
+        core::str::from_utf8(err.as_bytes()).unwrap_err()
+    );
+    let truncated: Vec<u8> = Vec::from([b'a', 0xC3].as_slice());
+    assert_eq!(String::from_utf8(truncated).unwrap_err().error_len(), None);
+    assert_eq!(err.into_bytes().as_slice(), [b'a', 0xC3, b'b']);
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_str_from_utf8() {
+    let bytes: ArcSlice<u8> = ArcSlice::from("héllo".as_bytes());
+    let clone = bytes.clone();
+    let s = ArcStr::from_utf8(bytes).unwrap();
+    assert_eq!(s.as_str(), "héllo");
+    assert!(core::ptr::eq(s.as_ptr(), clone.as_slice().as_ptr()));
+    assert_eq!(ArcSlice::strong_count(&clone), 2);
+    assert_eq!(ArcStr::as_bytes(&s), "héllo".as_bytes());
+    let bytes = ArcStr::into_bytes(s);
+    assert!(ArcSlice::ptr_eq(&bytes, &clone));
+    assert_eq!(ArcSlice::strong_count(&clone), 2);
+    assert!(ArcStr::from_utf8(bytes).is_ok());
+    let invalid: ArcSlice<u8> = ArcSlice::from([b'a', 0xC3].as_slice());
+    let invalid = ArcStr::from_utf8(invalid).unwrap_err();
+    assert_eq!(invalid.as_slice(), [b'a', 0xC3]);
+    assert_eq!(ArcSlice::strong_count(&invalid), 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn insert_remove_truncate() {
+    let mut s: String = "héllo".into();
+    s.insert(0, '🦀');
+    s.insert(s.len(), '!');
+    s.insert(5, 'é');
+    assert_eq!(s.as_str(), "🦀hééllo!");
+    assert_eq!(s.remove(4), 'h');
+    assert_eq!(s.remove(6), 'é');
+    assert_eq!(s.as_str(), "🦀éllo!");
+    s.truncate(6);
+    assert_eq!(s.as_str(), "🦀é");
+    s.truncate(10);
+    assert_eq!(s.as_str(), "🦀é");
+    let e = || -> String { "é".into() };
+    for result in [
+        std::panic::catch_unwind(|| e().insert(1, 'a')),
+        std::panic::catch_unwind(|| {
+            e().remove(1);
+        }),
+        std::panic::catch_unwind(|| {
+            e().remove(2);
+        }),
+        std::panic::catch_unwind(|| e().truncate(1)),
+    ] {
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn borrowed_lookups() {
+    use core::hash::BuildHasher;
+    use std::collections::{BTreeMap, HashMap};
+    let key = |s: &str| -> ArcStr { String::from(s).into() };
+    let btree: BTreeMap<ArcStr, u8> = [(key("a"), 0), (key("b"), 1)].into_iter().collect();
+    assert_eq!(btree.get("b"), Some(&1));
+    let hashmap: HashMap<ArcStr, u8> = btree.clone().into_iter().collect();
+    assert_eq!(hashmap.get("a"), Some(&0));
+    assert_eq!(hashmap.get("c"), None);
+    let hasher = hashmap.hasher();
+    assert_eq!(hasher.hash_one(key("a")), hasher.hash_one("a"));
+    let slices: HashMap<ArcSlice<u8>, u8> = [(ArcSlice::from([1, 2].as_slice()), 3)].into();
+    assert_eq!(slices.get([1, 2].as_slice()), Some(&3));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn std_conversions() {
+    let shared: ArcStr = std::string::String::from("héllo").into();
+    assert_eq!(shared.as_str(), "héllo");
+    assert_eq!(std::string::String::from(shared.clone()), "héllo");
+    let owned: String = std::string::String::from("wörld").into();
+    assert_eq!(std::string::String::from(owned), "wörld");
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn arc_str_concat() {
+    use super::allocators::{RustAlloc, TrackingAlloc};
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    let empty = ArcStr::concat_in::<&str>(&[], alloc.clone());
+    assert_eq!(empty.as_str(), "");
+    let path = ArcStr::concat_in(&["", "/ünï", "", "/cödé/", "🦀"], alloc.clone());
+    assert_eq!(path.as_str(), "/ünï/cödé/🦀");
+    assert_eq!(path.len(), "/ünï/cödé/🦀".len());
+    // Appending to a shared string leaves the other owners untouched.
+    let shared = path.clone();
+    let longer = shared + "/é";
+    assert_eq!(
+        (path.as_str(), longer.as_str()),
+        ("/ünï/cödé/🦀", "/ünï/cödé/🦀/é")
+    );
+    let ptr = path.as_ptr();
+    assert!(core::ptr::eq((path + "").as_ptr(), ptr));
+    assert_eq!((empty + "🦀").as_str(), "🦀");
+    // A unique string with spare capacity is appended to in place.
+    let mut string = String::new_in(alloc.clone());
+    string.reserve(64);
+    string.push_str("ß");
+    let unique = ArcStr::from(string);
+    let ptr = unique.as_ptr();
+    let allocations = alloc.allocations();
+    let unique = unique + "ẞ" + &ArcStr::concat_in(&["ǅ"], alloc.clone());
+    assert_eq!(unique.as_str(), "ßẞǅ");
+    assert!(core::ptr::eq(unique.as_ptr(), ptr));
+    assert_eq!(alloc.allocations(), allocations + 1);
+    drop((longer, unique));
+    assert_eq!(alloc.live_allocations(), 0);
+}
+
 #[cfg(feature = "std")]
 mod std_impl {
     use crate::alloc::IAlloc;
+    impl<Alloc: IAlloc> std::error::Error for crate::alloc::string::FromUtf8Error<Alloc> {}
+    /// Copies a [`std::string::String`] into a new stabby [`String`](crate::alloc::string::String).
+    ///
+    /// Stabby's allocations are prefixed with metadata `std` has no room for, so the bytes always need to be copied
+    /// to a new allocation, even if `Alloc` is the global allocator.
     impl<Alloc: IAlloc + Default> From<std::string::String> for crate::alloc::string::String<Alloc> {
         fn from(value: std::string::String) -> Self {
             Self::from(value.as_ref())
         }
     }
+    /// Copies a stabby [`String`](crate::alloc::string::String) into a new [`std::string::String`].
+    ///
+    /// Stabby's allocations are prefixed with metadata `std` has no room for, so the bytes always need to be copied
+    /// to a new allocation, even if `Alloc` is the global allocator.
     impl<Alloc: IAlloc + Default> From<crate::alloc::string::String<Alloc>> for std::string::String {
         fn from(value: crate::alloc::string::String<Alloc>) -> Self {
             Self::from(value.as_ref())
         }
     }
+    /// Copies a [`std::string::String`] into a new [`ArcStr`](crate::alloc::string::ArcStr).
+    impl<Alloc: IAlloc + Default> From<std::string::String> for crate::alloc::string::ArcStr<Alloc> {
+        fn from(value: std::string::String) -> Self {
+            crate::alloc::string::String::from(value).into()
+        }
+    }
+    /// Copies an [`ArcStr`](crate::alloc::string::ArcStr) into a new [`std::string::String`].
+    impl<Alloc: IAlloc> From<crate::alloc::string::ArcStr<Alloc>> for std::string::String {
+        fn from(value: crate::alloc::string::ArcStr<Alloc>) -> Self {
+            Self::from(value.as_str())
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -22,15 +22,23 @@ use core::{
 };
 
 use crate::{
-    unreachable_unchecked, vtable::HasDropVt, AnonymRef, AnonymRefMut, Dyn, IStable, IntoDyn,
+    unreachable_unchecked,
+    vtable::{AnyDyn, HasDropVt, IConstConstructor, StabbyVtableAny, TransitiveDeref},
+    AnonymRef, AnonymRefMut, Dyn, IStable, IntoDyn,
 };
 
 use super::{
-    vec::{ptr_add, ptr_diff, Vec, VecInner},
+    boxed::Box,
+    vec::{addr, ptr_add, ptr_diff, Vec, VecInner},
     AllocPtr, AllocSlice, DefaultAllocator, IAlloc,
 };
 
 /// [`alloc::sync::Arc`](https://doc.rust-lang.org/stable/alloc/sync/struct.Arc.html), but ABI-stable.
+///
+/// Like `std`'s, cloning an `Arc` (or a [`Weak`]) so many times that its reference count would risk overflowing
+/// aborts the process when the `std` feature is enabled. Without it, the clone panics instead.
+///
+/// Comparisons and hashing delegate to the pointee: use [`Arc::ptr_eq`] to compare identities instead.
 #[crate::stabby]
 pub struct Arc<T, Alloc: IAlloc = super::DefaultAllocator> {
     ptr: AllocPtr<T, Alloc>,
@@ -40,6 +48,124 @@ unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Send for Arc<T, Alloc>
 // SAFETY: Same constraints as in `std`.
 unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Sync for Arc<T, Alloc> {}
 const USIZE_TOP_BIT: usize = 1 << (core::mem::size_of::<usize>() as i32 * 8 - 1);
+/// The highest reference count `stabby` accepts: reaching it means references are being leaked.
+///
+/// It leaves [`USIZE_TOP_BIT`] free, as well as plenty of room for concurrent increments to happen before one
+/// of them notices the overflow.
+///
+/// Older versions of [`Weak::upgrade`] set [`USIZE_TOP_BIT`] while operating on the strong count: since the
+/// allocations they upgrade may be shared with this version, the bit is still ignored when reading the count.
+const MAX_REFCOUNT: usize = USIZE_TOP_BIT >> 1;
+
+/// The atomic operations the reference counting functions below rely on.
+///
+/// They are generic over it so that the loom models can run them on loom's atomics, which [`AllocPrefix`]'s
+/// ABI-stable counts can't be swapped for.
+///
+/// [`AllocPrefix`]: super::AllocPrefix
+trait RefCount {
+    fn load(&self, order: Ordering) -> usize;
+    fn fetch_add(&self, value: usize, order: Ordering) -> usize;
+    fn fetch_sub(&self, value: usize, order: Ordering) -> usize;
+    fn compare_exchange_weak(
+        &self,
+        current: usize,
+        new: usize,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<usize, usize>;
+    /// An acquire fence, in the same memory model as the count.
+    fn fence_acquire();
+}
+macro_rules! impl_refcount {
+    ($atomic: ty, $fence: path) => {
+        impl RefCount for $atomic {
+            fn load(&self, order: Ordering) -> usize {
+                <$atomic>::load(self, order)
+            }
+            fn fetch_add(&self, value: usize, order: Ordering) -> usize {
+                <$atomic>::fetch_add(self, value, order)
+            }
+            fn fetch_sub(&self, value: usize, order: Ordering) -> usize {
+                <$atomic>::fetch_sub(self, value, order)
+            }
+            fn compare_exchange_weak(
+                &self,
+                current: usize,
+                new: usize,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<usize, usize> {
+                <$atomic>::compare_exchange_weak(self, current, new, success, failure)
+            }
+            fn fence_acquire() {
+                $fence(Ordering::Acquire)
+            }
+        }
+    };
+}
+impl_refcount!(AtomicUsize, core::sync::atomic::fence);
+#[cfg(loom)]
+impl_refcount!(loom::sync::atomic::AtomicUsize, loom::sync::atomic::fence);
+
+/// Increments a reference count, returning its previous value.
+///
+/// Should the count exceed [`MAX_REFCOUNT`], the process is aborted if the `std` feature is enabled.
+/// Otherwise, the increment is undone and this panics, keeping the count saturated below the point
+/// where it could wrap around.
+fn increment_refcount<C: RefCount>(count: &C, order: Ordering) -> usize {
+    let previous = count.fetch_add(1, order);
+    check_refcount(count, previous);
+    previous
+}
+/// Checks that `count` didn't exceed [`MAX_REFCOUNT`] when it got incremented from `previous`.
+fn check_refcount<C: RefCount>(count: &C, previous: usize) {
+    if previous & !USIZE_TOP_BIT >= MAX_REFCOUNT {
+        refcount_overflow(count)
+    }
+}
+/// Increments a strong count unless it is 0, returning whether it did.
+///
+/// A strong count that reached 0 must never be incremented again, as the value has been (or is being) dropped:
+/// the count is therefore only incremented by a compare-exchange that checks it didn't reach 0 in the meantime.
+/// Succeeding acquires the store that made the value available, such as [`Arc::new_cyclic`]'s.
+fn upgrade_refcount<C: RefCount>(strong: &C) -> bool {
+    let mut count = strong.load(Ordering::Relaxed);
+    loop {
+        if count & !USIZE_TOP_BIT == 0 {
+            return false;
+        }
+        match strong.compare_exchange_weak(count, count + 1, Ordering::Acquire, Ordering::Relaxed) {
+            Ok(_) => {
+                check_refcount(strong, count);
+                return true;
+            }
+            Err(actual) => count = actual,
+        }
+    }
+}
+/// Decrements a reference count, returning `true` if it reached 0.
+///
+/// The decrement releases this reference's accesses to the allocation, and reaching 0 acquires those of all
+/// other references, so that they happen before the value is dropped or the allocation freed.
+fn decrement_refcount<C: RefCount>(count: &C) -> bool {
+    if count.fetch_sub(1, Ordering::Release) != 1 {
+        return false;
+    }
+    C::fence_acquire();
+    true
+}
+#[cold]
+#[cfg_attr(feature = "std", allow(unused_variables))]
+fn refcount_overflow<C: RefCount>(count: &C) -> ! {
+    #[cfg(feature = "std")]
+    std::process::abort();
+    #[cfg(not(feature = "std"))]
+    {
+        count.fetch_sub(1, Ordering::Relaxed);
+        panic!("reference count overflow")
+    }
+}
 
 #[cfg(not(stabby_default_alloc = "disabled"))]
 impl<T> Arc<T> {
@@ -72,6 +198,55 @@ impl<T> Arc<T> {
     pub fn new(value: T) -> Self {
         Self::new_in(value, DefaultAllocator::new())
     }
+    /// Attempts to allocate [`Self`] and store `value` in it.
+    ///
+    /// Unlike [`Self::try_new_in`], this doesn't need to return the allocator on failure.
+    /// ```
+    /// # use stabby_abi::alloc::sync::Arc;
+    /// let Ok(value) = Arc::try_new(3u64) else {
+    ///     panic!("out of memory")
+    /// };
+    /// assert_eq!(*value, 3);
+    /// ```
+    /// # Errors
+    /// Returns `value` if the allocator fails to provide an appropriate allocation.
+    pub fn try_new(value: T) -> Result<Self, T> {
+        Self::try_new_in(value, DefaultAllocator::new()).map_err(|(value, _)| value)
+    }
+    /// Allocates [`Self`], constructing its value with `data_fn`, which is given a [`Weak`] to the allocation.
+    ///
+    /// Any attempt to upgrade the [`Weak`] before `data_fn` returns will fail.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new_cyclic<F: FnOnce(&Weak<T>) -> T>(data_fn: F) -> Self {
+        Self::new_cyclic_in(data_fn, DefaultAllocator::new())
+    }
+    /// Allocates [`Self`] with its value's memory filled with zeroes, using [`IAlloc::alloc_zeroed`].
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new_zeroed() -> Arc<MaybeUninit<T>> {
+        Self::new_zeroed_in(DefaultAllocator::new())
+    }
+    /// Allocates an [`Arc`] storing `value`, and turns it into a trait object in a single call.
+    ///
+    /// The vtable is constructed for `T`, and is usually inferred from the expected [`DynArc`].
+    /// ```
+    /// # use stabby_abi::{alloc::{sync::{Arc, DynArc}, Layout}, vtable::{VtDrop, VtLayout}};
+    /// let value: DynArc<VtLayout<VtDrop>> = Arc::new_dyn(6u32);
+    /// assert_eq!(value.value_layout(), Layout::of::<u32>());
+    /// ```
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new_dyn<'a, Vt>(value: T) -> DynArc<'a, Vt>
+    where
+        T: 'a,
+        Vt: HasDropVt + Copy + IConstConstructor<'static, T> + 'static,
+    {
+        Self::new_dyn_in(value, DefaultAllocator::new())
+    }
 }
 
 impl<T, Alloc: IAlloc> Arc<T, Alloc> {
@@ -176,6 +351,63 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
         // SAFETY: `constructor` is infallible.
         unsafe { this.unwrap_unchecked() }
     }
+    /// Allocates an [`Arc`] storing `value` in `alloc`, and turns it into a trait object in a single call.
+    ///
+    /// The vtable is constructed for `T`, and is usually inferred from the expected [`DynArc`].
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new_dyn_in<'a, Vt>(value: T, alloc: Alloc) -> DynArc<'a, Vt, Alloc>
+    where
+        T: 'a,
+        Alloc: 'a,
+        Vt: HasDropVt + Copy + IConstConstructor<'static, T> + 'static,
+    {
+        Self::new_in(value, alloc).into()
+    }
+    /// Allocates [`Self`] in `alloc`, constructing its value with `data_fn`, which is given a [`Weak`] to the allocation.
+    ///
+    /// Any attempt to upgrade the [`Weak`] before `data_fn` returns will fail.
+    ///
+    /// If `data_fn` panics, the allocation is freed.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new_cyclic_in<F: FnOnce(&Weak<T, Alloc>) -> T>(data_fn: F, mut alloc: Alloc) -> Self {
+        let Some(mut ptr) = AllocPtr::<T, Alloc>::alloc(&mut alloc) else {
+            panic!("Allocation failed")
+        };
+        // SAFETY: `ptr` just got allocated via `AllocPtr::alloc`.
+        let prefix = unsafe { ptr.prefix_mut() };
+        prefix.alloc.write(alloc);
+        prefix.strong = AtomicUsize::new(0);
+        prefix.weak = AtomicUsize::new(1);
+        // This weak reference becomes the one shared by all strong references once construction is done.
+        // Should `data_fn` panic, dropping it frees the allocation without touching the uninitialized value.
+        let weak = Weak { ptr };
+        let value = data_fn(&weak);
+        // SAFETY: We are the sole owners of the value's slot, as `Weak` never accesses it while the strong count is 0.
+        unsafe { ptr.as_ptr().write(value) };
+        // SAFETY: `ptr` is still allocated, since `weak` is still alive.
+        unsafe { ptr.prefix() }.strong.store(1, Ordering::Release);
+        core::mem::forget(weak);
+        Self { ptr }
+    }
+    /// Allocates [`Self`] in `alloc`, with its value's memory filled with zeroes, using [`IAlloc::alloc_zeroed`].
+    ///
+    /// If `T` is valid when zeroed (such as any `bytemuck::Zeroable` type), the result may then be
+    /// [`Arc::assume_init`]ed.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new_zeroed_in(mut alloc: Alloc) -> Arc<MaybeUninit<T>, Alloc> {
+        let Some(mut ptr) = AllocPtr::<MaybeUninit<T>, _>::alloc_zeroed(&mut alloc) else {
+            panic!("Allocation failed")
+        };
+        // SAFETY: `ptr` just got allocated via `AllocPtr::alloc_zeroed`.
+        unsafe { ptr.prefix_mut() }.alloc.write(alloc);
+        Arc { ptr }
+    }
 
     /// Returns the pointer to the inner raw allocation, leaking `this`.
     ///
@@ -191,6 +423,12 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
     pub const unsafe fn from_raw(this: AllocPtr<T, Alloc>) -> Self {
         Self { ptr: this }
     }
+    /// Returns a pointer to the value, without affecting the reference counts.
+    ///
+    /// The pointer stays valid for as long as at least one strong reference to the value exists.
+    pub const fn as_ptr(this: &Self) -> *const T {
+        this.ptr.ptr.as_ptr()
+    }
 
     /// Provides a mutable reference to the internals if the strong and weak counts are both 1.
     pub fn get_mut(this: &mut Self) -> Option<&mut T> {
@@ -212,7 +450,7 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
 
     /// Returns the strong count.
     pub fn strong_count(this: &Self) -> usize {
-        unsafe { this.ptr.prefix() }.strong.load(Ordering::Relaxed)
+        unsafe { this.ptr.prefix() }.strong.load(Ordering::Relaxed) & !USIZE_TOP_BIT
     }
     /// Increments the strong count.
     /// # Safety
@@ -222,9 +460,7 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
             ptr: NonNull::new_unchecked(this.cast_mut()),
             marker: core::marker::PhantomData,
         };
-        unsafe { ptr.prefix() }
-            .strong
-            .fetch_add(1, Ordering::Relaxed)
+        increment_refcount(&unsafe { ptr.prefix() }.strong, Ordering::Relaxed)
     }
     /// Returns the weak count. Note that all Arcs to a same value share a Weak, so the weak count can never be 0.
     pub fn weak_count(this: &Self) -> usize {
@@ -232,9 +468,25 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
     }
     /// Increments the weak count, returning its previous value.
     pub fn increment_weak_count(this: &Self) -> usize {
-        unsafe { this.ptr.prefix() }
-            .weak
-            .fetch_add(1, Ordering::Relaxed)
+        increment_refcount(&unsafe { this.ptr.prefix() }.weak, Ordering::Relaxed)
+    }
+    /// Returns the `(strong, weak)` counts, as [`Self::strong_count`] and [`Self::weak_count`] would.
+    ///
+    /// The two counts are distinct atomics, so they can't be read at once. Instead, the strong count is read again
+    /// after the weak count until it is unchanged: unless the strong count was changed and then restored between
+    /// these reads, the returned counts were both held at the moment the weak count was read. Like any such
+    /// snapshot, they may be outdated by the time they are returned.
+    pub fn counts(this: &Self) -> (usize, usize) {
+        let prefix = unsafe { this.ptr.prefix() };
+        let mut strong = prefix.strong.load(Ordering::Acquire);
+        loop {
+            let weak = prefix.weak.load(Ordering::Acquire);
+            let confirmed = prefix.strong.load(Ordering::Acquire);
+            if confirmed == strong {
+                return (strong & !USIZE_TOP_BIT, weak);
+            }
+            strong = confirmed;
+        }
     }
 
     /// Returns a mutable reference to this `Arc`'s value, cloning that value into a new `Arc` if [`Self::get_mut`] would have failed.
@@ -270,7 +522,15 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
 
     /// Whether or not `this` is the sole owner of its data, including weak owners.
     pub fn is_unique(this: &Self) -> bool {
-        Self::strong_count(this) == 1 && Self::weak_count(this) == 1
+        // Acquiring the counts ensures that the accesses of references that were dropped happen before `this`'s.
+        let prefix = unsafe { this.ptr.prefix() };
+        prefix.strong.load(Ordering::Acquire) == 1 && prefix.weak.load(Ordering::Acquire) == 1
+    }
+    /// Returns `true` if `this` and `other` point to the same allocation.
+    ///
+    /// Unlike `==`, this compares identity rather than value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        core::ptr::eq(this.ptr.ptr.as_ptr(), other.ptr.ptr.as_ptr())
     }
     /// Attempts the value from the allocation, freeing said allocation.
     /// # Errors
@@ -284,11 +544,30 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
             Ok(ret)
         }
     }
+    /// Moves the value out of `this` if it's the sole owner of its value, cloning it otherwise.
+    pub fn unwrap_or_clone(this: Self) -> T
+    where
+        T: Clone,
+    {
+        Self::try_into_inner(this).unwrap_or_else(|this| T::clone(&this))
+    }
 
     /// Constructs an additional [`Weak`] pointer to `this`.
     pub fn downgrade(this: &Self) -> Weak<T, Alloc> {
         this.into()
     }
+    /// Borrows `this` as a [`WeakRef`], without touching the reference counts.
+    ///
+    /// Since `this` can't be dropped while it's borrowed, this [`WeakRef`] always upgrades, and [`WeakRef::get`]
+    /// gives access to the value without touching the counts either. This isn't the case of a [`WeakRef`] borrowed
+    /// from a [`Weak`].
+    pub const fn weak_ref(this: &Self) -> WeakRef<'_, T, Alloc> {
+        WeakRef {
+            ptr: this.ptr,
+            borrows_strong: true,
+            marker: PhantomData,
+        }
+    }
     #[rustversion::since(1.73)]
     /// Returns a reference to the allocator used to construct `this`
     pub const fn allocator(this: &Self) -> &Alloc {
@@ -300,13 +579,24 @@ impl<T, Alloc: IAlloc> Arc<T, Alloc> {
         unsafe { this.ptr.prefix().alloc.assume_init_ref() }
     }
 }
+impl<T, Alloc: IAlloc> Arc<MaybeUninit<T>, Alloc> {
+    /// Assumes the value of the allocation has been initialized.
+    ///
+    /// # Safety
+    /// The value must have been initialized, either through [`Arc::get_mut`] or by allocating it with
+    /// [`Arc::new_zeroed_in`] if `T` is valid when zeroed.
+    pub const unsafe fn assume_init(this: Self) -> Arc<T, Alloc> {
+        let ptr = this.ptr;
+        core::mem::forget(this);
+        Arc {
+            // SAFETY: Ensured by the caller.
+            ptr: unsafe { ptr.assume_init() },
+        }
+    }
+}
 impl<T, Alloc: IAlloc> Drop for Arc<T, Alloc> {
     fn drop(&mut self) {
-        if unsafe { self.ptr.prefix() }
-            .strong
-            .fetch_sub(1, Ordering::Relaxed)
-            != 1
-        {
+        if !decrement_refcount(&unsafe { self.ptr.prefix() }.strong) {
             return;
         }
         unsafe {
@@ -317,9 +607,7 @@ impl<T, Alloc: IAlloc> Drop for Arc<T, Alloc> {
 }
 impl<T, Alloc: IAlloc> Clone for Arc<T, Alloc> {
     fn clone(&self) -> Self {
-        unsafe { self.ptr.prefix() }
-            .strong
-            .fetch_add(1, Ordering::Relaxed);
+        increment_refcount(&unsafe { self.ptr.prefix() }.strong, Ordering::Relaxed);
         Self { ptr: self.ptr }
     }
 }
@@ -329,6 +617,38 @@ impl<T, Alloc: IAlloc> core::ops::Deref for Arc<T, Alloc> {
         unsafe { self.ptr.as_ref() }
     }
 }
+fmt_passthrough!(Arc);
+impl<T, Alloc: IAlloc> AsRef<T> for Arc<T, Alloc> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+impl<T, Alloc: IAlloc> core::borrow::Borrow<T> for Arc<T, Alloc> {
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+impl<T: Eq, Alloc: IAlloc> Eq for Arc<T, Alloc> {}
+impl<T: PartialEq, Alloc: IAlloc> PartialEq for Arc<T, Alloc> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+impl<T: Ord, Alloc: IAlloc> Ord for Arc<T, Alloc> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+impl<T: PartialOrd, Alloc: IAlloc> PartialOrd for Arc<T, Alloc> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+impl<T: Hash, Alloc: IAlloc> Hash for Arc<T, Alloc> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
 
 /// [`alloc::sync::Weak`](https://doc.rust-lang.org/stable/alloc/sync/struct.Weak.html), but ABI-stable.
 #[crate::stabby]
@@ -351,12 +671,21 @@ impl<T, Alloc: IAlloc> From<&Weak<T, Alloc>> for Weak<T, Alloc> {
 }
 impl<T, Alloc: IAlloc> From<&Arc<T, Alloc>> for Weak<T, Alloc> {
     fn from(value: &Arc<T, Alloc>) -> Self {
-        unsafe { value.ptr.prefix() }
-            .weak
-            .fetch_add(1, Ordering::Relaxed);
+        increment_refcount(&unsafe { value.ptr.prefix() }.weak, Ordering::Relaxed);
         Self { ptr: value.ptr }
     }
 }
+impl<T, Alloc: IAlloc> From<Box<T, Alloc>> for Arc<T, Alloc> {
+    /// Converts the box into an `Arc`, reusing its allocation.
+    fn from(value: Box<T, Alloc>) -> Self {
+        let ptr = Box::into_raw(value);
+        // SAFETY: `ptr` was uniquely owned by the box, which already stored the allocator in the prefix.
+        let prefix = unsafe { ptr.prefix() };
+        prefix.strong.store(1, Ordering::Relaxed);
+        prefix.weak.store(1, Ordering::Relaxed);
+        Self { ptr }
+    }
+}
 impl<T, Alloc: IAlloc> Weak<T, Alloc> {
     /// Returns the pointer to the inner raw allocation, leaking `this`.
     ///
@@ -372,38 +701,59 @@ impl<T, Alloc: IAlloc> Weak<T, Alloc> {
     pub const unsafe fn from_raw(this: AllocPtr<T, Alloc>) -> Self {
         Self { ptr: this }
     }
+    /// Returns the number of [`Arc`]s to the value, which is 0 if it has been dropped.
+    pub fn strong_count(&self) -> usize {
+        // Older versions of `upgrade` set the top bit while they operate on the count.
+        unsafe { self.ptr.prefix() }.strong.load(Ordering::Relaxed) & !USIZE_TOP_BIT
+    }
+    /// Returns the weak count, in the same way as [`Arc::weak_count`]: all [`Arc`]s to a same value share a
+    /// [`Weak`], which is included in the count as long as any of them lives.
+    pub fn weak_count(&self) -> usize {
+        unsafe { self.ptr.prefix() }.weak.load(Ordering::Relaxed)
+    }
     /// Attempts to upgrade self into an Arc.
+    ///
+    /// This fails once the strong count has reached 0, and never blocks concurrent upgrades or drops.
     pub fn upgrade(&self) -> Option<Arc<T, Alloc>> {
-        let strong = &unsafe { self.ptr.prefix() }.strong;
-        let count = strong.fetch_or(USIZE_TOP_BIT, Ordering::Acquire);
-        match count {
-            0 | USIZE_TOP_BIT => {
-                strong.store(0, Ordering::Release);
-                None
-            }
-            _ => {
-                strong.fetch_add(1, Ordering::Release);
-                strong.fetch_and(!USIZE_TOP_BIT, Ordering::Release);
-                Some(Arc { ptr: self.ptr })
-            }
-        }
+        upgrade_refcount(&unsafe { self.ptr.prefix() }.strong).then(|| Arc { ptr: self.ptr })
+    }
+}
+impl<T, Alloc: IAlloc + Default> Weak<T, Alloc> {
+    /// Constructs a [`Weak`] that will never upgrade.
+    ///
+    /// Unlike `std`'s, this allocates: `stabby` needs an [`AllocPrefix`](super::AllocPrefix) to store the reference counts
+    /// and allocator. The allocation is only large enough to fit the prefix, and is freed once `self` and all of its
+    /// clones are dropped.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new() -> Self {
+        let mut alloc = Alloc::default();
+        let Some(mut ptr) = AllocPtr::alloc_array(&mut alloc, 0) else {
+            panic!("Allocation failed")
+        };
+        // SAFETY: `ptr` just got allocated via `AllocPtr::alloc_array`.
+        let prefix = unsafe { ptr.prefix_mut() };
+        prefix.alloc.write(alloc);
+        prefix.strong = AtomicUsize::new(0);
+        prefix.weak = AtomicUsize::new(1);
+        Self { ptr }
+    }
+}
+impl<T, Alloc: IAlloc + Default> Default for Weak<T, Alloc> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 impl<T, Alloc: IAlloc> Clone for Weak<T, Alloc> {
     fn clone(&self) -> Self {
-        unsafe { self.ptr.prefix() }
-            .weak
-            .fetch_add(1, Ordering::Relaxed);
+        increment_refcount(&unsafe { self.ptr.prefix() }.weak, Ordering::Relaxed);
         Self { ptr: self.ptr }
     }
 }
 impl<T, Alloc: IAlloc> Drop for Weak<T, Alloc> {
     fn drop(&mut self) {
-        if unsafe { self.ptr.prefix() }
-            .weak
-            .fetch_sub(1, Ordering::Relaxed)
-            != 1
-        {
+        if !decrement_refcount(&unsafe { self.ptr.prefix() }.weak) {
             return;
         }
         unsafe {
@@ -413,6 +763,76 @@ impl<T, Alloc: IAlloc> Drop for Weak<T, Alloc> {
     }
 }
 
+/// A borrowed [`Weak`], which doesn't own a weak reference to the value.
+///
+/// It is obtained from [`Arc::weak_ref`], or by converting a `&Weak`: this lets functions that may need to access
+/// the value accept either without the increments and decrements of constructing and dropping a [`Weak`].
+#[crate::stabby]
+pub struct WeakRef<'a, T, Alloc: IAlloc = super::DefaultAllocator> {
+    ptr: AllocPtr<T, Alloc>,
+    /// Whether `self` was borrowed from an [`Arc`], which keeps the value alive for `'a`.
+    borrows_strong: bool,
+    marker: PhantomData<&'a Weak<T, Alloc>>,
+}
+// SAFETY: `WeakRef` is equivalent to a `&Weak`.
+unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Send for WeakRef<'_, T, Alloc> {}
+// SAFETY: `WeakRef` is equivalent to a `&Weak`.
+unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Sync for WeakRef<'_, T, Alloc> {}
+impl<T, Alloc: IAlloc> Clone for WeakRef<'_, T, Alloc> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, Alloc: IAlloc> Copy for WeakRef<'_, T, Alloc> {}
+impl<'a, T, Alloc: IAlloc> From<&'a Arc<T, Alloc>> for WeakRef<'a, T, Alloc> {
+    fn from(value: &'a Arc<T, Alloc>) -> Self {
+        Arc::weak_ref(value)
+    }
+}
+/// The value may already have been dropped, so upgrading this [`WeakRef`] may fail, and [`WeakRef::get`] always
+/// returns `None`.
+impl<'a, T, Alloc: IAlloc> From<&'a Weak<T, Alloc>> for WeakRef<'a, T, Alloc> {
+    fn from(value: &'a Weak<T, Alloc>) -> Self {
+        Self {
+            ptr: value.ptr,
+            borrows_strong: false,
+            marker: PhantomData,
+        }
+    }
+}
+impl<T, Alloc: IAlloc> From<WeakRef<'_, T, Alloc>> for Weak<T, Alloc> {
+    fn from(value: WeakRef<'_, T, Alloc>) -> Self {
+        increment_refcount(&unsafe { value.ptr.prefix() }.weak, Ordering::Relaxed);
+        Self { ptr: value.ptr }
+    }
+}
+impl<'a, T, Alloc: IAlloc> WeakRef<'a, T, Alloc> {
+    /// Returns a reference to the value if `self` was borrowed from an [`Arc`], without touching the reference counts.
+    ///
+    /// Returns `None` if `self` was borrowed from a [`Weak`]: [`Self::upgrade`] must be used to access the value then.
+    #[rustversion::attr(since(1.73), const)]
+    pub fn get(&self) -> Option<&'a T> {
+        if !self.borrows_strong {
+            return None;
+        }
+        // SAFETY: the `Arc` `self` was borrowed from keeps the value alive for `'a`.
+        Some(unsafe { self.ptr.ptr.as_ref() })
+    }
+    /// Returns the number of [`Arc`]s to the value, as [`Weak::strong_count`] would.
+    pub fn strong_count(&self) -> usize {
+        // Older versions of `upgrade` set the top bit while they operate on the count.
+        unsafe { self.ptr.prefix() }.strong.load(Ordering::Relaxed) & !USIZE_TOP_BIT
+    }
+    /// Returns the weak count, as [`Weak::weak_count`] would: `self` isn't included in it.
+    pub fn weak_count(&self) -> usize {
+        unsafe { self.ptr.prefix() }.weak.load(Ordering::Relaxed)
+    }
+    /// Attempts to upgrade `self` into an [`Arc`], as [`Weak::upgrade`] would.
+    pub fn upgrade(&self) -> Option<Arc<T, Alloc>> {
+        upgrade_refcount(&unsafe { self.ptr.prefix() }.strong).then(|| Arc { ptr: self.ptr })
+    }
+}
+
 /// A strong reference to a fixed size slice of elements.
 ///
 /// Equivalent to `alloc::sync::Arc<[T]>`
@@ -429,10 +849,76 @@ unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Send for WeakSlice<T, A
 // SAFETY: Same constraints as in `std`.
 unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Sync for WeakSlice<T, Alloc> {}
 
+impl<T, Alloc: IAlloc + Default> ArcSlice<T, Alloc> {
+    /// Constructs a slice from the elements of `iter`, allocating exactly for as many elements as it advertises.
+    ///
+    /// See [`Self::from_exact_iter_in`].
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn from_exact_iter<I: IntoIterator<Item = T>>(iter: I) -> Self
+    where
+        I::IntoIter: ExactSizeIterator,
+    {
+        Self::from_exact_iter_in(iter, Alloc::default())
+    }
+}
 impl<T, Alloc: IAlloc> ArcSlice<T, Alloc> {
+    /// Constructs a slice from the elements of `iter` in `alloc`, allocating exactly for as many elements as it advertises.
+    ///
+    /// This spares the reallocation that collecting through a [`Vec`] may require. Should `iter` yield fewer elements
+    /// than advertised, the slice is truncated to those it did yield; any extraneous elements are left in the iterator.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn from_exact_iter_in<I: IntoIterator<Item = T>>(iter: I, mut alloc: Alloc) -> Self
+    where
+        I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let len = iter.len();
+        let Some(mut start) = AllocPtr::alloc_array(&mut alloc, len) else {
+            panic!("Allocation failed")
+        };
+        let capacity = if core::mem::size_of::<T>() != 0 {
+            len
+        } else {
+            // Zero-sized elements never run out of room, up to the end of the address space.
+            usize::MAX - addr(start.ptr)
+        };
+        // SAFETY: `start` just got allocated via `AllocPtr::alloc_array`.
+        let prefix = unsafe { start.prefix_mut() };
+        prefix.alloc.write(alloc);
+        prefix.capacity = AtomicUsize::new(capacity);
+        // Should `iter` panic, dropping `this` drops the elements written so far and frees the allocation.
+        let mut this = Self {
+            inner: AllocSlice {
+                start,
+                end: start.ptr,
+            },
+        };
+        for value in iter.take(len) {
+            // SAFETY: less than `len` elements have been written so far.
+            unsafe {
+                this.inner.end.as_ptr().write(value);
+                this.inner.end = ptr_add(this.inner.end, 1);
+            }
+        }
+        this
+    }
+    #[rustversion::since(1.73)]
+    /// Returns a reference to the allocator used to construct `this`
+    pub const fn allocator(this: &Self) -> &Alloc {
+        unsafe { this.inner.start.prefix().alloc.assume_init_ref() }
+    }
+    #[rustversion::before(1.73)]
+    /// Returns a reference to the allocator used to construct `this`
+    pub fn allocator(this: &Self) -> &Alloc {
+        unsafe { this.inner.start.prefix().alloc.assume_init_ref() }
+    }
     /// Returns the number of elements in the slice.
     pub const fn len(&self) -> usize {
-        ptr_diff(self.inner.end, self.inner.start.ptr)
+        self.inner.len()
     }
     /// Returns true if the slice is empty.
     pub const fn is_empty(&self) -> bool {
@@ -449,6 +935,31 @@ impl<T, Alloc: IAlloc> ArcSlice<T, Alloc> {
         (ArcSlice::strong_count(self) == 1 && ArcSlice::weak_count(self) == 1)
             .then(|| unsafe { self.as_slice_mut_unchecked() })
     }
+    /// Returns a mutable borrow to the slice, cloning its elements into a new [`ArcSlice`] if [`Self::as_slice_mut`] would have failed.
+    ///
+    /// The new slice is allocated using a clone of `this`'s allocator.
+    pub fn make_mut(this: &mut Self) -> &mut [T]
+    where
+        T: Clone,
+        Alloc: Clone,
+    {
+        if !Self::is_unique(this) {
+            let alloc = unsafe { this.inner.start.prefix().alloc.assume_init_ref() }.clone();
+            let mut vec = Vec::<T, Alloc>::new_in(alloc);
+            if core::mem::size_of::<T>() != 0 {
+                vec.reserve(this.len());
+            }
+            for (i, value) in this.iter().enumerate() {
+                // SAFETY: `vec` has room for `this.len()` elements (ZSTs never need any), and its length is only increased once the clone is written.
+                unsafe {
+                    vec.as_mut_ptr().add(i).write(value.clone());
+                    vec.set_len(i + 1);
+                }
+            }
+            *this = vec.into();
+        }
+        unsafe { this.as_slice_mut_unchecked() }
+    }
     /// Returns a mutable borrow to the slice.
     /// # Safety
     /// This can easily create aliased mutable references, which would be undefined behaviour.
@@ -457,9 +968,24 @@ impl<T, Alloc: IAlloc> ArcSlice<T, Alloc> {
         let start = self.inner.start;
         unsafe { core::slice::from_raw_parts_mut(start.ptr.as_ptr(), self.len()) }
     }
+    /// Returns a reference to the element at `index`, or `None` if it's out of bounds.
+    #[rustversion::attr(since(1.86), const)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            Some(&self.as_slice()[index])
+        } else {
+            None
+        }
+    }
+    /// Returns a mutable reference to the element at `index`.
+    ///
+    /// Returns `None` if `index` is out of bounds, or if other references to the slice may exist.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.as_slice_mut()?.get_mut(index)
+    }
     /// Returns the strong count to the slice.
     pub fn strong_count(this: &Self) -> usize {
-        unsafe { this.inner.start.prefix().strong.load(Ordering::Relaxed) }
+        unsafe { this.inner.start.prefix().strong.load(Ordering::Relaxed) & !USIZE_TOP_BIT }
     }
     /// Returns the weak count to the slice.
     pub fn weak_count(this: &Self) -> usize {
@@ -467,7 +993,68 @@ impl<T, Alloc: IAlloc> ArcSlice<T, Alloc> {
     }
     /// Whether or not `this` is the sole owner of its data, including weak owners.
     pub fn is_unique(this: &Self) -> bool {
-        Self::strong_count(this) == 1 && Self::weak_count(this) == 1
+        // Acquiring the counts ensures that the accesses of references that were dropped happen before `this`'s.
+        let prefix = unsafe { this.inner.start.prefix() };
+        prefix.strong.load(Ordering::Acquire) == 1 && prefix.weak.load(Ordering::Acquire) == 1
+    }
+    /// Returns `true` if `this` and `other` start at the same address and have the same length.
+    ///
+    /// Unlike `==`, this compares identity rather than value.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        core::ptr::eq(
+            this.inner.start.ptr.as_ptr(),
+            other.inner.start.ptr.as_ptr(),
+        ) && this.len() == other.len()
+    }
+    /// Returns a reference counted view into `range` of `this`, sharing ownership of the whole slice.
+    ///
+    /// This returns an [`ArcSubSlice`] rather than an [`ArcSlice`]: an [`ArcSlice`] finds its allocation's prefix,
+    /// which holds the reference counts and allocator, right before its first element, so it can't start in the
+    /// middle of its allocation. Storing the offset to the prefix instead would change [`ArcSlice`]'s layout, which
+    /// is part of `stabby`'s ABI. An [`ArcSubSlice`] holds the whole [`ArcSlice`] alongside the view's bounds.
+    ///
+    /// # Panics
+    /// If `range` is out of bounds, with the same messages as slice indexing.
+    pub fn slice<R: core::ops::RangeBounds<usize>>(this: &Self, range: R) -> ArcSubSlice<T, Alloc> {
+        ArcSubSlice::from(this.clone()).slice(range)
+    }
+    /// Splits `this` into views of `chunk_size` elements, the last one possibly being shorter.
+    ///
+    /// Each chunk is an [`ArcSubSlice`] that shares ownership of the whole slice, so that they may be handed out
+    /// and dropped independently, for example to process them in parallel.
+    /// ```
+    /// # use stabby_abi::alloc::{sync::ArcSlice, vec::Vec};
+    /// let slice: ArcSlice<u32> = (0..10).collect();
+    /// let chunks: Vec<_> = ArcSlice::chunks(&slice, 4).collect();
+    /// assert_eq!(chunks.len(), 3);
+    /// assert_eq!(chunks[2].as_slice(), [8, 9]);
+    /// assert_eq!(ArcSlice::strong_count(&slice), 4);
+    /// ```
+    ///
+    /// # Panics
+    /// If `chunk_size` is 0.
+    pub fn chunks(this: &Self, chunk_size: usize) -> ArcChunks<T, Alloc> {
+        ArcSubSlice::chunks(this.clone().into(), chunk_size)
+    }
+    /// Returns an iterator over clones of the slice's elements.
+    pub fn iter_cloned(&self) -> core::iter::Cloned<core::slice::Iter<'_, T>>
+    where
+        T: Clone,
+    {
+        self.as_slice().iter().cloned()
+    }
+    /// Converts a slice of exactly one element back into an [`Arc`], reusing its allocation and reference counts.
+    ///
+    /// # Errors
+    /// Returns `this` unchanged if its length isn't 1.
+    pub const fn try_into_arc(this: Self) -> Result<Arc<T, Alloc>, Self> {
+        if this.len() != 1 {
+            return Err(this);
+        }
+        let ptr = this.inner.start;
+        // The `Arc` takes over `this`'s reference counts.
+        core::mem::forget(this);
+        Ok(Arc { ptr })
     }
     /// Returns the slice's raw representation, without altering the associated reference counts.
     ///
@@ -485,6 +1072,30 @@ impl<T, Alloc: IAlloc> ArcSlice<T, Alloc> {
         Self { inner: this }
     }
 }
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod, Alloc: IAlloc> ArcSlice<T, Alloc> {
+    /// Reinterprets the elements as a slice of `U`.
+    ///
+    /// The elements are stored right after an [`AllocPrefix`](crate::alloc::AllocPrefix), and are therefore aligned
+    /// for any type that isn't more aligned than the prefix: in practice, only size mismatches cause this to fail.
+    ///
+    /// # Errors
+    /// Returns [`bytemuck::PodCastError`] if the size of the elements isn't a multiple of `U`'s size,
+    /// or if they aren't sufficiently aligned for `U`.
+    pub fn try_cast_slice<U: bytemuck::Pod>(&self) -> Result<&[U], bytemuck::PodCastError> {
+        crate::alloc::try_cast_slice(self.as_slice())
+    }
+    /// Reinterprets the elements as a slice of `U`.
+    ///
+    /// # Panics
+    /// If [`Self::try_cast_slice`] would fail.
+    pub fn cast_slice<U: bytemuck::Pod>(&self) -> &[U] {
+        match self.try_cast_slice() {
+            Ok(slice) => slice,
+            Err(e) => panic!("failed to cast ArcSlice: {e}"),
+        }
+    }
+}
 impl<T, Alloc: IAlloc> core::ops::Deref for ArcSlice<T, Alloc> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
@@ -493,19 +1104,24 @@ impl<T, Alloc: IAlloc> core::ops::Deref for ArcSlice<T, Alloc> {
 }
 impl<T, Alloc: IAlloc> Clone for ArcSlice<T, Alloc> {
     fn clone(&self) -> Self {
-        unsafe { self.inner.start.prefix() }
-            .strong
-            .fetch_add(1, Ordering::Relaxed);
+        increment_refcount(
+            &unsafe { self.inner.start.prefix() }.strong,
+            Ordering::Relaxed,
+        );
         Self { inner: self.inner }
     }
 }
 impl<T, Alloc: IAlloc> From<Arc<T, Alloc>> for ArcSlice<T, Alloc> {
     fn from(mut value: Arc<T, Alloc>) -> Self {
         unsafe { value.ptr.prefix_mut() }.capacity = AtomicUsize::new(1);
+        let ptr = value.ptr;
+        // The slice takes over `value`'s reference counts.
+        core::mem::forget(value);
         Self {
             inner: AllocSlice {
-                start: value.ptr,
-                end: ptr_add(value.ptr.ptr, 1),
+                start: ptr,
+                // SAFETY: `ptr` points to a single value.
+                end: unsafe { ptr_add(ptr.ptr, 1) },
             },
         }
     }
@@ -539,17 +1155,19 @@ impl<T, Alloc: IAlloc> From<Vec<T, Alloc>> for ArcSlice<T, Alloc> {
                 start.prefix_mut().capacity = if core::mem::size_of::<T>() != 0 {
                     AtomicUsize::new(0)
                 } else {
-                    AtomicUsize::new(ptr_diff(
-                        core::mem::transmute::<usize, NonNull<u8>>(usize::MAX),
-                        start.ptr.cast::<u8>(),
-                    ))
+                    // Zero-sized elements never run out of room, up to the end of the address space.
+                    AtomicUsize::new(usize::MAX - addr(start.ptr))
                 };
-                slice.start.prefix_mut().alloc.write(alloc);
+                start.prefix_mut().alloc.write(alloc);
             }
             Self {
-                inner: AllocSlice {
-                    start,
-                    end: ptr_add(start.ptr.cast::<u8>(), slice.len()).cast(),
+                inner: if core::mem::size_of::<T>() != 0 {
+                    AllocSlice {
+                        start,
+                        end: start.ptr,
+                    }
+                } else {
+                    AllocSlice::from_zst_len(start, slice.len())
                 },
             }
         }
@@ -562,29 +1180,45 @@ impl<T, Alloc: IAlloc> TryFrom<ArcSlice<T, Alloc>> for Vec<T, Alloc> {
             Err(value)
         } else {
             unsafe {
-                let ret = Vec {
+                let AllocSlice { start, end } = value.inner;
+                let capacity = start.prefix().capacity.load(Ordering::Relaxed);
+                let mut alloc = start.prefix().alloc.assume_init_read();
+                core::mem::forget(value);
+                if capacity == 0 {
+                    // Empty slices still own an allocation for their prefix, which a `Vec` without capacity
+                    // considers dangling: free it rather than leaking it.
+                    start.free(&mut alloc);
+                    return Ok(Vec::new_in(alloc));
+                }
+                Ok(Vec {
                     inner: VecInner {
-                        start: value.inner.start,
-                        end: value.inner.end,
-                        capacity: ptr_add(
-                            value.inner.start.ptr,
-                            value.inner.start.prefix().capacity.load(Ordering::Relaxed),
-                        ),
-                        alloc: value.inner.start.prefix().alloc.assume_init_read(),
+                        start,
+                        end,
+                        capacity: ptr_add(start.ptr, capacity),
+                        alloc,
                     },
-                };
-                core::mem::forget(value);
-                Ok(ret)
+                })
             }
         }
     }
 }
+impl<T, Alloc: IAlloc> AsRef<[T]> for ArcSlice<T, Alloc> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+impl<T, Alloc: IAlloc> core::borrow::Borrow<[T]> for ArcSlice<T, Alloc> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
 impl<T: Eq, Alloc: IAlloc> Eq for ArcSlice<T, Alloc> {}
-impl<T: PartialEq, Alloc: IAlloc> PartialEq for ArcSlice<T, Alloc> {
-    fn eq(&self, other: &Self) -> bool {
-        self.as_slice() == other.as_slice()
+impl<T: PartialEq, Alloc: IAlloc, Rhs: AsRef<[T]>> PartialEq<Rhs> for ArcSlice<T, Alloc> {
+    fn eq(&self, other: &Rhs) -> bool {
+        self.as_slice() == other.as_ref()
     }
 }
+slice_eq!(ArcSlice);
 impl<T: Ord, Alloc: IAlloc> Ord for ArcSlice<T, Alloc> {
     fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.as_slice().cmp(other.as_slice())
@@ -602,11 +1236,7 @@ impl<T: Hash, Alloc: IAlloc> Hash for ArcSlice<T, Alloc> {
 }
 impl<T, Alloc: IAlloc> Drop for ArcSlice<T, Alloc> {
     fn drop(&mut self) {
-        if unsafe { self.inner.start.prefix() }
-            .strong
-            .fetch_sub(1, Ordering::Relaxed)
-            != 1
-        {
+        if !decrement_refcount(&unsafe { self.inner.start.prefix() }.strong) {
             return;
         }
         unsafe { core::ptr::drop_in_place(self.as_slice_mut_unchecked()) }
@@ -620,28 +1250,12 @@ impl<T: Debug, Alloc: IAlloc> Debug for ArcSlice<T, Alloc> {
 }
 impl<T: core::fmt::LowerHex, Alloc: IAlloc> core::fmt::LowerHex for ArcSlice<T, Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut first = true;
-        for item in self {
-            if !first {
-                f.write_str(":")?;
-            }
-            first = false;
-            core::fmt::LowerHex::fmt(item, f)?;
-        }
-        Ok(())
+        super::fmt_hex(self.as_slice(), f, core::fmt::LowerHex::fmt)
     }
 }
 impl<T: core::fmt::UpperHex, Alloc: IAlloc> core::fmt::UpperHex for ArcSlice<T, Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut first = true;
-        for item in self {
-            if !first {
-                f.write_str(":")?;
-            }
-            first = false;
-            core::fmt::UpperHex::fmt(item, f)?;
-        }
-        Ok(())
+        super::fmt_hex(self.as_slice(), f, core::fmt::UpperHex::fmt)
     }
 }
 impl<'a, T, Alloc: IAlloc> IntoIterator for &'a ArcSlice<T, Alloc> {
@@ -665,21 +1279,24 @@ pub struct WeakSlice<T, Alloc: IAlloc = super::DefaultAllocator> {
 }
 
 impl<T, Alloc: IAlloc> WeakSlice<T, Alloc> {
+    /// Returns the number of [`ArcSlice`]s to the slice, which is 0 if it has been dropped.
+    pub fn strong_count(&self) -> usize {
+        // Older versions of `upgrade` set the top bit while they operate on the count.
+        unsafe { self.inner.start.prefix() }
+            .strong
+            .load(Ordering::Relaxed)
+            & !USIZE_TOP_BIT
+    }
+    /// Returns the weak count, in the same way as [`ArcSlice::weak_count`].
+    pub fn weak_count(&self) -> usize {
+        unsafe { self.inner.start.prefix() }
+            .weak
+            .load(Ordering::Relaxed)
+    }
     /// Return a strong reference to the slice if it hasn't been destroyed yet.
     pub fn upgrade(&self) -> Option<ArcSlice<T, Alloc>> {
-        let strong = &unsafe { self.inner.start.prefix() }.strong;
-        let count = strong.fetch_or(USIZE_TOP_BIT, Ordering::Acquire);
-        match count {
-            0 | USIZE_TOP_BIT => {
-                strong.store(0, Ordering::Release);
-                None
-            }
-            _ => {
-                strong.fetch_add(1, Ordering::Release);
-                strong.fetch_and(!USIZE_TOP_BIT, Ordering::Release);
-                Some(ArcSlice { inner: self.inner })
-            }
-        }
+        upgrade_refcount(&unsafe { self.inner.start.prefix() }.strong)
+            .then(|| ArcSlice { inner: self.inner })
     }
     /// For types that are [`Copy`], the slice actually remains valid even after all strong references
     /// have been dropped as long as at least a weak reference lives on.
@@ -690,22 +1307,57 @@ impl<T, Alloc: IAlloc> WeakSlice<T, Alloc> {
         T: Copy,
     {
         let strong = &unsafe { self.inner.start.prefix() }.strong;
-        match strong.fetch_add(1, Ordering::Release) {
+        match increment_refcount(strong, Ordering::Release) {
             0 | USIZE_TOP_BIT => {
-                unsafe { self.inner.start.prefix() }
-                    .weak
-                    .fetch_add(1, Ordering::Relaxed);
+                increment_refcount(
+                    &unsafe { self.inner.start.prefix() }.weak,
+                    Ordering::Relaxed,
+                );
             }
             _ => {}
         }
         ArcSlice { inner: self.inner }
     }
 }
+impl<T, Alloc: IAlloc + Default> WeakSlice<T, Alloc> {
+    /// Constructs a [`WeakSlice`] that will never upgrade.
+    ///
+    /// Like [`Weak::new`], this allocates just enough memory for an [`AllocPrefix`](super::AllocPrefix),
+    /// whose strong count is 0. This distinguishes it from a weak reference to an empty slice that still
+    /// has strong owners, which will upgrade successfully.
+    ///
+    /// # Panics
+    /// If the allocator fails to provide an appropriate allocation.
+    pub fn new() -> Self {
+        let mut alloc = Alloc::default();
+        let Some(mut start) = AllocPtr::alloc_array(&mut alloc, 0) else {
+            panic!("Allocation failed")
+        };
+        // SAFETY: `start` just got allocated via `AllocPtr::alloc_array`.
+        let prefix = unsafe { start.prefix_mut() };
+        prefix.alloc.write(alloc);
+        prefix.strong = AtomicUsize::new(0);
+        prefix.weak = AtomicUsize::new(1);
+        prefix.capacity = AtomicUsize::new(0);
+        Self {
+            inner: AllocSlice {
+                start,
+                end: start.ptr,
+            },
+        }
+    }
+}
+impl<T, Alloc: IAlloc + Default> Default for WeakSlice<T, Alloc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 impl<T, Alloc: IAlloc> Clone for WeakSlice<T, Alloc> {
     fn clone(&self) -> Self {
-        unsafe { self.inner.start.prefix() }
-            .weak
-            .fetch_add(1, Ordering::Relaxed);
+        increment_refcount(
+            &unsafe { self.inner.start.prefix() }.weak,
+            Ordering::Relaxed,
+        );
         Self { inner: self.inner }
     }
 }
@@ -721,25 +1373,168 @@ impl<T, Alloc: IAlloc> From<&WeakSlice<T, Alloc>> for WeakSlice<T, Alloc> {
 }
 impl<T, Alloc: IAlloc> From<&ArcSlice<T, Alloc>> for WeakSlice<T, Alloc> {
     fn from(value: &ArcSlice<T, Alloc>) -> Self {
-        unsafe { value.inner.start.prefix() }
-            .weak
-            .fetch_add(1, Ordering::Relaxed);
+        increment_refcount(
+            &unsafe { value.inner.start.prefix() }.weak,
+            Ordering::Relaxed,
+        );
         Self { inner: value.inner }
     }
 }
 impl<T, Alloc: IAlloc> Drop for WeakSlice<T, Alloc> {
     fn drop(&mut self) {
-        if unsafe { self.inner.start.prefix() }
-            .weak
-            .fetch_sub(1, Ordering::Relaxed)
-            != 1
-        {
+        if !decrement_refcount(&unsafe { self.inner.start.prefix() }.weak) {
             return;
         }
         let mut alloc = unsafe { self.inner.start.prefix().alloc.assume_init_read() };
         unsafe { self.inner.start.free(&mut alloc) }
     }
 }
+
+/// A reference counted view into an [`ArcSlice`], which keeps the whole slice alive.
+///
+/// Obtained through [`ArcSlice::slice`], and can be sliced further without reallocating.
+#[crate::stabby]
+pub struct ArcSubSlice<T, Alloc: IAlloc = super::DefaultAllocator> {
+    owner: ArcSlice<T, Alloc>,
+    start: NonNull<T>,
+    end: NonNull<T>,
+}
+// SAFETY: Same constraints as in `std`.
+unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Send for ArcSubSlice<T, Alloc> {}
+// SAFETY: Same constraints as in `std`.
+unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Sync for ArcSubSlice<T, Alloc> {}
+impl<T, Alloc: IAlloc> ArcSubSlice<T, Alloc> {
+    /// Returns the number of elements in the view.
+    pub const fn len(&self) -> usize {
+        // SAFETY: `end` is always `len` elements past `start`.
+        unsafe { ptr_diff(self.end, self.start) }
+    }
+    /// Returns true if the view is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns a borrow to the viewed elements.
+    #[rustversion::attr(since(1.86), const)]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.start.as_ptr(), self.len()) }
+    }
+    /// Returns the [`ArcSlice`] this view borrows from.
+    pub const fn owner(&self) -> &ArcSlice<T, Alloc> {
+        &self.owner
+    }
+    /// Narrows the view down to `range`, relative to the current view.
+    ///
+    /// # Panics
+    /// If `range` is out of bounds, with the same messages as slice indexing.
+    pub fn slice<R: core::ops::RangeBounds<usize>>(self, range: R) -> Self {
+        let bounds = (range.start_bound().cloned(), range.end_bound().cloned());
+        let slice = &self.as_slice()[bounds];
+        let len = slice.len();
+        // SAFETY: slices are never null.
+        let start = unsafe { NonNull::new_unchecked(slice.as_ptr().cast_mut()) };
+        Self {
+            start,
+            // SAFETY: `start` and `len` come from a slice.
+            end: unsafe { ptr_add(start, len) },
+            owner: self.owner,
+        }
+    }
+}
+impl<T, Alloc: IAlloc> ArcSubSlice<T, Alloc> {
+    /// Splits `this` into views of `chunk_size` elements, the last one possibly being shorter.
+    ///
+    /// See [`ArcSlice::chunks`].
+    ///
+    /// # Panics
+    /// If `chunk_size` is 0.
+    pub fn chunks(this: Self, chunk_size: usize) -> ArcChunks<T, Alloc> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        ArcChunks {
+            remaining: this,
+            chunk_size,
+        }
+    }
+}
+/// An iterator over views of an [`ArcSlice`], which each share ownership of it.
+///
+/// Obtained through [`ArcSlice::chunks`] or [`ArcSubSlice::chunks`].
+#[crate::stabby]
+pub struct ArcChunks<T, Alloc: IAlloc = super::DefaultAllocator> {
+    remaining: ArcSubSlice<T, Alloc>,
+    chunk_size: usize,
+}
+impl<T, Alloc: IAlloc> Iterator for ArcChunks<T, Alloc> {
+    type Item = ArcSubSlice<T, Alloc>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let len = self.chunk_size.min(self.remaining.len());
+        let chunk = self.remaining.clone().slice(..len);
+        // SAFETY: `len` is in bounds of `remaining`.
+        self.remaining.start = unsafe { ptr_add(self.remaining.start, len) };
+        Some(chunk)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+impl<T, Alloc: IAlloc> ExactSizeIterator for ArcChunks<T, Alloc> {
+    fn len(&self) -> usize {
+        let len = self.remaining.len();
+        len / self.chunk_size + usize::from(len % self.chunk_size != 0)
+    }
+}
+impl<T, Alloc: IAlloc> core::iter::FusedIterator for ArcChunks<T, Alloc> {}
+impl<T, Alloc: IAlloc> From<ArcSlice<T, Alloc>> for ArcSubSlice<T, Alloc> {
+    fn from(value: ArcSlice<T, Alloc>) -> Self {
+        Self {
+            start: value.inner.start.ptr,
+            end: value.inner.end,
+            owner: value,
+        }
+    }
+}
+impl<T, Alloc: IAlloc> Clone for ArcSubSlice<T, Alloc> {
+    fn clone(&self) -> Self {
+        Self {
+            owner: self.owner.clone(),
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+impl<T, Alloc: IAlloc> core::ops::Deref for ArcSubSlice<T, Alloc> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<T: Eq, Alloc: IAlloc> Eq for ArcSubSlice<T, Alloc> {}
+impl<T: PartialEq, Alloc: IAlloc> PartialEq for ArcSubSlice<T, Alloc> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl<T: Hash, Alloc: IAlloc> Hash for ArcSubSlice<T, Alloc> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+impl<T: Debug, Alloc: IAlloc> Debug for ArcSubSlice<T, Alloc> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+impl<'a, T, Alloc: IAlloc> IntoIterator for &'a ArcSubSlice<T, Alloc> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
 pub use super::string::{ArcStr, WeakStr};
 
 impl<T, Alloc: IAlloc> crate::IPtr for Arc<T, Alloc> {
@@ -769,11 +1564,7 @@ impl<T, Alloc: IAlloc> crate::IPtrOwned for Arc<T, Alloc> {
         this: &mut core::mem::ManuallyDrop<Self>,
         drop: unsafe extern "C" fn(AnonymRefMut<'_>),
     ) {
-        if unsafe { this.ptr.prefix() }
-            .strong
-            .fetch_sub(1, Ordering::Relaxed)
-            != 1
-        {
+        if !decrement_refcount(&unsafe { this.ptr.prefix() }.strong) {
             return;
         }
         unsafe {
@@ -790,24 +1581,69 @@ impl<T, Alloc: IAlloc> IntoDyn for Arc<T, Alloc> {
     type Anonymized = Arc<(), Alloc>;
     type Target = T;
     fn anonimize(self) -> Self::Anonymized {
-        let original_prefix = self.ptr.prefix_ptr();
-        let anonymized = unsafe { core::mem::transmute::<Self, Self::Anonymized>(self) };
-        let anonymized_prefix = anonymized.ptr.prefix_ptr();
-        assert_eq!(anonymized_prefix, original_prefix, "The allocation prefix was lost in anonimization, this is definitely a bug, please report it.");
-        anonymized
+        // SAFETY: The prefix is always located right before the value, regardless of its type,
+        // so casting the pointer keeps it reachable.
+        unsafe { Arc::from_raw(Arc::into_raw(self).cast()) }
+    }
+}
+
+impl<T, Alloc: IAlloc> crate::IPtr for ArcSlice<T, Alloc> {
+    unsafe fn as_ref(&self) -> AnonymRef<'_> {
+        AnonymRef {
+            ptr: self.inner.start.ptr.cast(),
+            _marker: PhantomData,
+        }
+    }
+}
+impl<T, Alloc: IAlloc> crate::IPtrClone for ArcSlice<T, Alloc> {
+    fn clone(this: &Self) -> Self {
+        this.clone()
+    }
+}
+impl<T, Alloc: IAlloc> crate::IPtrTryAsMut for ArcSlice<T, Alloc> {
+    unsafe fn try_as_mut(&mut self) -> Option<AnonymRefMut<'_>> {
+        Self::is_unique(self).then(|| AnonymRefMut {
+            ptr: self.inner.start.ptr.cast(),
+            _marker: PhantomData,
+        })
+    }
+}
+/// Unlike [`Arc`], [`ArcSlice`] doesn't erase its element type, and may hold any number of elements: it therefore drops
+/// them itself rather than calling the pointee's `drop`.
+/// Like [`Arc`]'s, dropping the last strong reference runs `drop` instead of the elements' own destructor: it is
+/// called on each of the elements in turn.
+impl<T, Alloc: IAlloc> crate::IPtrOwned for ArcSlice<T, Alloc> {
+    fn drop(
+        this: &mut core::mem::ManuallyDrop<Self>,
+        drop: unsafe extern "C" fn(AnonymRefMut<'_>),
+    ) {
+        if !decrement_refcount(&unsafe { this.inner.start.prefix() }.strong) {
+            return;
+        }
+        unsafe {
+            for element in this.as_slice_mut_unchecked() {
+                drop(AnonymRefMut {
+                    ptr: NonNull::from(element).cast(),
+                    _marker: PhantomData,
+                });
+            }
+            _ = WeakSlice { inner: this.inner };
+        }
     }
 }
 
+/// An ABI-stable equivalent to `Arc<dyn Trait>`, where `Vt` is the vtable for `Trait`.
+///
+/// This is the type [`dynptr!(Arc<dyn Trait>)`](crate::dynptr) would expand to, and can be constructed through
+/// [`Arc::new_dyn`]. Like [`dynptr!`](crate::dynptr), [`vtable!`](crate::vtmacro) lets you name `Vt` from `Trait`'s bounds.
+pub type DynArc<'a, Vt, Alloc = DefaultAllocator> = Dyn<'a, Arc<(), Alloc>, Vt>;
+
 impl<T, Alloc: IAlloc> crate::IPtrOwned for Weak<T, Alloc> {
     fn drop(
         this: &mut core::mem::ManuallyDrop<Self>,
         _drop: unsafe extern "C" fn(AnonymRefMut<'_>),
     ) {
-        if unsafe { this.ptr.prefix() }
-            .weak
-            .fetch_sub(1, Ordering::Relaxed)
-            != 1
-        {
+        if !decrement_refcount(&unsafe { this.ptr.prefix() }.weak) {
             return;
         }
         unsafe {
@@ -826,11 +1662,9 @@ impl<T, Alloc: IAlloc> IntoDyn for Weak<T, Alloc> {
     type Anonymized = Weak<(), Alloc>;
     type Target = T;
     fn anonimize(self) -> Self::Anonymized {
-        let original_prefix = self.ptr.prefix_ptr();
-        let anonymized = unsafe { core::mem::transmute::<Self, Self::Anonymized>(self) };
-        let anonymized_prefix = anonymized.ptr.prefix_ptr();
-        assert_eq!(anonymized_prefix, original_prefix, "The allocation prefix was lost in anonimization, this is definitely a bug, please report it.");
-        anonymized
+        // SAFETY: The prefix is always located right before the value, regardless of its type,
+        // so casting the pointer keeps it reachable.
+        unsafe { Weak::from_raw(Weak::into_raw(self).cast()) }
     }
 }
 
@@ -859,8 +1693,38 @@ impl<'a, Vt: HasDropVt + IStable, Alloc: IAlloc> Dyn<'a, Weak<(), Alloc>, Vt> {
     }
 }
 
+impl<'a, Vt: HasDropVt + 'static, Alloc: IAlloc> Dyn<'a, Arc<(), Alloc>, Vt> {
+    /// Downcasts the trait object back into a typed [`Arc`] based on its reflection report.
+    ///
+    /// The reference counts are left untouched, whether the downcast succeeds or not.
+    ///
+    /// # Errors
+    /// Returns `self` if the reports of the trait object and `T` don't match.
+    pub fn stable_downcast<T: IStable, Path>(self) -> Result<Arc<T, Alloc>, Self>
+    where
+        Vt: TransitiveDeref<StabbyVtableAny<'a>, Path> + IConstConstructor<'a, T>,
+    {
+        if !(self.id() == T::ID && self.report() == T::REPORT) {
+            return Err(self);
+        }
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never dropped, so ownership of the pointer is transferred to the returned `Arc`,
+        // whose pointee was just proven to be a `T`.
+        let ptr = unsafe { core::ptr::read(&*this.ptr) };
+        // SAFETY: see above.
+        Ok(unsafe { Arc::from_raw(Arc::into_raw(ptr).cast()) })
+    }
+}
+
 #[crate::stabby]
 /// An owner of an [`Arc<T, Alloc>`] whose pointee can be atomically changed.
+///
+/// The value is stored as the pointer an [`Option<Arc<T, Alloc>>`] is made of: this relies on [`Arc`] being a
+/// non-null pointer, so that `None` is represented by the null pointer. This is checked at compile time.
+///
+/// Loads read the pointer before incrementing its strong count, so a value must not be freed while it may still be
+/// loaded: [`Self::store`] leaks the value it replaces, and the previous value a successful [`Self::compare_exchange`]
+/// returns should be kept alive until no load may still observe it.
 pub struct AtomicArc<T, Alloc: IAlloc> {
     ptr: AtomicPtr<T>,
     alloc: core::marker::PhantomData<*const Alloc>,
@@ -884,10 +1748,87 @@ impl<T, Alloc: IAlloc> Drop for AtomicArc<T, Alloc> {
     }
 }
 
+/// Strengthens a load's `order` to [`Ordering::Acquire`], since the loaded pointer is then dereferenced
+/// to update the reference counts: this requires the store that published it to happen before.
+///
+/// # Panics
+/// If `order` is [`Ordering::Release`] or [`Ordering::AcqRel`], like [`AtomicPtr::load`] does.
+const fn load_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release | Ordering::AcqRel => panic!("there is no such thing as a release load"),
+        Ordering::Relaxed => Ordering::Acquire,
+        order => order,
+    }
+}
+/// Strengthens a read-modify-write's `order` to [`Ordering::AcqRel`], since it both publishes a pointer
+/// and reads one that may then be dereferenced.
+const fn swap_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::SeqCst => Ordering::SeqCst,
+        _ => Ordering::AcqRel,
+    }
+}
+/// Strengthens a store's `order` to [`Ordering::Release`], since loads must be able to acquire the pointee it publishes.
+///
+/// # Panics
+/// If `order` is [`Ordering::Acquire`] or [`Ordering::AcqRel`], like [`AtomicPtr::store`] does.
+const fn store_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Acquire | Ordering::AcqRel => {
+            panic!("there is no such thing as an acquire store")
+        }
+        Ordering::Relaxed => Ordering::Release,
+        order => order,
+    }
+}
+
+/// The operations [`AtomicArc`] and [`AtomicWeak`] perform on the pointer they store.
+///
+/// They are generic over it so that the loom models can run them on loom's atomics, which can't be stored in
+/// these ABI-stable types.
+trait AtomicPointer<T> {
+    fn load(&self, order: Ordering) -> *mut T;
+    fn store(&self, ptr: *mut T, order: Ordering);
+}
+macro_rules! impl_atomic_pointer {
+    ($($atomic: ident)::+) => {
+        impl<T> AtomicPointer<T> for $($atomic)::+<T> {
+            fn load(&self, order: Ordering) -> *mut T {
+                $($atomic)::+::load(self, order)
+            }
+            fn store(&self, ptr: *mut T, order: Ordering) {
+                $($atomic)::+::store(self, ptr, order)
+            }
+        }
+    };
+}
+impl_atomic_pointer!(AtomicPtr);
+#[cfg(loom)]
+impl_atomic_pointer!(loom::sync::atomic::AtomicPtr);
+
 type MaybeArc<T, Alloc> = Option<Arc<T, Alloc>>;
 impl<T, Alloc: IAlloc> AtomicArc<T, Alloc> {
+    /// An [`AtomicArc`] set to `None`, convenient to initialize `static`s with.
+    /// ```
+    /// # use stabby_abi::alloc::{allocators::RustAlloc, sync::{Arc, AtomicArc}};
+    /// # use core::sync::atomic::Ordering;
+    /// static SLOT: AtomicArc<u32, RustAlloc> = AtomicArc::NULL;
+    /// assert!(SLOT.load(Ordering::Acquire).is_none());
+    /// SLOT.store(Some(Arc::new_in(1, RustAlloc::new())), Ordering::Release);
+    /// assert_eq!(*SLOT.load(Ordering::Acquire).unwrap(), 1);
+    /// ```
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const NULL: Self = Self::new(None);
+    /// Ensures that `Option<Arc<T, Alloc>>` is a nullable pointer, which [`Self::new`] transmutes it to.
+    const NICHE_CHECK: () = assert!(
+        core::mem::size_of::<MaybeArc<T, Alloc>>() == core::mem::size_of::<*mut T>()
+            && unsafe { core::mem::transmute::<MaybeArc<T, Alloc>, usize>(None) } == 0,
+        "Option<Arc<T, Alloc>> must be a nullable pointer"
+    );
     /// Constructs a new [`AtomicArc`] set to the provided value.
     pub const fn new(value: MaybeArc<T, Alloc>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NICHE_CHECK;
         Self {
             ptr: AtomicPtr::new(unsafe {
                 core::mem::transmute::<Option<Arc<T, Alloc>>, *mut T>(value)
@@ -896,8 +1837,16 @@ impl<T, Alloc: IAlloc> AtomicArc<T, Alloc> {
         }
     }
     /// Atomically load the current value.
+    ///
+    /// [`Ordering::Relaxed`] is strengthened to [`Ordering::Acquire`], as the value's reference count must be incremented.
+    ///
+    /// # Panics
+    /// If `order` is [`Ordering::Release`] or [`Ordering::AcqRel`].
     pub fn load(&self, order: Ordering) -> MaybeArc<T, Alloc> {
-        let ptr = NonNull::new(self.ptr.load(order))?;
+        Self::load_from(&self.ptr, order)
+    }
+    fn load_from(atomic: &impl AtomicPointer<T>, order: Ordering) -> MaybeArc<T, Alloc> {
+        let ptr = NonNull::new(atomic.load(load_ordering(order)))?;
         unsafe {
             Arc::<T, Alloc>::increment_strong_count(ptr.as_ptr());
             Some(Arc::from_raw(AllocPtr {
@@ -907,19 +1856,34 @@ impl<T, Alloc: IAlloc> AtomicArc<T, Alloc> {
         }
     }
     /// Atomically store a new value.
+    ///
+    /// The previous value's strong count is leaked rather than released: a concurrent [`Self::load`] may have read
+    /// its pointer without having incremented its strong count yet, and releasing it could free the value under its feet.
+    ///
+    /// [`Ordering::Relaxed`] is strengthened to [`Ordering::Release`], as loads must acquire the new value.
+    ///
+    /// # Panics
+    /// If `order` is [`Ordering::Acquire`] or [`Ordering::AcqRel`].
     pub fn store(&self, value: MaybeArc<T, Alloc>, order: Ordering) {
+        Self::store_into(&self.ptr, value, order)
+    }
+    fn store_into(atomic: &impl AtomicPointer<T>, value: MaybeArc<T, Alloc>, order: Ordering) {
         let ptr = value.map_or(core::ptr::null_mut(), |value| Arc::into_raw(value).as_ptr());
-        self.ptr.store(ptr, order)
+        atomic.store(ptr, store_ordering(order))
     }
     /// Compares `self` with `current` by pointer.
+    ///
+    /// `order` is strengthened like [`Self::load`]'s.
     /// # Errors
     /// Returns the new value of `self` if it differs from `current`.
+    /// # Panics
+    /// If `order` is [`Ordering::Release`] or [`Ordering::AcqRel`].
     pub fn is(
         &self,
         current: Option<&Arc<T, Alloc>>,
         order: Ordering,
     ) -> Result<(), MaybeArc<T, Alloc>> {
-        let ptr = NonNull::new(self.ptr.load(order));
+        let ptr = NonNull::new(self.ptr.load(load_ordering(order)));
         match (ptr, current) {
             (None, None) => Ok(()),
             (None, _) => Err(None),
@@ -936,40 +1900,928 @@ impl<T, Alloc: IAlloc> AtomicArc<T, Alloc> {
         }
     }
     /// Replace the current value with the new value.
+    ///
+    /// `success` is strengthened to [`Ordering::AcqRel`] unless it is [`Ordering::SeqCst`], as the new value must be
+    /// published and the previous one acquired. `failure` is strengthened like [`Self::load`]'s.
     /// # Errors
-    /// If `current` no longer points to the same value as `self`, it
+    /// If `current` no longer points to the same value as `self`, `new` is dropped and the value
+    /// `self` actually pointed to is returned.
+    /// # Panics
+    /// If `failure` is [`Ordering::Release`] or [`Ordering::AcqRel`].
     pub fn compare_exchange(
         &self,
         current: Option<&Arc<T, Alloc>>,
         new: MaybeArc<T, Alloc>,
         success: Ordering,
         failure: Ordering,
+    ) -> Result<MaybeArc<T, Alloc>, MaybeArc<T, Alloc>> {
+        self.compare_exchange_with(current, new, |ptr, current, new| {
+            ptr.compare_exchange(current, new, swap_ordering(success), load_ordering(failure))
+        })
+    }
+    /// Replace the current value with the new value.
+    ///
+    /// Unlike [`Self::compare_exchange`], this may spuriously fail even if `current` does point to the
+    /// same value as `self`, which allows for more efficient code on some platforms when used in a loop.
+    /// Orderings are strengthened in the same way.
+    /// # Errors
+    /// If `current` no longer points to the same value as `self`, or on a spurious failure, `new` is
+    /// dropped and the value `self` actually pointed to is returned.
+    /// # Panics
+    /// If `failure` is [`Ordering::Release`] or [`Ordering::AcqRel`].
+    pub fn compare_exchange_weak(
+        &self,
+        current: Option<&Arc<T, Alloc>>,
+        new: MaybeArc<T, Alloc>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<MaybeArc<T, Alloc>, MaybeArc<T, Alloc>> {
+        self.compare_exchange_with(current, new, |ptr, current, new| {
+            ptr.compare_exchange_weak(current, new, swap_ordering(success), load_ordering(failure))
+        })
+    }
+    fn compare_exchange_with(
+        &self,
+        current: Option<&Arc<T, Alloc>>,
+        new: MaybeArc<T, Alloc>,
+        exchange: impl FnOnce(&AtomicPtr<T>, *mut T, *mut T) -> Result<*mut T, *mut T>,
     ) -> Result<MaybeArc<T, Alloc>, MaybeArc<T, Alloc>> {
         let current = current.map_or(core::ptr::null_mut(), |value| value.ptr.ptr.as_ptr());
         let new = new.map_or(core::ptr::null_mut(), |value| Arc::into_raw(value).as_ptr());
-        match self.ptr.compare_exchange(current, new, success, failure) {
-            Ok(ptr) => Ok(NonNull::new(ptr).map(|ptr| unsafe {
+        let from_raw = |ptr: *mut T| {
+            NonNull::new(ptr).map(|ptr| unsafe {
                 Arc::from_raw(AllocPtr {
                     ptr,
                     marker: PhantomData,
                 })
-            })),
-            Err(ptr) => Err(NonNull::new(ptr).map(|ptr| unsafe {
-                Arc::<T, Alloc>::increment_strong_count(ptr.as_ptr());
-                Arc::from_raw(AllocPtr {
+            })
+        };
+        match exchange(&self.ptr, current, new) {
+            // The strong count `self` held on the previous value is transferred to the returned `Arc`.
+            Ok(ptr) => Ok(from_raw(ptr)),
+            Err(ptr) => {
+                // `new` was never stored, reclaim its strong count.
+                drop(from_raw(new));
+                if let Some(ptr) = NonNull::new(ptr) {
+                    unsafe { Arc::<T, Alloc>::increment_strong_count(ptr.as_ptr()) };
+                }
+                Err(from_raw(ptr))
+            }
+        }
+    }
+}
+
+#[crate::stabby]
+/// An owner of a [`Weak<T, Alloc>`] whose pointee can be atomically changed.
+///
+/// Like [`AtomicArc`], this relies on `None` being represented by the null pointer, which is checked at compile time.
+///
+/// Loads likewise read the pointer before incrementing its weak count, so [`Self::store`] leaks the value it replaces,
+/// and [`Self::swap`] is `unsafe`.
+pub struct AtomicWeak<T, Alloc: IAlloc> {
+    ptr: AtomicPtr<T>,
+    alloc: core::marker::PhantomData<*const Alloc>,
+}
+// SAFETY: Same constraints as in `std`.
+unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Send for AtomicWeak<T, Alloc> {}
+// SAFETY: Same constraints as in `std`.
+unsafe impl<T: Send + Sync, Alloc: IAlloc + Send + Sync> Sync for AtomicWeak<T, Alloc> {}
+
+impl<T, Alloc: IAlloc> Drop for AtomicWeak<T, Alloc> {
+    fn drop(&mut self) {
+        let ptr = self.ptr.load(Ordering::Relaxed);
+        if let Some(ptr) = NonNull::new(ptr) {
+            unsafe {
+                Weak::<T, Alloc>::from_raw(AllocPtr {
                     ptr,
                     marker: PhantomData,
                 })
-            })),
+            };
+        }
+    }
+}
+
+type MaybeWeak<T, Alloc> = Option<Weak<T, Alloc>>;
+impl<T, Alloc: IAlloc> AtomicWeak<T, Alloc> {
+    /// An [`AtomicWeak`] set to `None`, convenient to initialize `static`s with.
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const NULL: Self = Self::new(None);
+    /// Ensures that `Option<Weak<T, Alloc>>` is a nullable pointer, which [`Self::new`] transmutes it to.
+    const NICHE_CHECK: () = assert!(
+        core::mem::size_of::<MaybeWeak<T, Alloc>>() == core::mem::size_of::<*mut T>()
+            && unsafe { core::mem::transmute::<MaybeWeak<T, Alloc>, usize>(None) } == 0,
+        "Option<Weak<T, Alloc>> must be a nullable pointer"
+    );
+    /// Constructs a new [`AtomicWeak`] set to the provided value.
+    pub const fn new(value: MaybeWeak<T, Alloc>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::NICHE_CHECK;
+        Self {
+            ptr: AtomicPtr::new(unsafe {
+                core::mem::transmute::<Option<Weak<T, Alloc>>, *mut T>(value)
+            }),
+            alloc: PhantomData,
+        }
+    }
+    /// Atomically load the current value.
+    ///
+    /// [`Ordering::Relaxed`] is strengthened to [`Ordering::Acquire`], as the value's weak count must be incremented.
+    ///
+    /// # Panics
+    /// If `order` is [`Ordering::Release`] or [`Ordering::AcqRel`].
+    pub fn load(&self, order: Ordering) -> MaybeWeak<T, Alloc> {
+        Self::load_from(&self.ptr, order)
+    }
+    fn load_from(atomic: &impl AtomicPointer<T>, order: Ordering) -> MaybeWeak<T, Alloc> {
+        let ptr = NonNull::new(atomic.load(load_ordering(order)))?;
+        let ptr = AllocPtr {
+            ptr,
+            marker: PhantomData,
+        };
+        unsafe {
+            increment_refcount(&ptr.prefix().weak, Ordering::Relaxed);
+            Some(Weak::from_raw(ptr))
+        }
+    }
+    /// Atomically store a new value.
+    ///
+    /// Like [`AtomicArc::store`], the previous value's weak count is leaked rather than released, as a concurrent
+    /// [`Self::load`] may be about to increment it.
+    ///
+    /// [`Ordering::Relaxed`] is strengthened to [`Ordering::Release`], as loads must acquire the new value.
+    ///
+    /// # Panics
+    /// If `order` is [`Ordering::Acquire`] or [`Ordering::AcqRel`].
+    pub fn store(&self, value: MaybeWeak<T, Alloc>, order: Ordering) {
+        Self::store_into(&self.ptr, value, order)
+    }
+    fn store_into(atomic: &impl AtomicPointer<T>, value: MaybeWeak<T, Alloc>, order: Ordering) {
+        let ptr = value.map_or(core::ptr::null_mut(), |value| {
+            Weak::into_raw(value).ptr.as_ptr()
+        });
+        atomic.store(ptr, store_ordering(order))
+    }
+    /// Atomically replace the current value with a new one, returning the previous value.
+    ///
+    /// Any `order` weaker than [`Ordering::AcqRel`] is strengthened to it, as the new value must be published and the
+    /// previous one acquired.
+    ///
+    /// # Safety
+    /// The returned [`Weak`] must not release the last weak count of its allocation while a [`Self::load`] on `self`
+    /// may still be running, as that load may have read its pointer without having incremented its weak count yet.
+    /// This holds if an [`Arc`] or another [`Weak`] to the same value is known to outlive such loads.
+    pub unsafe fn swap(&self, value: MaybeWeak<T, Alloc>, order: Ordering) -> MaybeWeak<T, Alloc> {
+        let ptr = value.map_or(core::ptr::null_mut(), |value| {
+            Weak::into_raw(value).ptr.as_ptr()
+        });
+        NonNull::new(self.ptr.swap(ptr, swap_ordering(order))).map(|ptr| unsafe {
+            Weak::from_raw(AllocPtr {
+                ptr,
+                marker: PhantomData,
+            })
+        })
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_ptr_eq() {
+    let a = Arc::new(1u8);
+    let b = Arc::new(1u8);
+    assert!(Arc::ptr_eq(&a, &a.clone()));
+    assert!(!Arc::ptr_eq(&a, &b));
+    assert!(core::ptr::eq(Arc::as_ptr(&a), &*a));
+    assert_eq!(Arc::strong_count(&a), 1);
+    let zst = Arc::new(());
+    assert!(Arc::ptr_eq(&zst, &zst.clone()));
+    assert!(!Arc::ptr_eq(&zst, &Arc::new(())));
+    let slice: ArcSlice<u8> = ArcSlice::from([1u8, 2, 3].as_slice());
+    assert!(ArcSlice::ptr_eq(&slice, &slice.clone()));
+    assert!(!ArcSlice::ptr_eq(
+        &slice,
+        &ArcSlice::from([1u8, 2, 3].as_slice())
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn atomic_arc_compare_exchange_weak() {
+    const THREADS: usize = 8;
+    const ITERATIONS: usize = 1000;
+    // Every value that is ever published is kept alive here, so that `load` can never observe a freed pointer.
+    let values: crate::alloc::vec::Vec<Arc<usize>> =
+        (0..=THREADS * ITERATIONS).map(Arc::new).collect();
+    let atomic = AtomicArc::new(Some(values[0].clone()));
+    std::thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| {
+                for _ in 0..ITERATIONS {
+                    let mut current = atomic.load(Ordering::Acquire);
+                    loop {
+                        let next = values[**current.as_ref().unwrap() + 1].clone();
+                        match atomic.compare_exchange_weak(
+                            current.as_ref(),
+                            Some(next),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        ) {
+                            Ok(previous) => {
+                                assert!(Arc::ptr_eq(
+                                    previous.as_ref().unwrap(),
+                                    current.as_ref().unwrap()
+                                ));
+                                break;
+                            }
+                            Err(actual) => current = actual,
+                        }
+                    }
+                }
+            });
+        }
+    });
+    let last = atomic.load(Ordering::Acquire).unwrap();
+    assert_eq!(*last, THREADS * ITERATIONS);
+    drop(last);
+    // No strong count may have been leaked or lost along the way.
+    for value in &values[..THREADS * ITERATIONS] {
+        assert_eq!(Arc::strong_count(value), 1);
+    }
+    assert_eq!(Arc::strong_count(&values[THREADS * ITERATIONS]), 2);
+    drop(atomic);
+    assert_eq!(Arc::strong_count(&values[THREADS * ITERATIONS]), 1);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn atomic_arc_orderings() {
+    const VALUES: usize = 1000;
+    let atomic = AtomicArc::new(Some(Arc::new(0usize)));
+    // `Relaxed` is enough to publish values that were built by another thread, and replaced values needn't be kept
+    // alive elsewhere since `store` leaks them.
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            for i in 1..=VALUES {
+                atomic.store(Some(Arc::new(i)), Ordering::Relaxed);
+            }
+        });
+        for _ in 0..2 {
+            s.spawn(|| {
+                let mut last = 0;
+                while last != VALUES {
+                    let value = atomic.load(Ordering::Relaxed).unwrap();
+                    assert!(*value >= last);
+                    last = *value;
+                    if let Err(Some(value)) = atomic.is(Some(&value), Ordering::Relaxed) {
+                        assert!(*value >= last);
+                    }
+                }
+            });
+        }
+    });
+    let last = atomic.load(Ordering::Relaxed).unwrap();
+    assert_eq!(*last, VALUES);
+    assert_eq!(Arc::strong_count(&last), 2);
+    // The previous value's strong count is leaked, not released.
+    atomic.store(None, Ordering::Relaxed);
+    assert_eq!(Arc::strong_count(&last), 2);
+    assert!(std::panic::catch_unwind(|| atomic.load(Ordering::Release)).is_err());
+    assert!(std::panic::catch_unwind(|| atomic.store(None, Ordering::Acquire)).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn atomic_weak() {
+    let a = Arc::new(1u8);
+    let b = Arc::new(2u8);
+    let atomic = AtomicWeak::new(Some(Arc::downgrade(&a)));
+    assert_eq!(Arc::weak_count(&a), 2);
+    let loaded = atomic.load(Ordering::Acquire).unwrap();
+    assert_eq!(Arc::weak_count(&a), 3);
+    assert_eq!(*loaded.upgrade().unwrap(), 1);
+    // `store` leaks the previous value's weak count.
+    atomic.store(Some(Arc::downgrade(&b)), Ordering::Release);
+    assert_eq!(Arc::weak_count(&a), 3);
+    assert_eq!(Arc::weak_count(&b), 2);
+    // SAFETY: no load may run concurrently.
+    let previous = unsafe { atomic.swap(None, Ordering::AcqRel) }.unwrap();
+    assert_eq!(*previous.upgrade().unwrap(), 2);
+    assert!(atomic.load(Ordering::Acquire).is_none());
+    drop(a);
+    assert!(loaded.upgrade().is_none());
+    // SAFETY: see above.
+    assert!(unsafe { atomic.swap(Some(loaded), Ordering::Release) }.is_none());
+    drop(atomic);
+    assert_eq!(Arc::weak_count(&b), 2);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn arc_slice_slice() {
+    let slice: ArcSlice<std::string::String> = (0..6).map(|i| i.to_string()).collect();
+    let middle = ArcSlice::slice(&slice, 1..5);
+    assert_eq!(middle.as_slice(), ["1", "2", "3", "4"]);
+    assert_eq!(ArcSlice::strong_count(&slice), 2);
+    let inner = middle.clone().slice(..=1);
+    assert_eq!(inner.as_slice(), ["1", "2"]);
+    assert!(middle.clone().slice(4..).is_empty());
+    assert!(std::panic::catch_unwind(|| ArcSlice::slice(&slice, 2..7)).is_err());
+    // Views keep the whole slice alive, and its elements are dropped exactly once.
+    drop(slice);
+    drop(middle);
+    assert_eq!(inner.as_slice(), ["1", "2"]);
+    assert_eq!(inner.owner().as_slice(), ["0", "1", "2", "3", "4", "5"]);
+    assert_eq!(ArcSlice::strong_count(inner.owner()), 1);
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_slice_get() {
+    let mut slice: ArcSlice<u8> = ArcSlice::from([1u8, 2, 3].as_slice());
+    assert_eq!(slice.get(2), Some(&3));
+    assert_eq!(slice.get(3), None);
+    *slice.get_mut(0).unwrap() = 4;
+    assert_eq!(slice.get_mut(3), None);
+    let clone = slice.clone();
+    assert_eq!(slice.get_mut(0), None);
+    drop(clone);
+    let weak = WeakSlice::from(&slice);
+    assert_eq!(slice.get_mut(0), None);
+    drop(weak);
+    assert_eq!(slice.as_slice(), [4, 2, 3]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn arc_slice_make_mut() {
+    let mut slice: ArcSlice<std::string::String> = (0..3).map(|i| i.to_string()).collect();
+    let start = slice.as_ptr();
+    ArcSlice::make_mut(&mut slice)[0].push('!');
+    assert_eq!(start, slice.as_ptr());
+    let shared = slice.clone();
+    ArcSlice::make_mut(&mut slice)[1].push('?');
+    assert_ne!(start, slice.as_ptr());
+    assert_eq!(shared.as_slice(), ["0!", "1", "2"]);
+    assert_eq!(slice.as_slice(), ["0!", "1?", "2"]);
+    assert!(ArcSlice::is_unique(&shared) && ArcSlice::is_unique(&slice));
+
+    let mut empty: ArcSlice<u8> = ArcSlice::from([].as_slice());
+    let _weak = WeakSlice::from(&empty);
+    assert!(ArcSlice::make_mut(&mut empty).is_empty());
+    assert!(ArcSlice::is_unique(&empty));
+    let mut zsts = Vec::<()>::new();
+    // SAFETY: `()` is a ZST, so any number of them are initialized.
+    unsafe { zsts.set_len(5) };
+    let mut zsts = ArcSlice::from(zsts);
+    let _shared = zsts.clone();
+    assert_eq!(ArcSlice::make_mut(&mut zsts).len(), 5);
+    assert!(ArcSlice::is_unique(&zsts));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn arc_slice_from_exact_iter() {
+    use std::string::{String, ToString};
+    let slice = ArcSlice::<String>::from_exact_iter((0..100).map(|i| i.to_string()));
+    assert_eq!(slice.len(), 100);
+    assert!(slice.iter().map(|s| s.parse::<i32>().unwrap()).eq(0..100));
+    assert_eq!(
+        unsafe { slice.inner.start.prefix() }
+            .capacity
+            .load(Ordering::Relaxed),
+        100
+    );
+    assert!(ArcSlice::<String>::from_exact_iter(core::iter::empty()).is_empty());
+    let units = ArcSlice::<()>::from_exact_iter((0..7).map(|_| ()));
+    assert_eq!(units.len(), 7);
+
+    /// An iterator advertising more elements than it yields, which may panic instead of yielding its last one.
+    struct Short {
+        values: std::vec::IntoIter<Arc<u8>>,
+        panics: bool,
+    }
+    impl Iterator for Short {
+        type Item = Arc<u8>;
+        fn next(&mut self) -> Option<Arc<u8>> {
+            let value = self.values.next()?;
+            assert!(!(self.panics && self.values.len() == 0));
+            Some(value)
+        }
+    }
+    impl ExactSizeIterator for Short {
+        fn len(&self) -> usize {
+            self.values.len() + 3
+        }
+    }
+    let value = Arc::new(0);
+    let values = || std::vec![value.clone(); 5].into_iter();
+    let slice = ArcSlice::<Arc<u8>>::from_exact_iter(Short {
+        values: values(),
+        panics: false,
+    });
+    assert_eq!(slice.len(), 5);
+    assert_eq!(Arc::strong_count(&value), 6);
+    drop(slice);
+    assert_eq!(Arc::strong_count(&value), 1);
+    let short = Short {
+        values: values(),
+        panics: true,
+    };
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        ArcSlice::<Arc<u8>>::from_exact_iter(short)
+    }))
+    .is_err());
+    assert_eq!(Arc::strong_count(&value), 1);
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_slice_into_arc() {
+    let arc = Arc::new(std::string::String::from("hi"));
+    let clone = arc.clone();
+    let weak = Arc::downgrade(&arc);
+    let (strong, weaks) = (Arc::strong_count(&arc), Arc::weak_count(&arc));
+    let slice = ArcSlice::from(arc);
+    assert_eq!(slice.as_slice(), ["hi"]);
+    assert_eq!(ArcSlice::strong_count(&slice), strong);
+    assert_eq!(ArcSlice::weak_count(&slice), weaks);
+    let Ok(arc) = ArcSlice::try_into_arc(slice) else {
+        panic!("a slice of length 1 should convert back")
+    };
+    assert!(Arc::ptr_eq(&arc, &clone));
+    assert_eq!(Arc::strong_count(&arc), strong);
+    assert_eq!(Arc::weak_count(&arc), weaks);
+    drop(clone);
+    drop(arc);
+    assert!(weak.upgrade().is_none());
+
+    let slice: ArcSlice<u8> = ArcSlice::from([1u8, 2].as_slice());
+    let Err(slice) = ArcSlice::try_into_arc(slice) else {
+        panic!("a slice of length 2 shouldn't convert")
+    };
+    assert_eq!(slice.as_slice(), [1, 2]);
+    let empty: ArcSlice<u8> = ArcSlice::from([].as_slice());
+    assert!(ArcSlice::try_into_arc(empty).is_err());
+    let unit = ArcSlice::from(Arc::new(()));
+    assert_eq!(ArcSlice::strong_count(&unit), 1);
+    assert!(ArcSlice::try_into_arc(unit).is_ok());
+}
+
+#[test]
+#[cfg(all(feature = "std", not(miri)))]
+fn refcount_overflow_aborts() {
+    let arc = Arc::new(0u8);
+    unsafe { arc.ptr.prefix() }
+        .strong
+        .store(MAX_REFCOUNT - 1, Ordering::Relaxed);
+    // SAFETY: `arc` is alive, and its count is restored before it's dropped.
+    unsafe { Arc::<u8>::increment_strong_count(&*arc) };
+    assert_eq!(Arc::strong_count(&arc), MAX_REFCOUNT);
+    if std::env::var_os("STABBY_REFCOUNT_OVERFLOW").is_some() {
+        // This is the child process: overflowing must abort it.
+        core::mem::forget(arc.clone());
+        std::process::exit(0);
+    }
+    let status = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["alloc::sync::refcount_overflow_aborts", "--exact"])
+        .env("STABBY_REFCOUNT_OVERFLOW", "1")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .unwrap();
+    assert!(!status.success());
+    // SIGABRT is 6 on all unix platforms.
+    #[cfg(unix)]
+    assert_eq!(
+        std::os::unix::process::ExitStatusExt::signal(&status),
+        Some(6)
+    );
+    unsafe { arc.ptr.prefix() }
+        .strong
+        .store(1, Ordering::Relaxed);
+}
+
+/// Models two threads racing to drop the last references to a value, one of them having mutated it.
+///
+/// Since [`AllocPrefix`](super::AllocPrefix)'s counts must stay ABI-stable, they can't be swapped for loom's:
+/// the model runs [`decrement_refcount`], which [`Arc`]'s drop goes through, on loom's atomics instead.
+/// Run it with `RUSTFLAGS="--cfg loom" cargo test --release loom`.
+#[cfg(loom)]
+#[test]
+fn loom_racing_drops() {
+    use loom::{cell::UnsafeCell, sync::atomic::AtomicUsize};
+    struct Shared {
+        strong: AtomicUsize,
+        value: UnsafeCell<usize>,
+    }
+    fn drop_ref(shared: &Shared) {
+        if decrement_refcount(&shared.strong) {
+            // Dropping the value races with mutations made through other references, unless they're synchronized.
+            shared.value.with_mut(|value| unsafe { *value = 0 });
+        }
+    }
+    loom::model(|| {
+        let shared = loom::sync::Arc::new(Shared {
+            strong: AtomicUsize::new(2),
+            value: UnsafeCell::new(0),
+        });
+        let writer = {
+            let shared = shared.clone();
+            loom::thread::spawn(move || {
+                // Mutating the value while holding a reference, as `get_mut_unchecked` would.
+                shared.value.with_mut(|value| unsafe { *value = 1 });
+                drop_ref(&shared);
+            })
+        };
+        drop_ref(&shared);
+        writer.join().unwrap();
+    });
+}
+
+/// Models concurrent upgrades racing with the drop of the last strong reference.
+///
+/// Like [`loom_racing_drops`], this runs [`upgrade_refcount`] and [`decrement_refcount`] on loom's atomics.
+/// An upgrade must either fail, or succeed before the value is dropped and keep it alive until it is released.
+#[cfg(loom)]
+#[test]
+fn loom_racing_upgrades() {
+    use loom::{cell::UnsafeCell, sync::atomic::AtomicUsize};
+    const UPGRADERS: usize = 2;
+    struct Shared {
+        strong: AtomicUsize,
+        drops: AtomicUsize,
+        value: UnsafeCell<usize>,
+    }
+    fn drop_ref(shared: &Shared) {
+        if decrement_refcount(&shared.strong) {
+            shared.value.with_mut(|value| unsafe { *value = 0 });
+            shared.drops.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    loom::model(|| {
+        let shared = loom::sync::Arc::new(Shared {
+            strong: AtomicUsize::new(1),
+            drops: AtomicUsize::new(0),
+            value: UnsafeCell::new(1),
+        });
+        let upgraders: std::vec::Vec<_> = (0..UPGRADERS)
+            .map(|_| {
+                let shared = shared.clone();
+                loom::thread::spawn(move || {
+                    if upgrade_refcount(&shared.strong) {
+                        // The value can't have been dropped while this reference is held.
+                        shared.value.with(|value| assert_eq!(unsafe { *value }, 1));
+                        drop_ref(&shared);
+                    }
+                })
+            })
+            .collect();
+        drop_ref(&shared);
+        for upgrader in upgraders {
+            upgrader.join().unwrap();
+        }
+        assert_eq!(shared.strong.load(Ordering::Relaxed), 0);
+        assert_eq!(shared.drops.load(Ordering::Relaxed), 1);
+    });
+}
+
+/// Models an [`AtomicArc::store`] racing with an [`AtomicArc::load`], running their code on loom's [`AtomicPtr`].
+///
+/// The replaced value isn't kept alive elsewhere: since its drop writes to it, loom reports a race should `store`
+/// release it while the loader may still access it. The loader must also observe the stored value fully initialized.
+#[cfg(loom)]
+#[test]
+fn loom_atomic_arc_store_races_load() {
+    use loom::{cell::UnsafeCell, sync::atomic::AtomicPtr};
+    struct Value(UnsafeCell<usize>);
+    impl Drop for Value {
+        fn drop(&mut self) {
+            self.0.with_mut(|value| unsafe { *value = 0 });
         }
     }
+    type Slot = AtomicArc<Value, super::DefaultAllocator>;
+    let new = |value| Some(Arc::new(Value(UnsafeCell::new(value))));
+    loom::model(move || {
+        let atomic = loom::sync::Arc::new(AtomicPtr::new(core::ptr::null_mut()));
+        Slot::store_into(&*atomic, new(1), Ordering::Relaxed);
+        let storer = {
+            let atomic = atomic.clone();
+            loom::thread::spawn(move || Slot::store_into(&*atomic, new(2), Ordering::Relaxed))
+        };
+        let loaded = Slot::load_from(&*atomic, Ordering::Relaxed).unwrap();
+        loaded.0.with(|value| assert_ne!(unsafe { *value }, 0));
+        drop(loaded);
+        storer.join().unwrap();
+    });
+}
+
+/// Models an [`AtomicWeak::store`] racing with an [`AtomicWeak::load`], running their code on loom's [`AtomicPtr`].
+///
+/// The replaced value's only reference is the one stored, so `store` releasing it would free its allocation while the
+/// loader may be about to increment its weak count. The loader must also observe the stored value fully initialized.
+#[cfg(loom)]
+#[test]
+fn loom_atomic_weak_store_races_load() {
+    use loom::{cell::UnsafeCell, sync::atomic::AtomicPtr};
+    type Slot = AtomicWeak<UnsafeCell<usize>, super::DefaultAllocator>;
+    loom::model(|| {
+        let atomic = loom::sync::Arc::new(AtomicPtr::new(core::ptr::null_mut()));
+        // Only `atomic` references the replaced value.
+        Slot::store_into(
+            &*atomic,
+            Some(Arc::downgrade(&Arc::new(UnsafeCell::new(1)))),
+            Ordering::Relaxed,
+        );
+        let storer = {
+            let atomic = atomic.clone();
+            loom::thread::spawn(move || {
+                let stored = Arc::new(UnsafeCell::new(2));
+                Slot::store_into(&*atomic, Some(Arc::downgrade(&stored)), Ordering::Relaxed);
+                // Keeps the stored value upgradeable until the storer is joined.
+                stored
+            })
+        };
+        if let Some(value) = Slot::load_from(&*atomic, Ordering::Relaxed)
+            .unwrap()
+            .upgrade()
+        {
+            value.with(|value| assert_eq!(unsafe { *value }, 2));
+        }
+        storer.join().unwrap();
+    });
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_new_zeroed() {
+    let zeroed = Arc::<[u64; 32]>::new_zeroed();
+    assert_eq!(unsafe { zeroed.assume_init_ref() }, &[0; 32]);
+    assert_eq!(Arc::strong_count(&zeroed), 1);
+    let zeroed = Arc::<[u64; 32]>::new_zeroed_in(super::DefaultAllocator::new());
+    // SAFETY: `u64` is valid when zeroed.
+    let mut zeroed = unsafe { Arc::assume_init(zeroed) };
+    assert_eq!(*zeroed, [0; 32]);
+    Arc::get_mut(&mut zeroed).unwrap()[3] = 3;
+    let weak = Arc::downgrade(&zeroed);
+    assert_eq!(weak.upgrade().unwrap()[3], 3);
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_from_box() {
+    let boxed = Box::new([1u64, 2, 3]);
+    let data = &*boxed as *const [u64; 3];
+    let arc = Arc::from(boxed);
+    assert_eq!(&*arc as *const [u64; 3], data);
+    assert_eq!(*arc, [1, 2, 3]);
+    assert_eq!(Arc::strong_count(&arc), 1);
+    assert_eq!(Arc::weak_count(&arc), 1);
+    let weak = Arc::downgrade(&arc);
+    let clone = arc.clone();
+    drop(arc);
+    assert_eq!(*weak.upgrade().unwrap(), [1, 2, 3]);
+    drop(clone);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn weak_new() {
+    let weak = Weak::<u64>::new();
+    assert!(weak.upgrade().is_none());
+    let clone = weak.clone();
+    drop(weak);
+    assert!(clone.upgrade().is_none());
+    assert!(Weak::<u8>::default().upgrade().is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn weak_upgrade_races_drop() {
+    use core::sync::atomic::AtomicBool;
+    /// Records that it was dropped, checking it only happens once.
+    struct Flagged<'a>(&'a AtomicBool);
+    impl Drop for Flagged<'_> {
+        fn drop(&mut self) {
+            assert!(!self.0.swap(true, Ordering::Relaxed));
+        }
+    }
+    for _ in 0..200 {
+        let dropped = AtomicBool::new(false);
+        let arc = Arc::new(Flagged(&dropped));
+        let weak = Arc::downgrade(&arc);
+        std::thread::scope(|s| {
+            for _ in 0..3 {
+                s.spawn(|| {
+                    for _ in 0..100 {
+                        let Some(upgraded) = weak.upgrade() else {
+                            break;
+                        };
+                        assert!(!upgraded.0.load(Ordering::Relaxed));
+                        assert!(Arc::strong_count(&upgraded) >= 1);
+                        drop(upgraded.clone());
+                    }
+                });
+            }
+            drop(arc);
+        });
+        assert!(dropped.load(Ordering::Relaxed));
+        assert_eq!((weak.strong_count(), weak.weak_count()), (0, 1));
+        assert!(weak.upgrade().is_none());
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn weak_ref() {
+    fn get(weak: WeakRef<'_, u8>) -> Option<u8> {
+        weak.upgrade().map(|arc| *arc)
+    }
+    let arc = Arc::new(1u8);
+    let weak_ref = Arc::weak_ref(&arc);
+    assert_eq!((weak_ref.strong_count(), weak_ref.weak_count()), (1, 1));
+    assert_eq!(get(weak_ref), Some(1));
+    assert_eq!(Arc::counts(&arc), (1, 1));
+    let value: &u8 = weak_ref.get().unwrap();
+    assert!(core::ptr::eq(value, &*arc));
+    assert_eq!(Arc::counts(&arc), (1, 1));
+    let weak = Weak::from(weak_ref);
+    assert_eq!(Arc::counts(&arc), (1, 2));
+    assert_eq!(get((&weak).into()), Some(1));
+    assert!(WeakRef::from(&weak).get().is_none());
+    drop(arc);
+    // Borrowing a `Weak` doesn't keep the value alive.
+    let weak_ref = WeakRef::from(&weak);
+    assert_eq!((weak_ref.strong_count(), weak_ref.weak_count()), (0, 1));
+    assert_eq!(get(weak_ref), None);
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn weak_counts() {
+    let arc = Arc::new(0u8);
+    let weak = Arc::downgrade(&arc);
+    let clone = arc.clone();
+    assert_eq!((weak.strong_count(), weak.weak_count()), (2, 2));
+    // Simulate an older version's `upgrade` holding the lock bit.
+    let strong = &unsafe { arc.ptr.prefix() }.strong;
+    strong.fetch_or(USIZE_TOP_BIT, Ordering::Relaxed);
+    assert_eq!(weak.strong_count(), 2);
+    assert_eq!(Arc::strong_count(&arc), 2);
+    strong.fetch_and(!USIZE_TOP_BIT, Ordering::Relaxed);
+    drop((arc, clone));
+    assert_eq!((weak.strong_count(), weak.weak_count()), (0, 1));
+    assert_eq!(Weak::<u8>::new().strong_count(), 0);
+
+    let slice = ArcSlice::<u8>::from([1, 2].as_slice());
+    let weak = WeakSlice::from(&slice);
+    assert_eq!((weak.strong_count(), weak.weak_count()), (1, 2));
+    drop(slice);
+    assert_eq!((weak.strong_count(), weak.weak_count()), (0, 1));
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn weak_slice_new() {
+    let never = WeakSlice::<u64>::new();
+    assert!(never.upgrade().is_none());
+    assert!(never.clone().upgrade().is_none());
+    // A weak reference to a live empty slice must still upgrade.
+    let empty: ArcSlice<u64> = ArcSlice::from([].as_slice());
+    let weak = WeakSlice::from(&empty);
+    assert!(weak.upgrade().unwrap().is_empty());
+    drop(empty);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_new_cyclic() {
+    struct Node {
+        this: Weak<Node>,
+        value: u8,
+    }
+    let node = Arc::new_cyclic(|this| {
+        assert!(this.upgrade().is_none());
+        Node {
+            this: this.clone(),
+            value: 42,
+        }
+    });
+    let upgraded = node.this.upgrade().unwrap();
+    assert!(Arc::ptr_eq(&node, &upgraded));
+    assert_eq!(upgraded.value, 42);
+    assert_eq!(Arc::strong_count(&node), 2);
+    drop(upgraded);
+    let weak = Arc::downgrade(&node);
+    drop(node);
+    assert!(weak.upgrade().is_none());
+    let panicked = std::panic::catch_unwind(|| {
+        Arc::<u8>::new_cyclic(|_| panic!("construction failure"));
+    });
+    assert!(panicked.is_err());
+}
+
+#[test]
+#[cfg(all(feature = "std", feature = "alloc-rs"))]
+fn arc_slice_from_iter_unwinds() {
+    use core::sync::atomic::AtomicIsize;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    static LIVE_ALLOCATIONS: AtomicIsize = AtomicIsize::new(0);
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    #[derive(Default)]
+    struct Tracking(super::allocators::RustAlloc);
+    impl IAlloc for Tracking {
+        fn alloc(&mut self, layout: super::Layout) -> *mut () {
+            LIVE_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            self.0.alloc(layout)
+        }
+        unsafe fn free(&mut self, ptr: *mut ()) {
+            LIVE_ALLOCATIONS.fetch_sub(1, Ordering::Relaxed);
+            unsafe { self.0.free(ptr) }
+        }
+    }
+    struct Counted(#[allow(dead_code)] u32);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    let counted = |n| {
+        (0..1000).map(move |i| {
+            assert!(i < n, "the iterator panicked");
+            Counted(i)
+        })
+    };
+    for n in [0, 1, 5, 100] {
+        DROPS.store(0, Ordering::Relaxed);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            counted(n).collect::<ArcSlice<Counted, Tracking>>()
+        }));
+        assert!(result.is_err());
+        assert_eq!(DROPS.load(Ordering::Relaxed), n as usize);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::Relaxed), 0);
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            ArcSlice::from_exact_iter_in(counted(n), Tracking::default())
+        }));
+        assert!(result.is_err());
+        assert_eq!(DROPS.load(Ordering::Relaxed), 2 * n as usize);
+        assert_eq!(LIVE_ALLOCATIONS.load(Ordering::Relaxed), 0);
+    }
+}
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn over_aligned() {
+    #[repr(align(64))]
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Align64([u8; 80]);
+    for _ in 0..16 {
+        let value = Arc::new(Align64([7; 80]));
+        assert_eq!(Arc::as_ptr(&value) as usize % 64, 0);
+        assert_eq!(*value, Align64([7; 80]));
+        let weak = Arc::downgrade(&value);
+        assert_eq!(weak.upgrade().as_deref(), Some(&Align64([7; 80])));
+        let slice: ArcSlice<Align64> = (0..5).map(|i| Align64([i; 80])).collect::<Vec<_>>().into();
+        assert_eq!(slice.as_slice().as_ptr() as usize % 64, 0);
+        assert!(slice.iter().enumerate().all(|(i, v)| v.0 == [i as u8; 80]));
+    }
+}
+#[test]
+#[cfg(feature = "alloc-rs")]
+fn arc_unwrap_or_clone() {
+    let a = Arc::new(crate::alloc::vec::Vec::<u8>::from([1, 2, 3].as_slice()));
+    let b = a.clone();
+    let cloned = Arc::unwrap_or_clone(a);
+    assert_eq!(cloned, [1, 2, 3]);
+    assert!(Arc::is_unique(&b));
+    let ptr = b.as_ptr();
+    let moved = Arc::unwrap_or_clone(b);
+    assert_eq!(moved.as_ptr(), ptr);
 }
 
 #[cfg(feature = "serde")]
 mod serde_impl {
     use super::*;
-    use crate::alloc::IAlloc;
-    use serde::{Deserialize, Serialize};
+    use crate::alloc::{vec::VecVisitor, IAlloc};
+    use serde::{de::Visitor, Deserialize, Serialize};
+    impl<T: Serialize, Alloc: IAlloc> Serialize for Arc<T, Alloc> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            let value: &T = self;
+            value.serialize(serializer)
+        }
+    }
+    impl<'a, T: Deserialize<'a>, Alloc: IAlloc + Default> Deserialize<'a> for Arc<T, Alloc> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'a>,
+        {
+            let value = T::deserialize(deserializer)?;
+            Arc::try_new_in(value, Alloc::default())
+                .map_err(|_| serde::de::Error::custom(crate::alloc::AllocationError()))
+        }
+    }
     impl<T: Serialize, Alloc: IAlloc> Serialize for ArcSlice<T, Alloc> {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
@@ -984,7 +2836,37 @@ mod serde_impl {
         where
             D: serde::Deserializer<'a>,
         {
-            crate::alloc::vec::Vec::deserialize(deserializer).map(Into::into)
+            deserializer.deserialize_seq(ArcSliceVisitor(core::marker::PhantomData))
+        }
+    }
+    pub struct ArcSliceVisitor<T, Alloc>(core::marker::PhantomData<(T, Alloc)>);
+    impl<'a, T: Deserialize<'a>, Alloc: IAlloc + Default> Visitor<'a> for ArcSliceVisitor<T, Alloc> {
+        type Value = ArcSlice<T, Alloc>;
+        fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+            formatter.write_str("A sequence")
+        }
+        /// When the sequence's length is hinted, the elements are deserialized straight into a buffer of that size,
+        /// which then becomes the slice's allocation without being copied.
+        ///
+        /// Unlike [`VecVisitor`]'s, the hint isn't capped: a buffer that had to grow past the cap would keep its extra
+        /// capacity once it becomes the slice's. Should allocating it fail, as it would for a wildly wrong hint, or
+        /// should the length be unknown, the elements are collected by [`VecVisitor`] instead.
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'a>,
+        {
+            let Some(Ok(mut this)) = seq
+                .size_hint()
+                .map(|hint| Vec::try_with_capacity_in(hint, Alloc::default()))
+            else {
+                return VecVisitor(core::marker::PhantomData)
+                    .visit_seq(seq)
+                    .map(Into::into);
+            };
+            while let Some(v) = seq.next_element()? {
+                this.push(v);
+            }
+            Ok(this.into())
         }
     }
     impl<Alloc: IAlloc> Serialize for ArcStr<Alloc> {
@@ -1005,3 +2887,354 @@ mod serde_impl {
         }
     }
 }
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl {
+    use super::*;
+    use crate::alloc::{vec::Vec, IAlloc};
+    use rkyv::{
+        rancor::Fallible,
+        ser::{Allocator, Writer},
+        vec::{ArchivedVec, VecResolver},
+        Archive, Deserialize, Place, Serialize,
+    };
+    impl<T: Archive, Alloc: IAlloc> Archive for ArcSlice<T, Alloc> {
+        type Archived = ArchivedVec<T::Archived>;
+        type Resolver = VecResolver;
+        fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            ArchivedVec::resolve_from_slice(self.as_slice(), resolver, out);
+        }
+    }
+    impl<T: Serialize<S>, Alloc: IAlloc, S: Fallible + Allocator + Writer + ?Sized> Serialize<S>
+        for ArcSlice<T, Alloc>
+    {
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedVec::<T::Archived>::serialize_from_slice(self.as_slice(), serializer)
+        }
+    }
+    impl<T: Archive, Alloc: IAlloc + Default, D: Fallible + ?Sized>
+        Deserialize<ArcSlice<T, Alloc>, D> for ArchivedVec<T::Archived>
+    where
+        T::Archived: Deserialize<T, D>,
+    {
+        fn deserialize(&self, deserializer: &mut D) -> Result<ArcSlice<T, Alloc>, D::Error> {
+            Deserialize::<Vec<T, Alloc>, D>::deserialize(self, deserializer).map(Into::into)
+        }
+    }
+}
+
+#[cfg(all(feature = "rkyv", feature = "std", feature = "libc", unix))]
+#[test]
+fn rkyv_mmap() {
+    use crate::alloc::vec::Vec;
+    use rkyv::{rancor::Error, vec::ArchivedVec, Archived};
+    use std::io::Write;
+    let slice: ArcSlice<u32> = (0..1000).collect();
+    let bytes = rkyv::to_bytes::<Error>(&slice).unwrap();
+    let path = std::env::temp_dir().join(std::format!("stabby-rkyv-{}", std::process::id()));
+    let mut file = std::fs::File::create(&path).unwrap();
+    file.write_all(&bytes).unwrap();
+    drop(file);
+    let file = std::fs::File::open(&path).unwrap();
+    let map = unsafe {
+        libc::mmap(
+            core::ptr::null_mut(),
+            bytes.len(),
+            libc::PROT_READ,
+            libc::MAP_PRIVATE,
+            std::os::fd::AsRawFd::as_raw_fd(&file),
+            0,
+        )
+    };
+    assert_ne!(map, libc::MAP_FAILED);
+    let mapped = unsafe { core::slice::from_raw_parts(map.cast::<u8>(), bytes.len()) };
+    let archived = rkyv::access::<ArchivedVec<Archived<u32>>, Error>(mapped).unwrap();
+    // The archived slice is read straight from the mapping.
+    assert!(mapped.as_ptr_range().contains(&archived.as_ptr().cast()));
+    assert!(archived.iter().map(|x| x.to_native()).eq(0..1000));
+    let copy: ArcSlice<u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+    assert_eq!(copy, slice);
+    let vec: Vec<u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+    assert_eq!(vec.as_slice(), slice.as_slice());
+    unsafe { libc::munmap(map, bytes.len()) };
+    std::fs::remove_file(path).unwrap();
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+#[test]
+fn deserialize() {
+    use crate::alloc::{boxed::Box, vec::Vec};
+    use serde::de::{
+        value::{Error, SeqDeserializer},
+        Deserialize, IntoDeserializer,
+    };
+    let arc = Arc::<u32>::deserialize(IntoDeserializer::<Error>::into_deserializer(3u32)).unwrap();
+    assert_eq!((*arc, Arc::strong_count(&arc)), (3, 1));
+    let boxed = Box::<Vec<u32>>::deserialize(SeqDeserializer::<_, Error>::new(0..100u32)).unwrap();
+    assert!(boxed.iter().copied().eq(0..100));
+    let shared =
+        Arc::<Box<Vec<u32>>>::deserialize(SeqDeserializer::<_, Error>::new(0..3u32)).unwrap();
+    assert_eq!(shared.as_slice(), [0, 1, 2]);
+    /// An allocator that always fails.
+    #[derive(Default)]
+    struct Oom;
+    impl IAlloc for Oom {
+        fn alloc(&mut self, _: crate::alloc::Layout) -> *mut () {
+            core::ptr::null_mut()
+        }
+        unsafe fn free(&mut self, _: *mut ()) {}
+    }
+    let de = || IntoDeserializer::<Error>::into_deserializer(3u32);
+    let err = Arc::<u32, Oom>::deserialize(de()).unwrap_err();
+    assert_eq!(err.to_string(), "AllocationError");
+    let err = Box::<u32, Oom>::deserialize(de()).unwrap_err();
+    assert_eq!(err.to_string(), "AllocationError");
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+#[test]
+fn deserialize_arc_slice_in_place() {
+    use serde::de::{
+        value::{Error, SeqDeserializer},
+        Deserialize,
+    };
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    #[derive(Default)]
+    struct Counting(super::allocators::RustAlloc);
+    impl IAlloc for Counting {
+        fn alloc(&mut self, layout: super::Layout) -> *mut () {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            self.0.alloc(layout)
+        }
+        unsafe fn free(&mut self, ptr: *mut ()) {
+            unsafe { self.0.free(ptr) }
+        }
+    }
+    // Well past the 1MiB `Vec`'s visitor caps its preallocation to.
+    const LEN: u32 = 1_000_000;
+    let slice =
+        ArcSlice::<u32, Counting>::deserialize(SeqDeserializer::<_, Error>::new(0..LEN)).unwrap();
+    assert!(slice.iter().copied().eq(0..LEN));
+    // The buffer was allocated once to the hinted length, and became the slice's without being copied.
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), 1);
+    let capacity = unsafe { slice.inner.start.prefix() }
+        .capacity
+        .load(Ordering::Relaxed);
+    assert_eq!(capacity, LEN as usize);
+    // Without a hint, the elements are still collected.
+    let unhinted = (0..LEN).filter(|_| true);
+    let slice =
+        ArcSlice::<u32, Counting>::deserialize(SeqDeserializer::<_, Error>::new(unhinted)).unwrap();
+    assert!(slice.iter().copied().eq(0..LEN));
+}
+
+#[cfg(all(feature = "bytemuck", feature = "alloc-rs"))]
+#[test]
+fn cast_slice() {
+    use crate::alloc::vec::Vec;
+    use bytemuck::PodCastError;
+    let bytes: Vec<u8> = (0..16).collect();
+    let words = bytes.cast_slice::<u64>();
+    assert_eq!(words.len(), 2);
+    assert_eq!(bytemuck::cast_slice::<u64, u8>(words), bytes.as_slice());
+    let shared: ArcSlice<u8> = bytes.into();
+    assert_eq!(shared.cast_slice::<u32>().len(), 4);
+    assert_eq!(
+        shared.try_cast_slice::<[u8; 3]>(),
+        Err(PodCastError::OutputSliceWouldHaveSlop)
+    );
+    // The data of empty containers may be dangling, but casting them is still fine.
+    assert!(Vec::<u8>::new().cast_slice::<u64>().is_empty());
+    let odd: Vec<u8> = (0..10).collect();
+    assert_eq!(
+        odd.try_cast_slice::<u32>(),
+        Err(PodCastError::OutputSliceWouldHaveSlop)
+    );
+    assert_eq!(
+        odd.cast_slice::<[u8; 5]>(),
+        [[0, 1, 2, 3, 4], [5, 6, 7, 8, 9]]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hex_fmt() {
+    let bytes: ArcSlice<u8> = [0x0a, 0xbc].into_iter().collect();
+    assert_eq!(format!("{bytes:02x}"), "0abc");
+    assert_eq!(format!("{bytes:02X}"), "0ABC");
+    assert_eq!(format!("{bytes:#04x}"), "0x0a:0xbc");
+    assert_eq!(format!("{:x}", ArcSlice::<u8>::from_iter([])), "");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn arc_value_semantics() {
+    use std::collections::{BTreeSet, HashMap};
+    let key = Arc::new(3u32);
+    let twin = Arc::new(3u32);
+    assert!(key == twin && !Arc::ptr_eq(&key, &twin));
+    assert!(Arc::new(2u32) < key);
+    let mut map = HashMap::new();
+    map.insert(key, "three");
+    assert_eq!(map.get(&twin), Some(&"three"));
+    let set: BTreeSet<_> = [5u32, 1, 3].into_iter().map(Arc::new).collect();
+    assert!(set.iter().map(|x| **x).eq([1, 3, 5]));
+    assert_eq!(Arc::new(f32::NAN).partial_cmp(&Arc::new(0.)), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn arc_counts() {
+    let arc = Arc::new(0u8);
+    assert_eq!(Arc::counts(&arc), (1, 1));
+    let clone = arc.clone();
+    let weak = Arc::downgrade(&arc);
+    assert_eq!(Arc::counts(&arc), (2, 2));
+    let upgraded = weak.upgrade().unwrap();
+    assert_eq!(Arc::counts(&upgraded), (3, 2));
+    drop((clone, upgraded, weak));
+    assert_eq!(
+        Arc::counts(&arc),
+        (Arc::strong_count(&arc), Arc::weak_count(&arc))
+    );
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn arc_slice_iptr() {
+    use super::allocators::{RustAlloc, TrackingAlloc};
+    use crate::{IPtr, IPtrClone, IPtrOwned, IPtrTryAsMut};
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    let mut vec = Vec::new_in(alloc.clone());
+    vec.extend((0..4).map(|i| Arc::new_in(i, alloc.clone())));
+    let mut slice = ArcSlice::from(vec);
+    let start = slice.as_slice().as_ptr();
+    assert_eq!(
+        unsafe { IPtr::as_ref(&slice) }.as_ptr(),
+        start.cast_mut().cast()
+    );
+    let ptr = unsafe { slice.try_as_mut() }.unwrap();
+    *unsafe { ptr.cast::<Arc<i32, TrackingAlloc>>().as_mut() } = Arc::new_in(10, alloc.clone());
+    assert_eq!(*slice[0], 10);
+    let clone = IPtrClone::clone(&slice);
+    assert!(unsafe { slice.try_as_mut() }.is_none());
+    drop(clone);
+    let weak = WeakSlice::from(&slice);
+    assert!(unsafe { slice.try_as_mut() }.is_none());
+    drop(weak);
+    assert_eq!(
+        unsafe { slice.try_as_mut() }.map(|ptr| ptr.as_ptr()),
+        Some(start.cast_mut().cast())
+    );
+    // Dropping the slice through `IPtrOwned` runs the passed destructor on each element, once the last strong
+    // reference is dropped.
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    unsafe extern "C" fn counted_drop(value: crate::AnonymRefMut<'_>) {
+        DROPS.fetch_add(1, Ordering::Relaxed);
+        unsafe { core::ptr::drop_in_place(value.ptr.cast::<Arc<i32, TrackingAlloc>>().as_ptr()) }
+    }
+    let clone = slice.clone();
+    IPtrOwned::drop(&mut core::mem::ManuallyDrop::new(clone), counted_drop);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+    assert_eq!(alloc.live_allocations(), 5);
+    IPtrOwned::drop(&mut core::mem::ManuallyDrop::new(slice), counted_drop);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 4);
+    assert_eq!(alloc.live_allocations(), 0);
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn zst_slices() {
+    use super::{
+        allocators::{RustAlloc, TrackingAlloc},
+        boxed::BoxedSlice,
+    };
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+    #[derive(Debug, PartialEq)]
+    struct Zst;
+    impl Drop for Zst {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    for len in [0, 1, 2, 7, 1000, 100_000] {
+        DROPS.store(0, Ordering::Relaxed);
+        let mut vec = Vec::new_in(alloc.clone());
+        vec.extend((0..len).map(|_| Zst));
+        assert_eq!(
+            (vec.len(), vec.capacity()),
+            (len, usize::MAX - addr(vec.inner.start.ptr))
+        );
+        assert!(vec.try_reserve(usize::MAX).is_err());
+        let slice = ArcSlice::from(vec);
+        assert_eq!((slice.len(), slice.is_empty()), (len, len == 0));
+        assert_eq!(slice.iter().count(), len);
+        assert_eq!(slice.inner.zst_len(), len);
+        let clone = slice.clone();
+        assert!(slice.iter().zip(clone.iter()).all(|(a, b)| a == b));
+        assert!(Vec::try_from(clone).is_err());
+        assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+        drop(slice);
+        assert_eq!(DROPS.load(Ordering::Relaxed), len);
+        // Allocating the slice directly, from an iterator.
+        let slice = ArcSlice::from_exact_iter_in((0..len).map(|_| Zst), alloc.clone());
+        assert_eq!(slice.len(), len);
+        drop(slice);
+        let slice: ArcSlice<Zst> = (0..len).map(|_| Zst).collect();
+        assert_eq!(slice.as_slice().len(), len);
+        drop(slice);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 3 * len);
+        let mut boxed: BoxedSlice<Zst> = (0..len).map(|_| Zst).collect();
+        assert!(boxed.try_push(Zst).is_ok());
+        assert_eq!(boxed.len(), len + 1);
+        let vec = Vec::from(boxed);
+        assert_eq!(vec.len(), len + 1);
+        drop(vec);
+        assert_eq!(DROPS.load(Ordering::Relaxed), 4 * len + 1);
+        assert_eq!(alloc.live_allocations(), 0);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn arc_slice_chunks() {
+    use super::allocators::{RustAlloc, TrackingAlloc};
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    let mut vec = Vec::new_in(alloc.clone());
+    vec.extend((0..10).map(|i| Arc::new_in(i, alloc.clone())));
+    let slice = ArcSlice::from(vec);
+    let cloned: std::vec::Vec<_> = slice.iter_cloned().collect();
+    assert!(cloned.iter().map(|arc| **arc).eq(0..10));
+    assert!(cloned.iter().all(|arc| Arc::strong_count(arc) == 2));
+    drop(cloned);
+    for (chunk_size, lens) in [
+        (1, &[1; 10][..]),
+        (3, &[3, 3, 3, 1]),
+        (5, &[5, 5]),
+        (10, &[10]),
+        (100, &[10]),
+    ] {
+        let chunks = ArcSlice::chunks(&slice, chunk_size);
+        assert_eq!(chunks.len(), lens.len());
+        let chunks: std::vec::Vec<_> = chunks.collect();
+        assert!(chunks
+            .iter()
+            .map(|chunk| chunk.len())
+            .eq(lens.iter().copied()));
+        assert!(chunks.iter().flatten().map(|arc| **arc).eq(0..10));
+        assert_eq!(ArcSlice::strong_count(&slice), 1 + lens.len());
+    }
+    // Chunks keep the slice alive on their own, and can be dropped in any order.
+    let mut chunks: std::vec::Vec<_> = ArcSlice::chunks(&slice, 4).collect();
+    drop(slice);
+    assert_eq!(ArcSlice::strong_count(chunks[0].owner()), 3);
+    let last = chunks.pop().unwrap();
+    let handle = std::thread::spawn(move || last.iter().map(|arc| **arc).sum::<i32>());
+    drop(chunks);
+    assert_eq!(handle.join().unwrap(), 17);
+    assert_eq!(alloc.live_allocations(), 0);
+    let empty: ArcSlice<u8> = ArcSlice::from_exact_iter(core::iter::empty());
+    assert_eq!(ArcSlice::chunks(&empty, 2).next(), None);
+    assert!(std::panic::catch_unwind(|| ArcSlice::chunks(&empty, 0)).is_err());
+}
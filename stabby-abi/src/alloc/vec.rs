@@ -46,18 +46,59 @@ pub struct Vec<T, Alloc: IAlloc = super::DefaultAllocator> {
     pub(crate) inner: VecInner<T, Alloc>,
 }
 
-pub(crate) const fn ptr_diff<T>(lhs: NonNull<T>, rhs: NonNull<T>) -> usize {
-    let diff = if core::mem::size_of::<T>() == 0 {
-        unsafe { lhs.as_ptr().cast::<u8>().offset_from(rhs.as_ptr().cast()) }
-    } else {
-        unsafe { lhs.as_ptr().offset_from(rhs.as_ptr()) }
-    };
+/// Returns the address of `ptr`.
+///
+/// Unlike `ptr as usize`, this is allowed in `const fn`s. Evaluating it at compile time only succeeds for pointers
+/// that were never derived from an allocation, such as [`NonNull::dangling`], which are the only ones an empty
+/// collection may hold in a constant.
+pub(crate) const fn addr<T>(ptr: NonNull<T>) -> usize {
+    // SAFETY: pointers and `usize` have the same layout, and transmuting a pointer to an integer only discards
+    // its provenance.
+    unsafe { core::mem::transmute::<NonNull<T>, usize>(ptr) }
+}
+/// Returns the number of `T`s between `rhs` and `lhs`, in the manner of `<*const T>::offset_from`.
+///
+/// For zero-sized types, pointers are treated as byte-counters: this is how [`Vec`] and the other
+/// `stabby` collections track their length when `T` is a ZST, using [`ptr_add`] to increment them.
+/// Since such pointers may lie outside of any allocation, their distance is computed from their addresses.
+///
+/// # Safety
+/// `lhs` must be greater than or equal to `rhs`, and unless `T` is zero-sized, the same requirements as
+/// `<*const T>::offset_from` apply: both pointers must be derived from the same allocation, and their distance
+/// must be a multiple of `T`'s size.
+pub const unsafe fn ptr_diff<T>(lhs: NonNull<T>, rhs: NonNull<T>) -> usize {
+    if core::mem::size_of::<T>() == 0 {
+        debug_assert!(addr(lhs) >= addr(rhs));
+        return addr(lhs).wrapping_sub(addr(rhs));
+    }
+    let diff = unsafe { lhs.as_ptr().offset_from(rhs.as_ptr()) };
     debug_assert!(diff >= 0);
     diff as usize
 }
-pub(crate) const fn ptr_add<T>(lhs: NonNull<T>, rhs: usize) -> NonNull<T> {
+/// Offsets `lhs` by `rhs` `T`s, in the manner of `<*const T>::add`.
+///
+/// For zero-sized types, the pointer is offset by `rhs` bytes instead, so that [`ptr_diff`] may later retrieve `rhs`.
+/// ```
+/// # use core::ptr::NonNull;
+/// # use stabby_abi::alloc::{ptr_add, ptr_diff};
+/// let mut array = [0u32; 4];
+/// let start = NonNull::from(&mut array).cast::<u32>();
+/// let end = unsafe { ptr_add(start, 4) };
+/// assert_eq!(unsafe { ptr_diff(end, start) }, 4);
+/// let start = NonNull::<()>::dangling();
+/// let end = unsafe { ptr_add(start, 7) };
+/// assert_eq!(unsafe { ptr_diff(end, start) }, 7);
+/// ```
+///
+/// Since the result may then lie outside of any allocation, it is computed using wrapping arithmetic, which
+/// keeps `lhs`'s provenance without requiring the offset to stay in bounds.
+///
+/// # Safety
+/// The same requirements as `<*const T>::add` apply: unless `T` is zero-sized, the result must be in bounds of
+/// (or one byte past) the allocation `lhs` points to. For zero-sized types, `lhs + rhs` must not overflow nor be null.
+pub const unsafe fn ptr_add<T>(lhs: NonNull<T>, rhs: usize) -> NonNull<T> {
     if core::mem::size_of::<T>() == 0 {
-        unsafe { NonNull::new_unchecked(lhs.as_ptr().cast::<u8>().add(rhs)).cast() }
+        unsafe { NonNull::new_unchecked(lhs.as_ptr().cast::<u8>().wrapping_add(rhs)).cast() }
     } else {
         unsafe { NonNull::new_unchecked(lhs.as_ptr().add(rhs)) }
     }
@@ -66,12 +107,21 @@ pub(crate) const fn ptr_add<T>(lhs: NonNull<T>, rhs: usize) -> NonNull<T> {
 #[cfg(not(stabby_default_alloc = "disabled"))]
 impl<T> Vec<T> {
     /// Constructs a new vector with the default allocator. This doesn't actually allocate.
+    ///
+    /// This is a `const fn`, so empty vectors may be stored in `static`s:
+    /// ```
+    /// # use stabby_abi::alloc::vec::Vec;
+    /// static EMPTY: Vec<u8> = Vec::new();
+    /// assert!(EMPTY.is_empty());
+    /// ```
     pub const fn new() -> Self {
         Self::new_in(super::DefaultAllocator::new())
     }
 }
 impl<T, Alloc: IAlloc> Vec<T, Alloc> {
     /// Constructs a new vector in `alloc`. This doesn't actually allocate.
+    ///
+    /// This is a `const fn`: provided `alloc` can be constructed in a const context, so can the vector.
     pub const fn new_in(alloc: Alloc) -> Self {
         let start = AllocPtr::dangling();
         Self {
@@ -79,7 +129,7 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
                 start,
                 end: start.ptr,
                 capacity: if Self::zst_mode() {
-                    unsafe { core::mem::transmute::<usize, NonNull<T>>(usize::MAX) }
+                    AllocSlice::from_zst_len(start, usize::MAX - addr(start.ptr)).end
                 } else {
                     start.ptr
                 },
@@ -125,21 +175,65 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
     {
         Self::try_with_capacity_in(capacity, Alloc::default())
     }
+    /// Constructs a vector of `len` elements in `alloc`, whose memory is zeroed using [`IAlloc::alloc_zeroed`].
+    ///
+    /// # Safety
+    /// The all-zeroes bit pattern must be a valid value of `T`.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide a large enough allocation.
+    pub unsafe fn with_zeroed_in(len: usize, mut alloc: Alloc) -> Self {
+        if Self::zst_mode() || len == 0 {
+            let mut this = Self::new_in(alloc);
+            unsafe { this.set_len(len) };
+            return this;
+        }
+        let Some(start) = AllocPtr::alloc_array_zeroed(&mut alloc, len) else {
+            panic!("Allocation failed")
+        };
+        let end = ptr_add(start.ptr, len);
+        Self {
+            inner: VecInner {
+                start,
+                end,
+                capacity: end,
+                alloc,
+            },
+        }
+    }
+    /// Constructs a vector of `len` elements, whose memory is zeroed using [`IAlloc::alloc_zeroed`].
+    ///
+    /// # Safety
+    /// The all-zeroes bit pattern must be a valid value of `T`.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide a large enough allocation.
+    pub unsafe fn with_zeroed(len: usize) -> Self
+    where
+        Alloc: Default,
+    {
+        unsafe { Self::with_zeroed_in(len, Alloc::default()) }
+    }
     #[inline(always)]
     const fn zst_mode() -> bool {
         core::mem::size_of::<T>() == 0
     }
     /// Returns the number of elements in the vector.
     pub const fn len(&self) -> usize {
-        ptr_diff(self.inner.end, self.inner.start.ptr)
+        // SAFETY: `end` is always `len` elements past `start`.
+        unsafe { ptr_diff(self.inner.end, self.inner.start.ptr) }
     }
     /// Returns `true` if the vector is empty.
     pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
     /// Sets the length of the vector, not calling any destructors.
+    ///
+    /// This is typically used after initializing elements through [`Self::spare_capacity_mut`].
     /// # Safety
-    /// This can lead to uninitialized memory being interpreted as an initialized value of `T`.
+    /// `len` must not exceed [`Self::capacity`], and the first `len` elements must be initialized.
+    ///
+    /// When shrinking the vector, the elements past `len` are forgotten rather than dropped.
     #[rustversion::attr(since(1.86), const)]
     pub unsafe fn set_len(&mut self, len: usize) {
         self.inner.end = ptr_add(self.inner.start.ptr, len);
@@ -152,8 +246,10 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
         if self.inner.end == self.inner.capacity {
             self.grow();
         }
-        unsafe { self.inner.end.as_ptr().write(value) }
-        self.inner.end = ptr_add(self.inner.end, 1)
+        unsafe {
+            self.inner.end.as_ptr().write(value);
+            self.inner.end = ptr_add(self.inner.end, 1);
+        }
     }
     /// Adds `value` at the end of `self`.
     ///
@@ -166,21 +262,53 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
         if self.inner.end == self.inner.capacity && self.try_grow().is_err() {
             return Err(value);
         }
-        unsafe { self.inner.end.as_ptr().write(value) }
-        self.inner.end = ptr_add(self.inner.end, 1);
+        unsafe {
+            self.inner.end.as_ptr().write(value);
+            self.inner.end = ptr_add(self.inner.end, 1);
+        }
         Ok(())
     }
     /// The total capacity of the vector.
     pub const fn capacity(&self) -> usize {
-        ptr_diff(self.inner.capacity, self.inner.start.ptr)
+        // SAFETY: `capacity` is always `capacity` elements past `start`.
+        unsafe { ptr_diff(self.inner.capacity, self.inner.start.ptr) }
     }
     /// The remaining number of elements that can be pushed before reallocating.
     pub const fn remaining_capacity(&self) -> usize {
-        ptr_diff(self.inner.capacity, self.inner.end)
+        // SAFETY: `end` never exceeds `capacity`.
+        unsafe { ptr_diff(self.inner.capacity, self.inner.end) }
+    }
+    /// Returns the vector's spare capacity: the possibly uninitialized slots between its last element and the end
+    /// of its allocation.
+    ///
+    /// Once some of these slots have been initialized, starting from the first one,
+    /// [`Self::set_len`] may be used to make them part of the vector.
+    /// ```
+    /// # use stabby_abi::alloc::vec::Vec;
+    /// let mut vec: Vec<u8> = Vec::with_capacity(10);
+    /// vec.push(0);
+    /// let spare = vec.spare_capacity_mut();
+    /// for (i, slot) in spare.iter_mut().take(3).enumerate() {
+    ///     slot.write(i as u8 + 1);
+    /// }
+    /// // SAFETY: the 3 elements following the first one have just been initialized.
+    /// unsafe { vec.set_len(4) };
+    /// assert_eq!(vec.as_slice(), [0, 1, 2, 3]);
+    /// ```
+    #[rustversion::attr(since(1.83), const)]
+    pub fn spare_capacity_mut(&mut self) -> &mut [core::mem::MaybeUninit<T>] {
+        // SAFETY: the slots past `end` are part of the allocation, and `MaybeUninit` may be uninitialized.
+        unsafe {
+            core::slice::from_raw_parts_mut(
+                self.inner.end.as_ptr().cast(),
+                self.remaining_capacity(),
+            )
+        }
     }
-    const FIRST_CAPACITY: usize = match 1024 / core::mem::size_of::<T>() {
-        0 => 1,
-        v @ 1..=8 => v,
+    const FIRST_CAPACITY: usize = match 1024usize.checked_div(core::mem::size_of::<T>()) {
+        Some(0) => 1,
+        Some(v @ 1..=8) => v,
+        // Zero-sized types never grow, as they start with the largest possible capacity.
         _ => 8,
     };
     fn grow(&mut self) {
@@ -212,14 +340,19 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
     /// otherwise returns Err(AllocationError)
     pub fn try_reserve(&mut self, additional: usize) -> Result<NonMaxUsize, AllocationError> {
         if self.remaining_capacity() < additional {
+            if Self::zst_mode() {
+                return Err(AllocationError());
+            }
             let len = self.len();
-            let new_capacity = len + additional;
+            let Some(new_capacity) = len.checked_add(additional) else {
+                return Err(AllocationError());
+            };
             let old_capacity = self.capacity();
             let start = if old_capacity != 0 {
                 unsafe {
                     self.inner
                         .start
-                        .realloc(&mut self.inner.alloc, old_capacity, new_capacity)
+                        .grow(&mut self.inner.alloc, old_capacity, new_capacity)
                 }
             } else {
                 AllocPtr::alloc_array(&mut self.inner.alloc, new_capacity)
@@ -227,8 +360,8 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
             let Some(start) = start else {
                 return Err(AllocationError());
             };
-            let end = ptr_add(*start, len);
-            let capacity = ptr_add(*start, new_capacity);
+            // SAFETY: `start` was allocated for `new_capacity >= len` elements.
+            let (end, capacity) = unsafe { (ptr_add(*start, len), ptr_add(*start, new_capacity)) };
             self.inner.start = start;
             self.inner.end = end;
             self.inner.capacity = capacity;
@@ -241,18 +374,86 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
             Ok(unsafe { NonMaxUsize::new_unchecked(capacity) })
         }
     }
+    /// Shrinks the capacity of `self` to match its length, freeing the allocation entirely if `self` is empty.
+    ///
+    /// Should the allocator fail to provide the smaller allocation, `self` keeps its current allocation.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self.len();
+        let capacity = self.capacity();
+        if Self::zst_mode() || capacity == len {
+            return;
+        }
+        if len == 0 {
+            unsafe { self.inner.start.free(&mut self.inner.alloc) };
+            let start = AllocPtr::dangling();
+            self.inner.start = start;
+            self.inner.end = start.ptr;
+            self.inner.capacity = start.ptr;
+            return;
+        }
+        if let Some(start) = unsafe {
+            self.inner
+                .start
+                .shrink(&mut self.inner.alloc, capacity, len)
+        } {
+            self.inner.start = start;
+            // SAFETY: `start` was allocated for `len` elements.
+            self.inner.end = unsafe { ptr_add(*start, len) };
+            self.inner.capacity = self.inner.end;
+        }
+    }
     /// Removes all elements from `self` from the `len`th onward.
     ///
     /// Does nothing if `self.len() <= len`
     pub fn truncate(&mut self, len: usize) {
-        if self.len() <= len {
+        let original_len = self.len();
+        if original_len <= len {
             return;
         }
         unsafe {
-            core::ptr::drop_in_place(&mut self[len..]);
-            self.set_len(len)
+            // The length is updated first so that a panicking destructor can't cause double drops.
+            self.set_len(len);
+            core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(
+                self.inner.start.as_ptr().add(len),
+                original_len - len,
+            ));
         };
     }
+    /// Resizes `self` to `new_len`, either by truncating it, or by pushing clones of `value` onto it.
+    ///
+    /// Should cloning `value` panic, the clones that were already pushed are kept in `self`.
+    ///
+    /// # Panics
+    /// If the allocator failed to provide an appropriate allocation.
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        if new_len <= len {
+            self.truncate(new_len);
+            return;
+        }
+        self.reserve(new_len - len);
+        for _ in len + 1..new_len {
+            unsafe {
+                self.inner.end.as_ptr().write(value.clone());
+                self.inner.end = ptr_add(self.inner.end, 1);
+            }
+        }
+        unsafe {
+            self.inner.end.as_ptr().write(value);
+            self.inner.end = ptr_add(self.inner.end, 1);
+        }
+    }
+    /// Converts `self` into a [`BoxedSlice`](crate::alloc::boxed::BoxedSlice), shrinking its capacity to fit its length first.
+    ///
+    /// Should the allocator fail to provide the smaller allocation, the boxed slice keeps the vector's allocation,
+    /// and the capacity is kept track of so that converting it back into a [`Vec`] doesn't lose it.
+    pub fn into_boxed_slice(mut self) -> crate::alloc::boxed::BoxedSlice<T, Alloc> {
+        self.shrink_to_fit();
+        self.into()
+    }
     /// Returns a slice of the vector's elements.
     #[rustversion::attr(since(1.86), const)]
     pub fn as_slice(&self) -> &[T] {
@@ -267,6 +468,13 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
         let end = self.inner.end;
         unsafe { core::slice::from_raw_parts_mut(start.ptr.as_ptr(), ptr_diff(end, start.ptr)) }
     }
+    /// Returns a mutable slice of the vector's elements.
+    ///
+    /// This is an alias for [`Self::as_slice_mut`], named after [`alloc::vec::Vec::as_mut_slice`](https://doc.rust-lang.org/stable/alloc/vec/struct.Vec.html#method.as_mut_slice).
+    #[rustversion::attr(since(1.86), const)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.as_slice_mut()
+    }
     pub(crate) fn into_raw_components(self) -> (AllocSlice<T, Alloc>, usize, Alloc) {
         let VecInner {
             start,
@@ -310,6 +518,36 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
         }
         Ok(())
     }
+    /// Moves all of `other`'s elements to the end of `self`, leaving `other` empty.
+    ///
+    /// `other` keeps its allocation, so that it may be refilled without reallocating.
+    /// # Panics
+    /// If extending required an allocation that failed.
+    pub fn append(&mut self, other: &mut Self) {
+        self.try_append(other).unwrap();
+    }
+    /// Moves all of `other`'s elements to the end of `self`, leaving `other` empty.
+    ///
+    /// `other` keeps its allocation, so that it may be refilled without reallocating.
+    /// # Errors
+    /// If extending required an allocation that failed, in which case neither vector is modified.
+    pub fn try_append(&mut self, other: &mut Self) -> Result<(), AllocationError> {
+        let count = other.len();
+        if count == 0 {
+            return Ok(());
+        }
+        self.try_reserve(count)?;
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                other.inner.start.as_ptr(),
+                self.inner.end.as_ptr(),
+                count,
+            );
+            other.set_len(0);
+            self.set_len(self.len() + count);
+        }
+        Ok(())
+    }
     /// Iterates immutably over the vector's elements.
     pub fn iter(&self) -> core::slice::Iter<'_, T> {
         self.into_iter()
@@ -318,6 +556,45 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
     pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
         self.into_iter()
     }
+    /// Sorts the vector's elements, without preserving the order of equal elements.
+    ///
+    /// See [`slice::sort_unstable`].
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_slice_mut().sort_unstable()
+    }
+    /// Sorts the vector's elements according to `compare`, without preserving the order of equal elements.
+    ///
+    /// See [`slice::sort_unstable_by`].
+    pub fn sort_unstable_by<F: FnMut(&T, &T) -> core::cmp::Ordering>(&mut self, compare: F) {
+        self.as_slice_mut().sort_unstable_by(compare)
+    }
+    /// Binary searches the sorted vector for `x`.
+    ///
+    /// See [`slice::binary_search`].
+    ///
+    /// # Errors
+    /// Returns the index at which `x` could be inserted while keeping the vector sorted if it wasn't found.
+    pub fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.as_slice().binary_search(x)
+    }
+    /// Binary searches the vector, sorted according to `compare`, for an element for which `compare` returns [`Equal`](core::cmp::Ordering::Equal).
+    ///
+    /// See [`slice::binary_search_by`].
+    ///
+    /// # Errors
+    /// Returns the index at which such an element could be inserted while keeping the vector sorted if it wasn't found.
+    pub fn binary_search_by<F: FnMut(&T) -> core::cmp::Ordering>(
+        &self,
+        compare: F,
+    ) -> Result<usize, usize> {
+        self.as_slice().binary_search_by(compare)
+    }
     /// Removes the specified range from the vector in bulk,
     /// returning all removed elements as an iterator.
     /// If the iterator is dropped before being fully consumed,
@@ -358,6 +635,8 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
     ///
     /// If the drain is leaked, then the vector may lose and leak elements,
     /// even if they weren't in the specified `range`
+    ///
+    /// Returns `None` if the range has a negative size, or if the range exceeeds `self.len()`
     pub fn try_drain<R: core::ops::RangeBounds<usize>>(
         &mut self,
         range: R,
@@ -373,7 +652,7 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
             core::ops::Bound::Excluded(i) => *i,
             core::ops::Bound::Unbounded => original_len,
         };
-        if to >= from || to <= original_len {
+        if to < from || to > original_len {
             return None;
         }
         unsafe { self.set_len(from) };
@@ -385,7 +664,32 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
             original_len,
         })
     }
+    /// Inserts `value` at `index`, shifting all elements after it to the right.
+    ///
+    /// Inserting at `self.len()` is equivalent to [`Self::push`].
+    ///
+    /// # Panics
+    /// If `index > self.len()`, or if the vector tried to grow due to being full, and the
+    /// allocator failed to provide a new allocation.
+    pub fn insert(&mut self, index: usize, value: T) {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {index}) should be <= len (is {len})"
+        );
+        if self.inner.end == self.inner.capacity {
+            self.grow();
+        }
+        unsafe {
+            let at = self.inner.start.as_ptr().add(index);
+            core::ptr::copy(at, at.add(1), len - index);
+            at.write(value);
+            self.set_len(len + 1);
+        }
+    }
     /// Removes the element at `index` without reordering.
+    ///
+    /// Returns `None` if `index` is out of bounds.
     #[rustversion::attr(since(1.86), const)]
     pub fn remove(&mut self, index: usize) -> Option<T> {
         if index < self.len() {
@@ -430,15 +734,107 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
             }
         }
     }
-    /// Removes the element at `index`, moving the last element in its place.
+    /// Removes the element at `index` in `O(1)`, moving the last element in its place.
     ///
     /// This is more efficient than [`Self::remove`], but causes reordering.
+    ///
+    /// Like [`Self::remove`], returns `None` if `index` is out of bounds.
+    #[rustversion::attr(since(1.86), const)]
     pub fn swap_remove(&mut self, index: usize) -> Option<T> {
-        if index >= self.len() {
+        let len = self.len();
+        if index >= len {
             return None;
         }
-        self.swap(index, self.len() - 1);
-        self.pop()
+        unsafe {
+            let base = self.inner.start.ptr.as_ptr();
+            let value = base.add(index).read();
+            // If `index` is the last element, this copies it onto itself, which `copy` allows.
+            core::ptr::copy(base.add(len - 1), base.add(index), 1);
+            self.set_len(len - 1);
+            Some(value)
+        }
+    }
+    /// Splits `self` in two at `at`, returning a newly allocated vector containing the elements `[at..]`,
+    /// while `self` keeps the elements `[..at]` and its capacity.
+    ///
+    /// # Panics
+    /// If `at > self.len()`, or if the allocator failed to provide an appropriate allocation.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        Alloc: Clone,
+    {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {at}) should be <= len (is {len})"
+        );
+        let tail_len = len - at;
+        let mut tail = Self::with_capacity_in(tail_len, self.inner.alloc.clone());
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                self.inner.start.as_ptr().add(at),
+                tail.inner.start.as_ptr(),
+                tail_len,
+            );
+            self.set_len(at);
+            tail.set_len(tail_len);
+        }
+        tail
+    }
+    /// Retains only the elements for which `f` returns `true`, dropping the others in place.
+    ///
+    /// The order of the retained elements is preserved.
+    ///
+    /// Should `f` or an element's destructor panic, `self` is left in a valid state: the elements
+    /// that weren't visited yet are kept, and no element is dropped twice.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        struct Guard<'a, T, Alloc: IAlloc> {
+            vec: &'a mut Vec<T, Alloc>,
+            processed: usize,
+            deleted: usize,
+            original_len: usize,
+        }
+        impl<T, Alloc: IAlloc> Drop for Guard<'_, T, Alloc> {
+            fn drop(&mut self) {
+                let start = self.vec.inner.start.as_ptr();
+                unsafe {
+                    if self.deleted > 0 {
+                        core::ptr::copy(
+                            start.add(self.processed),
+                            start.add(self.processed - self.deleted),
+                            self.original_len - self.processed,
+                        );
+                    }
+                    self.vec.set_len(self.original_len - self.deleted);
+                }
+            }
+        }
+        let original_len = self.len();
+        // Elements are only accounted for by the guard until it's dropped.
+        unsafe { self.set_len(0) };
+        let mut guard = Guard {
+            vec: self,
+            processed: 0,
+            deleted: 0,
+            original_len,
+        };
+        let start = guard.vec.inner.start.as_ptr();
+        while guard.processed < original_len {
+            let current = unsafe { start.add(guard.processed) };
+            if f(unsafe { &*current }) {
+                if guard.deleted > 0 {
+                    unsafe {
+                        core::ptr::copy_nonoverlapping(current, current.sub(guard.deleted), 1)
+                    };
+                }
+                guard.processed += 1;
+            } else {
+                // Marking the element as processed first ensures it won't be dropped twice should its destructor panic.
+                guard.processed += 1;
+                guard.deleted += 1;
+                unsafe { core::ptr::drop_in_place(current) };
+            }
+        }
     }
     /// Returns a reference to the vector's allocator.
     pub const fn allocator(&self) -> &Alloc {
@@ -451,13 +847,41 @@ impl<T, Alloc: IAlloc> Vec<T, Alloc> {
     }
 }
 
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod, Alloc: IAlloc> Vec<T, Alloc> {
+    /// Reinterprets the elements as a slice of `U`.
+    ///
+    /// The elements are stored right after an [`AllocPrefix`](crate::alloc::AllocPrefix), and are therefore aligned
+    /// for any type that isn't more aligned than the prefix: in practice, only size mismatches cause this to fail.
+    ///
+    /// # Errors
+    /// Returns [`bytemuck::PodCastError`] if the size of the elements isn't a multiple of `U`'s size,
+    /// or if they aren't sufficiently aligned for `U`.
+    pub fn try_cast_slice<U: bytemuck::Pod>(&self) -> Result<&[U], bytemuck::PodCastError> {
+        crate::alloc::try_cast_slice(self.as_slice())
+    }
+    /// Reinterprets the elements as a slice of `U`.
+    ///
+    /// # Panics
+    /// If [`Self::try_cast_slice`] would fail.
+    pub fn cast_slice<U: bytemuck::Pod>(&self) -> &[U] {
+        match self.try_cast_slice() {
+            Ok(slice) => slice,
+            Err(e) => panic!("failed to cast Vec: {e}"),
+        }
+    }
+}
 impl<T: Clone, Alloc: IAlloc + Clone> Clone for Vec<T, Alloc> {
     fn clone(&self) -> Self {
         let mut ret = Self::with_capacity_in(self.len(), self.inner.alloc.clone());
-        for (i, item) in self.iter().enumerate() {
-            unsafe { ret.inner.start.ptr.as_ptr().add(i).write(item.clone()) }
+        for item in self.iter() {
+            // SAFETY: `ret` was allocated for exactly `self.len()` elements. Its length is bumped after each write,
+            // so that the clones made so far are dropped if a later one panics.
+            unsafe {
+                ret.inner.end.as_ptr().write(item.clone());
+                ret.inner.end = ptr_add(ret.inner.end, 1);
+            }
         }
-        unsafe { ret.set_len(self.len()) };
         ret
     }
 }
@@ -466,6 +890,7 @@ impl<T: PartialEq, Alloc: IAlloc, Rhs: AsRef<[T]>> PartialEq<Rhs> for Vec<T, All
         self.as_slice() == other.as_ref()
     }
 }
+slice_eq!(Vec);
 impl<T: Eq, Alloc: IAlloc> Eq for Vec<T, Alloc> {}
 impl<T: PartialOrd, Alloc: IAlloc, Rhs: AsRef<[T]>> PartialOrd<Rhs> for Vec<T, Alloc> {
     fn partial_cmp(&self, other: &Rhs) -> Option<core::cmp::Ordering> {
@@ -521,6 +946,11 @@ impl<T, Alloc: IAlloc> core::convert::AsRef<[T]> for Vec<T, Alloc> {
         self.as_slice()
     }
 }
+impl<T, Alloc: IAlloc> core::borrow::Borrow<[T]> for Vec<T, Alloc> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
 impl<T, Alloc: IAlloc> core::ops::DerefMut for Vec<T, Alloc> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_slice_mut()
@@ -554,20 +984,10 @@ impl<T: Copy, Alloc: IAlloc + Default> From<&[T]> for Vec<T, Alloc> {
 impl<T, Alloc: IAlloc> core::iter::Extend<T> for Vec<T, Alloc> {
     fn extend<Iter: IntoIterator<Item = T>>(&mut self, iter: Iter) {
         let iter = iter.into_iter();
-        let (min, max) = iter.size_hint();
-        match max {
-            Some(max) => {
-                self.reserve(max);
-                iter.for_each(|item| {
-                    unsafe { self.inner.end.as_ptr().write(item) };
-                    self.inner.end = ptr_add(self.inner.end, 1);
-                })
-            }
-            _ => {
-                self.reserve(min);
-                iter.for_each(|item| self.push(item))
-            }
-        }
+        // Only the lower bound is reserved: `size_hint` can't be trusted for memory safety,
+        // and the upper bound may be much larger than what the iterator actually yields.
+        self.reserve(iter.size_hint().0);
+        iter.for_each(|item| self.push(item))
     }
 }
 
@@ -594,28 +1014,12 @@ impl<T: Debug, Alloc: IAlloc> Debug for Vec<T, Alloc> {
 }
 impl<T: core::fmt::LowerHex, Alloc: IAlloc> core::fmt::LowerHex for Vec<T, Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut first = true;
-        for item in self {
-            if !first {
-                f.write_str(":")?;
-            }
-            first = false;
-            core::fmt::LowerHex::fmt(item, f)?;
-        }
-        Ok(())
+        super::fmt_hex(self.as_slice(), f, core::fmt::LowerHex::fmt)
     }
 }
 impl<T: core::fmt::UpperHex, Alloc: IAlloc> core::fmt::UpperHex for Vec<T, Alloc> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut first = true;
-        for item in self {
-            if !first {
-                f.write_str(":")?;
-            }
-            first = false;
-            core::fmt::UpperHex::fmt(item, f)?;
-        }
-        Ok(())
+        super::fmt_hex(self.as_slice(), f, core::fmt::UpperHex::fmt)
     }
 }
 impl<'a, T, Alloc: IAlloc> IntoIterator for &'a Vec<T, Alloc> {
@@ -650,6 +1054,10 @@ pub struct IntoIter<T, Alloc: IAlloc> {
 }
 impl<T, Alloc: IAlloc> Iterator for IntoIter<T, Alloc> {
     type Item = T;
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.vec.len() - self.index;
+        (remaining, Some(remaining))
+    }
     fn next(&mut self) -> Option<Self::Item> {
         (self.index < self.vec.len()).then(|| unsafe {
             let ret = self.vec.inner.start.as_ptr().add(self.index).read();
@@ -658,6 +1066,20 @@ impl<T, Alloc: IAlloc> Iterator for IntoIter<T, Alloc> {
         })
     }
 }
+impl<T, Alloc: IAlloc> DoubleEndedIterator for IntoIter<T, Alloc> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.vec.len() {
+            self.vec.pop()
+        } else {
+            None
+        }
+    }
+}
+impl<T, Alloc: IAlloc> ExactSizeIterator for IntoIter<T, Alloc> {
+    fn len(&self) -> usize {
+        self.vec.len() - self.index
+    }
+}
 impl<T, Alloc: IAlloc> Drop for IntoIter<T, Alloc> {
     fn drop(&mut self) {
         unsafe {
@@ -750,7 +1172,7 @@ pub struct DoubleEndedDrain<'a, T: 'a, Alloc: IAlloc + 'a> {
 impl<'a, T: 'a, Alloc: IAlloc + 'a> Iterator for DoubleEndedDrain<'a, T, Alloc> {
     type Item = T;
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.to - self.lindex;
+        let remaining = self.rindex - self.lindex;
         (remaining, Some(remaining))
     }
     fn next(&mut self) -> Option<Self::Item> {
@@ -764,9 +1186,8 @@ impl<'a, T: 'a, Alloc: IAlloc + 'a> Iterator for DoubleEndedDrain<'a, T, Alloc>
 impl<'a, T: 'a, Alloc: IAlloc + 'a> DoubleEndedIterator for DoubleEndedDrain<'a, T, Alloc> {
     fn next_back(&mut self) -> Option<Self::Item> {
         (self.lindex < self.rindex).then(|| unsafe {
-            let ret = self.vec.inner.start.as_ptr().add(self.rindex).read();
             self.rindex -= 1;
-            ret
+            self.vec.inner.start.as_ptr().add(self.rindex).read()
         })
     }
 }
@@ -833,8 +1254,316 @@ fn test() {
     assert_eq!(new.as_slice(), capacity.as_slice());
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn drain() {
+    let mut vec: Vec<std::string::String> = (0..10).map(|i| i.to_string()).collect();
+    assert_eq!(vec.drain(3..3).count(), 0);
+    assert_eq!(vec.len(), 10);
+    assert!(vec.try_drain(5..11).is_none());
+    let (from, to) = (5, 4);
+    assert!(vec.try_drain(from..to).is_none());
+    assert_eq!(
+        vec.try_drain(8..).unwrap().collect::<std::vec::Vec<_>>(),
+        ["8", "9"]
+    );
+    // Partially consumed drains still remove their whole range.
+    let mut drain = vec.drain(1..4);
+    assert_eq!(drain.next().as_deref(), Some("1"));
+    drop(drain);
+    assert_eq!(vec.as_slice(), ["0", "4", "5", "6", "7"]);
+    let mut drain = vec.drain(1..=3).double_ended();
+    assert_eq!(drain.len(), 3);
+    assert_eq!(drain.next_back().as_deref(), Some("6"));
+    assert_eq!(drain.next().as_deref(), Some("4"));
+    assert_eq!(drain.size_hint(), (1, Some(1)));
+    assert_eq!(drain.next_back().as_deref(), Some("5"));
+    assert_eq!(drain.next_back(), None);
+    drop(drain);
+    assert_eq!(vec.as_slice(), ["0", "7"]);
+    // Leaking a drain leaks elements, but leaves the vector in a consistent state.
+    core::mem::forget(vec.drain(1..));
+    assert_eq!(vec.as_slice(), ["0"]);
+    vec.push("1".into());
+    assert_eq!(vec.as_slice(), ["0", "1"]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn insert_remove() {
+    let mut vec: Vec<std::string::String> = Vec::new();
+    vec.insert(0, "b".into());
+    vec.insert(0, "a".into());
+    vec.insert(2, "d".into());
+    vec.insert(2, "c".into());
+    assert_eq!(vec.as_slice(), ["a", "b", "c", "d"]);
+    assert_eq!(vec.remove(1).as_deref(), Some("b"));
+    assert_eq!(vec.remove(3), None);
+    assert_eq!(vec.as_slice(), ["a", "c", "d"]);
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.insert(4, "e".into())));
+    assert!(result.is_err());
+    assert_eq!(vec.as_slice(), ["a", "c", "d"]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn truncate_resize() {
+    let mut vec: Vec<std::string::String> = Vec::new();
+    vec.resize(3, "a".into());
+    assert_eq!(vec.as_slice(), ["a", "a", "a"]);
+    vec.resize(5, "b".into());
+    assert_eq!(vec.as_slice(), ["a", "a", "a", "b", "b"]);
+    vec.resize(2, "c".into());
+    assert_eq!(vec.as_slice(), ["a", "a"]);
+    vec.truncate(3);
+    assert_eq!(vec.len(), 2);
+    vec.truncate(0);
+    assert!(vec.is_empty());
+
+    let clones = core::sync::atomic::AtomicUsize::new(0);
+    let drops = core::sync::atomic::AtomicUsize::new(0);
+    struct Fragile<'a>(
+        &'a core::sync::atomic::AtomicUsize,
+        &'a core::sync::atomic::AtomicUsize,
+    );
+    impl Clone for Fragile<'_> {
+        fn clone(&self) -> Self {
+            assert!(self.0.fetch_add(1, core::sync::atomic::Ordering::Relaxed) < 3);
+            Fragile(self.0, self.1)
+        }
+    }
+    impl Drop for Fragile<'_> {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
+    let mut vec: Vec<Fragile> = Vec::new();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.resize(10, Fragile(&clones, &drops))
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec.len(), 3);
+    // Only `value` has been dropped by the unwinding so far.
+    assert_eq!(drops.load(core::sync::atomic::Ordering::Relaxed), 1);
+    drop(vec);
+    assert_eq!(drops.load(core::sync::atomic::Ordering::Relaxed), 4);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn split_off() {
+    let mut vec: Vec<std::string::String> = (0..5).map(|i| i.to_string()).collect();
+    let tail = vec.split_off(2);
+    assert_eq!(vec.as_slice(), ["0", "1"]);
+    assert_eq!(tail.as_slice(), ["2", "3", "4"]);
+    assert!(vec.split_off(2).is_empty());
+    let all = vec.split_off(0);
+    assert!(vec.is_empty());
+    assert_eq!(all.as_slice(), ["0", "1"]);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.split_off(1)));
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn into_iter() {
+    let vec: Vec<std::string::String> = (0..5).map(|i| i.to_string()).collect();
+    let mut iter = vec.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next().as_deref(), Some("0"));
+    assert_eq!(iter.next_back().as_deref(), Some("4"));
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    // The remaining elements are dropped along with the iterator.
+    drop(iter);
+    let vec: Vec<std::string::String> = (0..5).map(|i| i.to_string()).collect();
+    assert_eq!(
+        vec.into_iter().rev().collect::<std::vec::Vec<_>>(),
+        ["4", "3", "2", "1", "0"]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn shrink_to_fit() {
+    let mut vec: Vec<std::string::String> = Vec::with_capacity(20);
+    vec.extend((0..5).map(|i| i.to_string()));
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), 5);
+    assert_eq!(vec.as_slice(), ["0", "1", "2", "3", "4"]);
+    vec.push("5".into());
+    assert_eq!(vec.len(), 6);
+    vec.truncate(0);
+    vec.shrink_to_fit();
+    assert_eq!(vec.capacity(), 0);
+    vec.push("0".into());
+    assert_eq!(vec.as_slice(), ["0"]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn as_mut_slice() {
+    let mut vec: Vec<u8> = [3, 1, 2].as_slice().into();
+    vec[0] = 4;
+    vec.sort();
+    vec.iter_mut().for_each(|i| *i *= 2);
+    vec.as_mut_slice()[2] += 1;
+    assert_eq!(vec.as_slice(), [2, 4, 9]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn with_zeroed() {
+    let mut vec: Vec<u64> = unsafe { Vec::with_zeroed(100) };
+    assert_eq!(vec.len(), 100);
+    assert!(vec.iter().all(|i| *i == 0));
+    vec.push(1);
+    assert_eq!(vec.len(), 101);
+    let empty: Vec<u64> = unsafe { Vec::with_zeroed(0) };
+    assert!(empty.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn grow_shrink() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    static GROWS: AtomicUsize = AtomicUsize::new(0);
+    static SHRINKS: AtomicUsize = AtomicUsize::new(0);
+    #[derive(Default)]
+    struct Counting(crate::alloc::allocators::RustAlloc);
+    impl IAlloc for Counting {
+        fn alloc(&mut self, layout: super::Layout) -> *mut () {
+            self.0.alloc(layout)
+        }
+        unsafe fn free(&mut self, ptr: *mut ()) {
+            unsafe { self.0.free(ptr) }
+        }
+        unsafe fn realloc(
+            &mut self,
+            ptr: *mut (),
+            prev: super::Layout,
+            new_size: usize,
+        ) -> *mut () {
+            unsafe { self.0.realloc(ptr, prev, new_size) }
+        }
+        unsafe fn grow(&mut self, ptr: *mut (), old: super::Layout, new: super::Layout) -> *mut () {
+            assert!(new.size >= old.size);
+            GROWS.fetch_add(1, Ordering::Relaxed);
+            unsafe { self.realloc(ptr, old, new.size) }
+        }
+        unsafe fn shrink(
+            &mut self,
+            ptr: *mut (),
+            old: super::Layout,
+            new: super::Layout,
+        ) -> *mut () {
+            assert!(new.size <= old.size);
+            SHRINKS.fetch_add(1, Ordering::Relaxed);
+            unsafe { self.realloc(ptr, old, new.size) }
+        }
+    }
+    let mut vec: Vec<u32, Counting> = Vec::default();
+    vec.extend(0..10);
+    vec.reserve(100);
+    assert_eq!(GROWS.load(Ordering::Relaxed), 1);
+    vec.shrink_to_fit();
+    assert_eq!(SHRINKS.load(Ordering::Relaxed), 1);
+    assert!(vec.iter().copied().eq(0..10));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn retain() {
+    let mut vec: Vec<u16> = (0..100).collect();
+    vec.retain(|i| i % 3 == 0);
+    assert_eq!(
+        vec.as_slice(),
+        (0..100)
+            .filter(|i| i % 3 == 0)
+            .collect::<std::vec::Vec<u16>>()
+    );
+    vec.retain(|_| false);
+    assert!(vec.is_empty());
+
+    let drops = core::sync::atomic::AtomicUsize::new(0);
+    struct Counted<'a>(u8, &'a core::sync::atomic::AtomicUsize);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.1.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
+    let mut vec: Vec<Counted> = (0..10).map(|i| Counted(i, &drops)).collect();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        vec.retain(|item| {
+            assert_ne!(item.0, 6);
+            item.0 % 2 == 0
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.load(core::sync::atomic::Ordering::Relaxed), 3);
+    assert_eq!(
+        vec.iter().map(|item| item.0).collect::<std::vec::Vec<_>>(),
+        [0, 2, 4, 6, 7, 8, 9]
+    );
+    drop(vec);
+    assert_eq!(drops.load(core::sync::atomic::Ordering::Relaxed), 10);
+}
+
 pub use super::single_or_vec::SingleOrVec;
 
+#[cfg(feature = "std")]
+mod std_impl {
+    use crate::alloc::{vec::Vec, IAlloc};
+    impl<T, Alloc: IAlloc> Vec<T, Alloc> {
+        /// Whether or not [`Self::from_std`] and [`Self::into_std`] can reuse the allocation they are passed.
+        ///
+        /// This would require stabby's allocations to be laid out exactly like `std`'s, even when `Alloc` is the
+        /// global allocator. However, stabby stores an [`AllocPrefix`](crate::alloc::AllocPrefix) holding the
+        /// reference counts and capacity in front of its data, which `std` leaves no room for, and wouldn't know to
+        /// free. This is therefore `false` for all allocators: converting always moves the elements to a new
+        /// allocation, although it doesn't clone them.
+        ///
+        /// ```
+        /// # use stabby_abi::alloc::vec::Vec;
+        /// assert!(!Vec::<u8>::SHARES_STD_ALLOCATIONS);
+        /// ```
+        pub const SHARES_STD_ALLOCATIONS: bool = false;
+        /// Moves the elements of `vec` into a new stabby [`Vec`].
+        ///
+        /// See [`Self::SHARES_STD_ALLOCATIONS`] for why this can't reuse `vec`'s allocation.
+        pub fn from_std(vec: std::vec::Vec<T>) -> Self
+        where
+            Alloc: Default,
+        {
+            let mut this = Self::with_capacity(vec.len());
+            this.extend(vec);
+            this
+        }
+        /// Moves the elements of `self` into a new [`std::vec::Vec`].
+        ///
+        /// See [`Self::SHARES_STD_ALLOCATIONS`] for why this can't reuse `self`'s allocation.
+        pub fn into_std(self) -> std::vec::Vec<T> {
+            let mut vec = std::vec::Vec::with_capacity(self.len());
+            vec.extend(self);
+            vec
+        }
+    }
+    /// Equivalent to [`Vec::from_std`].
+    impl<T, Alloc: IAlloc + Default> From<std::vec::Vec<T>> for Vec<T, Alloc> {
+        fn from(value: std::vec::Vec<T>) -> Self {
+            Self::from_std(value)
+        }
+    }
+    /// Equivalent to [`Vec::into_std`].
+    impl<T, Alloc: IAlloc> From<Vec<T, Alloc>> for std::vec::Vec<T> {
+        fn from(value: Vec<T, Alloc>) -> Self {
+            value.into_std()
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) use serde_impl::VecVisitor;
 #[cfg(feature = "serde")]
 mod serde_impl {
     use super::*;
@@ -857,7 +1586,7 @@ mod serde_impl {
             deserializer.deserialize_seq(VecVisitor(core::marker::PhantomData))
         }
     }
-    pub struct VecVisitor<T, Alloc>(core::marker::PhantomData<(T, Alloc)>);
+    pub struct VecVisitor<T, Alloc>(pub(crate) core::marker::PhantomData<(T, Alloc)>);
     impl<'a, T: Deserialize<'a>, Alloc: IAlloc + Default> Visitor<'a> for VecVisitor<T, Alloc> {
         type Value = Vec<T, Alloc>;
         fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -867,7 +1596,10 @@ mod serde_impl {
         where
             A: serde::de::SeqAccess<'a>,
         {
-            let mut this = Vec::with_capacity_in(seq.size_hint().unwrap_or(0), Alloc::default());
+            // The hint comes from the input, so it's capped to avoid preallocating unreasonable amounts of memory.
+            let max_hint = (1 << 20) / core::mem::size_of::<T>().max(1);
+            let hint = seq.size_hint().unwrap_or(0).min(max_hint);
+            let mut this = Vec::with_capacity_in(hint, Alloc::default());
             while let Some(v) = seq.next_element()? {
                 this.push(v);
             }
@@ -875,3 +1607,259 @@ mod serde_impl {
         }
     }
 }
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl {
+    use super::*;
+    use crate::alloc::IAlloc;
+    use rkyv::{
+        rancor::Fallible,
+        ser::{Allocator, Writer},
+        vec::{ArchivedVec, VecResolver},
+        Archive, Deserialize, Place, Serialize,
+    };
+    impl<T: Archive, Alloc: IAlloc> Archive for Vec<T, Alloc> {
+        type Archived = ArchivedVec<T::Archived>;
+        type Resolver = VecResolver;
+        fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+            ArchivedVec::resolve_from_slice(self.as_slice(), resolver, out);
+        }
+    }
+    impl<T: Serialize<S>, Alloc: IAlloc, S: Fallible + Allocator + Writer + ?Sized> Serialize<S>
+        for Vec<T, Alloc>
+    {
+        fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+            ArchivedVec::<T::Archived>::serialize_from_slice(self.as_slice(), serializer)
+        }
+    }
+    impl<T: Archive, Alloc: IAlloc + Default, D: Fallible + ?Sized> Deserialize<Vec<T, Alloc>, D>
+        for ArchivedVec<T::Archived>
+    where
+        T::Archived: Deserialize<T, D>,
+    {
+        fn deserialize(&self, deserializer: &mut D) -> Result<Vec<T, Alloc>, D::Error> {
+            let mut this = Vec::with_capacity_in(self.len(), Alloc::default());
+            for value in self.iter() {
+                this.push(value.deserialize(deserializer)?);
+            }
+            Ok(this)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn from_iter_extend() {
+    /// An iterator whose `size_hint` underestimates how many items it yields.
+    struct Liar(core::ops::Range<u32>);
+    impl Iterator for Liar {
+        type Item = u32;
+        fn next(&mut self) -> Option<u32> {
+            self.0.next()
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (1, Some(1))
+        }
+    }
+    let vec: Vec<u32> = (0..100).collect();
+    assert!(vec.iter().copied().eq(0..100));
+    let mut vec: Vec<u32> = Liar(0..100).collect();
+    assert!(vec.iter().copied().eq(0..100));
+    vec.extend(Liar(100..1000));
+    vec.extend((1000..2000).filter(|i| i % 2 == 0));
+    assert!(vec
+        .iter()
+        .copied()
+        .eq((0..1000).chain((1000..2000).filter(|i| i % 2 == 0))));
+    let mut strings: Vec<std::string::String> = Vec::new();
+    strings.extend(["a", "b"].map(std::string::String::from));
+    assert_eq!(strings.as_slice(), ["a", "b"]);
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn sort_and_search() {
+    let mut vec: Vec<u32> = [5, 3, 9, 1, 3].into_iter().collect();
+    vec.sort_unstable();
+    assert_eq!(vec.as_slice(), [1, 3, 3, 5, 9]);
+    assert_eq!(vec.binary_search(&5), Ok(3));
+    assert_eq!(vec.binary_search(&4), Err(3));
+    assert_eq!(vec.binary_search(&10), Err(5));
+    vec.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(vec.as_slice(), [9, 5, 3, 3, 1]);
+    assert_eq!(vec.binary_search_by(|x| 9.cmp(x)), Ok(0));
+    assert_eq!(vec.binary_search_by(|x| 0.cmp(x)), Err(5));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn into_boxed_slice() {
+    let mut vec: Vec<std::string::String> = Vec::with_capacity(100);
+    vec.extend((0..10).map(|i| i.to_string()));
+    let mut boxed = vec.into_boxed_slice();
+    assert_eq!(boxed.len(), 10);
+    boxed[3].push('!');
+    assert_eq!(boxed.join(","), "0,1,2,3!,4,5,6,7,8,9");
+    let vec = Vec::from(boxed);
+    assert_eq!(vec.capacity(), 10);
+    let boxed = Vec::<u8>::with_capacity(8).into_boxed_slice();
+    assert!(boxed.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn std_conversions() {
+    let strings: std::vec::Vec<std::string::String> = (0..10).map(|i| i.to_string()).collect();
+    let ptr = strings[3].as_ptr();
+    let vec: Vec<std::string::String> = strings.into();
+    assert!(vec.iter().map(|s| s.parse::<i32>().unwrap()).eq(0..10));
+    // The elements themselves are moved rather than cloned.
+    assert_eq!(vec[3].as_ptr(), ptr);
+    let strings: std::vec::Vec<std::string::String> = vec.into();
+    assert_eq!(strings[3].as_ptr(), ptr);
+    let vec = Vec::<std::string::String>::from_std(strings);
+    assert_eq!(vec[3].as_ptr(), ptr);
+    let strings = vec.into_std();
+    assert_eq!(strings[3].as_ptr(), ptr);
+    assert!(strings.iter().map(|s| s.parse::<i32>().unwrap()).eq(0..10));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn swap_remove() {
+    let mut vec: Vec<std::string::String> = (0..5).map(|i| i.to_string()).collect();
+    assert_eq!(vec.swap_remove(1).as_deref(), Some("1"));
+    assert_eq!(vec.as_slice(), ["0", "4", "2", "3"]);
+    assert_eq!(vec.swap_remove(3).as_deref(), Some("3"));
+    assert_eq!(vec.as_slice(), ["0", "4", "2"]);
+    assert_eq!(vec.swap_remove(3), None);
+    assert_eq!(vec.as_slice(), ["0", "4", "2"]);
+    while vec.swap_remove(0).is_some() {}
+    assert!(vec.is_empty());
+    assert_eq!(vec.swap_remove(0), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn append() {
+    let mut vec: Vec<std::string::String> = (0..3).map(|i| i.to_string()).collect();
+    let mut other: Vec<std::string::String> = (3..10).map(|i| i.to_string()).collect();
+    let capacity = other.capacity();
+    vec.append(&mut other);
+    assert!(vec.iter().map(|s| s.parse::<i32>().unwrap()).eq(0..10));
+    assert!(other.is_empty());
+    assert_eq!(other.capacity(), capacity);
+    vec.append(&mut other);
+    assert_eq!(vec.len(), 10);
+    other.push("10".into());
+    vec.append(&mut other);
+    assert!(vec.iter().map(|s| s.parse::<i32>().unwrap()).eq(0..11));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn spare_capacity_mut() {
+    let mut vec: Vec<std::string::String> = Vec::new();
+    assert!(vec.spare_capacity_mut().is_empty());
+    vec.reserve(16);
+    vec.push("0".into());
+    assert_eq!(vec.spare_capacity_mut().len(), vec.capacity() - 1);
+    let written = vec
+        .spare_capacity_mut()
+        .iter_mut()
+        .zip(1..5)
+        .map(|(slot, i)| slot.write(i.to_string()))
+        .count();
+    unsafe { vec.set_len(vec.len() + written) };
+    assert!(vec.iter().map(|s| s.parse::<i32>().unwrap()).eq(0..5));
+    assert_eq!(vec.spare_capacity_mut().len(), vec.remaining_capacity());
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn const_empty() {
+    use super::{allocators::RustAlloc, string::String};
+    static EMPTY: Vec<u32, RustAlloc> = Vec::new_in(RustAlloc::new());
+    static EMPTY_STR: String<RustAlloc> = String::new_in(RustAlloc::new());
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.capacity(), 0);
+    assert_eq!(EMPTY_STR.as_str(), "");
+    // Clones of the statics are ordinary containers.
+    let mut vec = EMPTY.clone();
+    vec.push(1);
+    let mut string = EMPTY_STR.clone();
+    string.push_str("hi");
+    assert_eq!((vec.as_slice(), string.as_str()), (&[1][..], "hi"));
+    assert!(EMPTY.is_empty());
+}
+
+#[cfg(feature = "alloc-rs")]
+#[test]
+fn slice_comparisons() {
+    use super::sync::ArcSlice;
+    let vec: Vec<u8> = [1, 2, 3].as_slice().into();
+    let slice: &[u8] = &[1, 2, 3];
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(vec, slice);
+    assert_eq!(vec, *slice);
+    assert_eq!([1, 2, 3], vec);
+    assert_eq!(slice, vec);
+    assert_eq!(*slice, vec);
+    assert_ne!(vec, [1, 2]);
+    assert_ne!([1, 2, 4], vec);
+    assert_ne!([0u8; 0], vec);
+    let arc: ArcSlice<u8> = vec.clone().into();
+    assert_eq!(arc, [1, 2, 3]);
+    assert_eq!(arc, slice);
+    assert_eq!(arc, *slice);
+    assert_eq!([1, 2, 3], arc);
+    assert_eq!(slice, arc);
+    assert_eq!(*slice, arc);
+    assert_ne!(arc, [1, 2]);
+    assert_ne!([1, 2, 4], arc);
+    assert_eq!(arc, vec);
+    assert_eq!(vec, arc);
+    assert_eq!(arc, arc.clone());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn clone() {
+    use super::allocators::{RustAlloc, TrackingAlloc};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+    // Panics when cloning `Fragile(true)`.
+    struct Fragile(bool);
+    impl Fragile {
+        fn new(fail: bool) -> Self {
+            LIVE.fetch_add(1, Ordering::Relaxed);
+            Self(fail)
+        }
+    }
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            assert!(!self.0, "fragile clone");
+            Self::new(false)
+        }
+    }
+    impl Drop for Fragile {
+        fn drop(&mut self) {
+            LIVE.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+    let alloc = TrackingAlloc::<RustAlloc>::default();
+    let mut vec = Vec::with_capacity_in(100, alloc.clone());
+    vec.extend((0..10).map(|_| Fragile::new(false)));
+    let clone = vec.clone();
+    assert_eq!((clone.len(), clone.capacity()), (10, 10));
+    assert_eq!(alloc.live_allocations(), 2);
+    drop(clone);
+    vec.push(Fragile::new(true));
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.clone())).is_err());
+    // The clones made before the panic were dropped, and their allocation freed.
+    assert_eq!(LIVE.load(Ordering::Relaxed), 11);
+    assert_eq!(alloc.live_allocations(), 1);
+    drop(vec);
+    assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+    assert_eq!(alloc.live_allocations(), 0);
+}
@@ -185,6 +185,14 @@ impl<'a, Vt: Copy + 'a> DynRef<'a, Vt> {
     pub const fn vtable(&self) -> &Vt {
         self.vtable
     }
+    /// Returns the layout of the concrete type behind the reference, which [`HasLayout`](crate::vtable::HasLayout)
+    /// stores in the vtable.
+    pub fn value_layout(&self) -> crate::alloc::Layout
+    where
+        Vt: HasLayoutVt,
+    {
+        self.vtable.layout()
+    }
 
     /// Allows casting a `dyn A + B` into `dyn A`.
     ///
@@ -260,6 +268,9 @@ impl_super!(VTable<Head, Tail>, Tail, Head, Tail: HasDropVt + 'static);
 impl_super!(VtSend<Vt>, Vt, Vt: HasDropVt + 'static);
 impl_super!(VtSync<Vt>, Vt, Vt: HasDropVt + 'static);
 impl_super!(VtSync<VtSend<Vt>>, Vt, Vt: HasDropVt + 'static);
+impl_super!(VtLayout<Vt>, Vt, Vt: HasDropVt + 'static);
+#[cfg(not(stabby_default_alloc = "disabled"))]
+impl_super!(VtClone<Vt>, Vt, Vt: HasDropVt + 'static);
 impl_super!(VtSend<VtSync<Vt>>, Vt, Vt: HasDropVt + 'static);
 impl_super!(VtSync<VtSend<Vt>>, VtSync<Vt>, Vt: HasDropVt + 'static);
 impl_super!(VtSend<VtSync<Vt>>, VtSend<Vt>, Vt: HasDropVt + 'static);
@@ -281,6 +292,21 @@ impl<'a, P: IPtrOwned + IPtrClone, Vt: HasDropVt + 'a> Clone for Dyn<'a, P, Vt>
         }
     }
 }
+#[cfg(not(stabby_default_alloc = "disabled"))]
+impl<'a, Vt: HasDropVt + HasCloneVt + 'a> Clone for Dyn<'a, crate::alloc::boxed::Box<()>, Vt> {
+    /// Clones the value into a new [`Box`](crate::alloc::boxed::Box), using the function stored in the vtable by
+    /// [`DynClone`].
+    fn clone(&self) -> Self {
+        Self {
+            // SAFETY: the vtable was built for the value `self` points to.
+            ptr: core::mem::ManuallyDrop::new(unsafe {
+                (self.vtable.clone_fn())(crate::IPtr::as_ref(&*self.ptr))
+            }),
+            vtable: self.vtable,
+            unsend: self.unsend,
+        }
+    }
+}
 impl<'a, P: IPtrOwned + IPtr, Vt: HasDropVt + 'a> Dyn<'a, P, Vt> {
     /// Access the data pointer immutably.
     #[allow(clippy::missing_const_for_fn)]
@@ -296,6 +322,14 @@ impl<'a, P: IPtrOwned + IPtr, Vt: HasDropVt + 'a> Dyn<'a, P, Vt> {
     pub const fn vtable(&self) -> &'a Vt {
         self.vtable
     }
+    /// Returns the layout of the concrete type behind the pointer, which [`HasLayout`](crate::vtable::HasLayout)
+    /// stores in the vtable.
+    pub fn value_layout(&self) -> crate::alloc::Layout
+    where
+        Vt: HasLayoutVt,
+    {
+        self.vtable.layout()
+    }
     /// Borrow into an ABI-stable `&dyn Traits`
     pub fn as_ref(&self) -> DynRef<'_, Vt> {
         DynRef {
@@ -365,6 +399,12 @@ impl<'a, P: IPtrOwned + IPtr, Vt: HasDropVt + 'a> Dyn<'a, P, Vt> {
         (self.vtable == Vt::vtable()).then(|| unsafe { self.ptr.as_ref().cast::<T>().as_ref() })
     }
     /// Downcasts the reference based on its reflection report.
+    ///
+    /// This requires the trait object to include [`stabby::Any`](crate::vtable::Any), whose vtable reports the
+    /// concrete type's [`IStable::ID`](crate::IStable::ID) and [`IStable::REPORT`](crate::IStable::REPORT).
+    /// Unlike [`Self::downcast_ref`], this works across FFI boundaries.
+    ///
+    /// The pointer is only borrowed: for reference counted pointers, the counts are left untouched.
     pub fn stable_downcast_ref<T: crate::IStable, Path>(&self) -> Option<&T>
     where
         Vt: TransitiveDeref<crate::vtable::StabbyVtableAny<'a>, Path> + IConstConstructor<'a, T>,
@@ -445,12 +485,12 @@ impl<'a, T, Vt: Copy + IConstConstructor<'a, T>> From<&'a T> for DynRef<'a, Vt>
     }
 }
 
-// SAFETY: This is analogous to a reference, and Vt proves the rest
-unsafe impl<Vt: HasSendVt> Send for DynRef<'_, Vt> {}
+// SAFETY: This is analogous to a reference, which is `Send` if the pointee is `Sync`, which Vt proves.
+unsafe impl<Vt: HasSyncVt> Send for DynRef<'_, Vt> {}
 // SAFETY: This is analogous to a reference, and Vt proves the rest
 unsafe impl<Vt: HasSyncVt> Sync for DynRef<'_, Vt> {}
 
-// SAFETY: The pointer must be `Send` and the pointee must me `Send` and `Sync`.
+// SAFETY: The pointer must be `Send` and the pointee must be `Send`, which Vt proves.
 unsafe impl<P: IPtrOwned + Send, Vt: HasSendVt + HasDropVt> Send for Dyn<'_, P, Vt> {}
-// SAFETY: The pointer must be `Sync` and the pointee must me `Send` and `Sync`.
+// SAFETY: The pointer must be `Sync` and the pointee must be `Sync`, which Vt proves.
 unsafe impl<P: IPtrOwned + Sync, Vt: HasSyncVt + HasDropVt> Sync for Dyn<'_, P, Vt> {}
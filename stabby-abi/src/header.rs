@@ -0,0 +1,310 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   Pierre Avital, <pierre.avital@me.com>
+//
+
+use alloc_rs::{format, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{
+    report::{TyTy, TypeReport},
+    IStable,
+};
+
+/// Emits a C declaration of `T`'s layout, in the form of `typedef struct { ... } Name;`, where `Name` is `T`'s name.
+///
+/// Nested structures are declared inline as anonymous structures, and padding is made explicit through
+/// `uint8_t _padN[size]` fields. The declaration relies on `<stdint.h>` and `<stdbool.h>`.
+///
+/// Pointers are declared as pointers to the corresponding C scalar when pointing to one, and as `void*` otherwise.
+/// Function pointers are declared as `void (*)(void)`, and should be cast to their actual signature before being called.
+///
+/// `Dyn` vtables aren't emitted yet: a trait object's reference to its vtable is declared like any other reference
+/// to a structure, as `const void*`, without describing the vtable's layout.
+/// ```
+/// # use stabby_abi::header::emit_c_decl;
+/// assert_eq!(
+///     emit_c_decl::<stabby_abi::Tuple<u8, u32>>(),
+///     "typedef struct {\n    uint8_t _0;\n    uint8_t _pad0[3];\n    uint32_t _1;\n} Tuple2;\n"
+/// );
+/// ```
+/// # Panics
+/// If `T` contains a type whose layout can't be expressed in C from its report: enums, 128-bit integers,
+/// or zero-sized types that are aligned to more than a byte. Since `T`'s size and alignment are known,
+/// this also panics if the emitted declaration wouldn't match them.
+pub fn emit_c_decl<T: IStable>() -> String {
+    let report = T::REPORT;
+    let mut pad = 0;
+    let (decl, layout) = c_type(report, 0, &mut pad);
+    assert_eq!(
+        (layout.size, layout.align),
+        (T::size(), T::align()),
+        "stabby couldn't reproduce {}'s layout in C",
+        report.name
+    );
+    format!("typedef {}{};\n", decl.prefix, report.name)
+}
+
+/// The size and alignment of a type.
+#[derive(Clone, Copy)]
+struct Layout {
+    size: usize,
+    align: usize,
+}
+impl Layout {
+    const fn of<T>() -> Self {
+        Self {
+            size: core::mem::size_of::<T>(),
+            align: core::mem::align_of::<T>(),
+        }
+    }
+}
+
+/// A C declarator: a field named `name` of this type is declared as `{prefix}{name}{suffix};`
+struct Decl {
+    prefix: String,
+    suffix: String,
+}
+impl Decl {
+    fn scalar(name: &str) -> Self {
+        Self {
+            prefix: format!("{name} "),
+            suffix: String::new(),
+        }
+    }
+}
+
+/// Returns the C scalar corresponding to a primitive reported by `stabby_abi::stable_impls`.
+fn c_scalar(report: &TypeReport) -> Option<(&'static str, Layout)> {
+    if report.module.as_str() != "stabby_abi::stable_impls" {
+        return None;
+    }
+    Some(match report.name.as_str() {
+        "bool" | "core::sync::atomic::AtomicBool" => ("bool", Layout::of::<bool>()),
+        "u8" | "core::num::NonZeroU8" | "core::sync::atomic::AtomicU8" => {
+            ("uint8_t", Layout::of::<u8>())
+        }
+        "i8" | "core::num::NonZeroI8" | "core::sync::atomic::AtomicI8" | "core::cmp::Ordering" => {
+            ("int8_t", Layout::of::<i8>())
+        }
+        "u16" | "core::num::NonZeroU16" | "core::sync::atomic::AtomicU16" => {
+            ("uint16_t", Layout::of::<u16>())
+        }
+        "i16" | "core::num::NonZeroI16" | "core::sync::atomic::AtomicI16" => {
+            ("int16_t", Layout::of::<i16>())
+        }
+        "u32" | "core::num::NonZeroU32" | "core::sync::atomic::AtomicU32" => {
+            ("uint32_t", Layout::of::<u32>())
+        }
+        "i32" | "core::num::NonZeroI32" | "core::sync::atomic::AtomicI32" => {
+            ("int32_t", Layout::of::<i32>())
+        }
+        "u64" | "core::num::NonZeroU64" | "core::sync::atomic::AtomicU64" => {
+            ("uint64_t", Layout::of::<u64>())
+        }
+        "i64" | "core::num::NonZeroI64" | "core::sync::atomic::AtomicI64" => {
+            ("int64_t", Layout::of::<i64>())
+        }
+        "usize" | "core::num::NonZeroUsize" | "core::sync::atomic::AtomicUsize" => {
+            ("uintptr_t", Layout::of::<usize>())
+        }
+        "isize" | "core::num::NonZeroIsize" | "core::sync::atomic::AtomicIsize" => {
+            ("intptr_t", Layout::of::<isize>())
+        }
+        "f32" => ("float", Layout::of::<f32>()),
+        "f64" => ("double", Layout::of::<f64>()),
+        "std::os::fd::OwnedFd" | "std::os::fd::BorrowedFd" => ("int", Layout::of::<i32>()),
+        _ => return None,
+    })
+}
+
+/// Returns the C declarator for `report`, indenting nested fields by `depth + 1` levels.
+fn c_type(report: &'static TypeReport, depth: usize, pad: &mut usize) -> (Decl, Layout) {
+    if let Some((scalar, layout)) = c_scalar(report) {
+        return (Decl::scalar(scalar), layout);
+    }
+    let name = report.name.as_str();
+    if report.module.as_str() == "stabby_abi::stable_impls" {
+        match name {
+            "()" | "core::marker::PhantomData" | "core::marker::PhantomPinned" => {
+                return (Decl::scalar("void"), Layout { size: 0, align: 1 })
+            }
+            "&"
+            | "&mut"
+            | "*const"
+            | "*mut"
+            | "core::ptr::NonNull"
+            | "core::sync::atomic::AtomicPtr" => {
+                let pointee = report
+                    .fields()
+                    .next()
+                    .and_then(|field| c_scalar(field.ty))
+                    .map_or("void", |(scalar, _)| scalar);
+                let constness = if matches!(name, "&" | "*const") {
+                    "const "
+                } else {
+                    ""
+                };
+                return (
+                    Decl::scalar(&format!("{constness}{pointee}*")),
+                    Layout::of::<*const ()>(),
+                );
+            }
+            _ if name.starts_with("extern \"") || name.starts_with("unsafe extern \"") => {
+                return (
+                    Decl {
+                        prefix: "void (*".into(),
+                        suffix: ")(void)".into(),
+                    },
+                    Layout::of::<fn()>(),
+                );
+            }
+            _ => {}
+        }
+        if let Some(len) = name
+            .strip_prefix("[T;")
+            .and_then(|len| len.strip_suffix(']'))
+            .and_then(|len| len.parse::<usize>().ok())
+        {
+            let element = report
+                .fields()
+                .next()
+                .expect("arrays report their element type");
+            let (decl, layout) = c_type(element.ty, depth, pad);
+            return (
+                Decl {
+                    prefix: decl.prefix,
+                    suffix: format!("[{len}]{}", decl.suffix),
+                },
+                Layout {
+                    size: layout.size * len,
+                    align: layout.align,
+                },
+            );
+        }
+        assert!(
+            report.fields().next().is_some(),
+            "{name} has no C equivalent"
+        );
+    }
+    let keyword = match report.tyty {
+        TyTy::Struct => "struct",
+        TyTy::Union => "union",
+        TyTy::Enum(_) => {
+            panic!("emitting C declarations for enums such as {name} isn't supported yet")
+        }
+    };
+    // Fields are reported in reverse order.
+    let mut fields = report.fields().collect::<Vec<_>>();
+    fields.reverse();
+    let indent = "    ".repeat(depth + 1);
+    let mut body = String::new();
+    let mut layout = Layout { size: 0, align: 1 };
+    for field in fields {
+        let (decl, field_layout) = c_type(field.ty, depth + 1, pad);
+        layout.align = layout.align.max(field_layout.align);
+        if field_layout.size == 0 {
+            assert!(
+                field_layout.align == 1,
+                "{name}.{} is zero-sized but aligned to {} bytes, which C can't express",
+                field.name,
+                field_layout.align
+            );
+            continue;
+        }
+        let offset = match report.tyty {
+            TyTy::Union => 0,
+            _ => padded(&mut body, &indent, pad, layout.size, field_layout.align),
+        };
+        let field_name = field.name.as_str();
+        let underscore = if field_name.starts_with(|c: char| c.is_ascii_digit()) {
+            "_"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            body,
+            "{indent}{}{underscore}{field_name}{};",
+            decl.prefix, decl.suffix
+        );
+        layout.size = layout.size.max(offset + field_layout.size);
+    }
+    layout.size = padded(&mut body, &indent, pad, layout.size, layout.align);
+    let outdent = &indent[4..];
+    (
+        Decl::scalar(&format!("{keyword} {{\n{body}{outdent}}}")),
+        layout,
+    )
+}
+
+/// Writes a padding field to `body` if needed to align `offset` to `align`, returning the aligned offset.
+fn padded(body: &mut String, indent: &str, pad: &mut usize, offset: usize, align: usize) -> usize {
+    let padding = (align - offset % align) % align;
+    if padding != 0 {
+        let _ = writeln!(body, "{indent}uint8_t _pad{pad}[{padding}];");
+        *pad += 1;
+    }
+    offset + padding
+}
+
+#[cfg(all(feature = "std", target_pointer_width = "64"))]
+#[test]
+fn c_decl() {
+    use crate::alloc::{boxed::Box, sync::Arc, vec::Vec};
+    #[crate::stabby(no_opt)]
+    pub struct Config {
+        flag: bool,
+        ratio: f64,
+        name: &'static u8,
+        values: Vec<u16>,
+        shared: Arc<u64>,
+        boxed: Box<[u32; 3]>,
+        pair: crate::Tuple<u8, u16>,
+        callback: extern "C" fn(u8) -> u32,
+    }
+    assert_eq!(
+        emit_c_decl::<Config>(),
+        "typedef struct {
+    bool flag;
+    uint8_t _pad0[7];
+    double ratio;
+    const uint8_t* name;
+    struct {
+        struct {
+            struct {
+                uint16_t* ptr;
+            } start;
+            uint16_t* end;
+            uint16_t* capacity;
+        } inner;
+    } values;
+    struct {
+        struct {
+            uint64_t* ptr;
+        } ptr;
+    } shared;
+    struct {
+        struct {
+            void* ptr;
+        } ptr;
+    } boxed;
+    struct {
+        uint8_t _0;
+        uint8_t _pad1[1];
+        uint16_t _1;
+    } pair;
+    uint8_t _pad2[4];
+    void (*callback)(void);
+} Config;
+"
+    );
+}
@@ -395,6 +395,9 @@ pub use result::Result;
 /// Like [`core::option::Option`], but ABI-stable with niche optimizations!
 pub mod option;
 pub use option::Option;
+/// Emitting C declarations for stabby types, based on their reports.
+#[cfg(feature = "header")]
+pub mod header;
 /// A very simple ABI-stable reflection framework.
 pub mod report;
 /// ABI-stable slices.
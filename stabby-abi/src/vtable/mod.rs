@@ -333,11 +333,17 @@ impl<T: HasDropVt> HasDropVt for VtSync<T> {
 }
 
 /// Whether or not a vtable includes [`VtSend`]
+///
+/// Since [`VtSend`] can only be constructed for types that are `Send`, this proves that the erased value is `Send`,
+/// which is what allows [`Dyn`](crate::Dyn) to implement `Send`.
 pub trait HasSendVt {}
 impl<T> HasSendVt for VtSend<T> {}
 impl<T: HasSendVt> HasSendVt for VtSync<T> {}
-impl<Head, Tail: HasSyncVt> HasSendVt for VTable<Head, Tail> {}
+impl<Head, Tail: HasSendVt> HasSendVt for VTable<Head, Tail> {}
 /// Whether or not a vtable includes [`VtSync`]
+///
+/// Since [`VtSync`] can only be constructed for types that are `Sync`, this proves that the erased value is `Sync`,
+/// which is what allows [`Dyn`](crate::Dyn) and [`DynRef`](crate::DynRef) to implement `Sync`.
 pub trait HasSyncVt {}
 impl<T> HasSyncVt for VtSync<T> {}
 impl<T: HasSyncVt> HasSyncVt for VtSend<T> {}
@@ -392,6 +398,9 @@ impl<'a, T> IConstConstructor<'a, T> for VtDrop {
 }
 
 /// A marker for vtables for types that are `Send`
+///
+/// It is added to a vtable by adding `Send` to the trait object's bounds, as in `dynptr!(Box<dyn Trait + Send>)`,
+/// and occupies no space in it.
 #[stabby::stabby]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct VtSend<T>(pub T);
@@ -416,6 +425,9 @@ impl<'a, T: Send, Vt: IConstConstructor<'a, T>> IConstConstructor<'a, T> for VtS
 }
 
 /// A marker for vtables for types that are `Sync`
+///
+/// It is added to a vtable by adding `Sync` to the trait object's bounds, as in `dynptr!(Box<dyn Trait + Sync>)`,
+/// and occupies no space in it.
 #[stabby::stabby]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct VtSync<T>(pub T);
@@ -433,6 +445,211 @@ impl<Tail: TransitiveDeref<Vt, N>, Vt, N> TransitiveDeref<Vt, N> for VtSync<Tail
         self.0.tderef()
     }
 }
+
+// LAYOUT
+/// A marker trait that adds the [`Layout`](crate::alloc::Layout) of the concrete type to a trait object's vtable,
+/// which can then be read through [`Dyn::value_layout`](crate::Dyn::value_layout).
+///
+/// It is implemented for all types, and is meant to be listed among a trait object's traits:
+/// `stabby::dynptr!(Box<dyn MyTrait + stabby::HasLayout>)`.
+pub trait HasLayout {}
+impl<T> HasLayout for T {}
+/// A vtable that stores the [`Layout`](crate::alloc::Layout) of the concrete type alongside `T`.
+#[stabby::stabby]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct VtLayout<T> {
+    /// The rest of the vtable.
+    ///
+    /// It comes first to allow upcasting vtables.
+    pub tail: T,
+    /// The layout of the concrete type.
+    pub layout: crate::alloc::Layout,
+}
+impl<'a> CompoundVt<'a> for dyn HasLayout {
+    type Vt<T> = VtLayout<T>;
+}
+impl<'a, T, Vt: IConstConstructor<'a, T>> IConstConstructor<'a, T> for VtLayout<Vt> {
+    impl_vtable_constructor!(
+        const VTABLE_REF: &'a VtLayout<Vt> = &VtLayout {
+            tail: *Vt::VTABLE_REF,
+            layout: crate::alloc::Layout::of::<T>(),
+        }; =>
+        const VTABLE: VtLayout<Vt> = VtLayout {
+            tail: Vt::VTABLE,
+            layout: crate::alloc::Layout::of::<T>(),
+        };
+    );
+}
+impl<Tail: TransitiveDeref<Vt, N>, Vt, N> TransitiveDeref<Vt, N> for VtLayout<Tail> {
+    fn tderef(&self) -> &Vt {
+        self.tail.tderef()
+    }
+}
+impl<T: HasDropVt> HasDropVt for VtLayout<T> {
+    fn drop_vt(&self) -> &VtDrop {
+        self.tail.drop_vt()
+    }
+}
+impl<T: HasSendVt> HasSendVt for VtLayout<T> {}
+impl<T: HasSyncVt> HasSyncVt for VtLayout<T> {}
+
+/// Allows extracting the [`Layout`](crate::alloc::Layout) of the concrete type from a vtable that includes [`VtLayout`].
+pub trait HasLayoutVt {
+    /// Returns the layout stored in the [`VtLayout`] section of the vtable.
+    fn layout(&self) -> crate::alloc::Layout;
+}
+impl<T> HasLayoutVt for VtLayout<T> {
+    fn layout(&self) -> crate::alloc::Layout {
+        self.layout
+    }
+}
+impl<Head, Tail: HasLayoutVt> HasLayoutVt for VTable<Head, Tail> {
+    fn layout(&self) -> crate::alloc::Layout {
+        self.tail.layout()
+    }
+}
+impl<T: HasLayoutVt> HasLayoutVt for VtSend<T> {
+    fn layout(&self) -> crate::alloc::Layout {
+        self.0.layout()
+    }
+}
+impl<T: HasLayoutVt> HasLayoutVt for VtSync<T> {
+    fn layout(&self) -> crate::alloc::Layout {
+        self.0.layout()
+    }
+}
+/// The marker traits that add entries to a trait object's vtable, which `stabby` re-exports at its root.
+///
+/// [`DynClone`] is only available if a default allocator is.
+pub mod markers {
+    #[cfg(not(stabby_default_alloc = "disabled"))]
+    pub use super::DynClone;
+    pub use super::HasLayout;
+}
+
+// CLONE
+#[cfg(not(stabby_default_alloc = "disabled"))]
+pub use clone::*;
+#[cfg(not(stabby_default_alloc = "disabled"))]
+mod clone {
+    use super::*;
+    use crate::{alloc::boxed::Box, fatptr::AnonymRef, IntoDyn};
+
+    /// A marker trait that adds a cloning function to a trait object's vtable, making
+    /// `Dyn<Box<()>, Vt>` [`Clone`], much like the `dyn-clone` crate does for `Box<dyn Trait>`.
+    ///
+    /// It is implemented for all [`Clone`] types, and is meant to be listed among a trait object's traits:
+    /// `stabby::dynptr!(Box<dyn MyTrait + stabby::DynClone>)`.
+    pub trait DynClone {}
+    impl<T: Clone> DynClone for T {}
+    /// The signature of the cloning function stored in [`VtClone`].
+    pub type CloneFn = unsafe extern "C" fn(AnonymRef<'_>) -> Box<()>;
+    /// A vtable that stores a function to clone the concrete type into a new [`Box`] alongside `T`.
+    #[stabby::stabby]
+    #[derive(Clone, Copy, Eq)]
+    pub struct VtClone<T> {
+        /// The rest of the vtable.
+        ///
+        /// It comes first to allow upcasting vtables.
+        pub tail: T,
+        /// The [`Clone::clone`] function, shimmed with the C calling convention, which boxes the clone.
+        pub clone: crate::StableLike<CloneFn, core::num::NonZeroUsize>,
+    }
+    impl<T: core::fmt::Debug> core::fmt::Debug for VtClone<T> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(
+                f,
+                "VtClone({:p}, {:?})",
+                unsafe { self.clone.as_ref_unchecked() },
+                self.tail
+            )
+        }
+    }
+    impl<T: Hash> Hash for VtClone<T> {
+        fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+            self.tail.hash(state);
+            self.clone.hash(state)
+        }
+    }
+    impl<T: PartialEq> PartialEq for VtClone<T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.tail == other.tail
+                && core::ptr::eq(
+                    unsafe { self.clone.as_ref_unchecked() } as *const CloneFn,
+                    unsafe { other.clone.as_ref_unchecked() } as *const CloneFn,
+                )
+        }
+    }
+    unsafe extern "C" fn clone<T: Clone>(this: AnonymRef<'_>) -> Box<()> {
+        Box::new(unsafe { this.cast::<T>().as_ref() }.clone()).anonimize()
+    }
+    impl<'a> CompoundVt<'a> for dyn DynClone {
+        type Vt<T> = VtClone<T>;
+    }
+    #[allow(unknown_lints)]
+    #[allow(clippy::missing_transmute_annotations)]
+    impl<'a, T: Clone, Vt: IConstConstructor<'a, T>> IConstConstructor<'a, T> for VtClone<Vt> {
+        impl_vtable_constructor!(
+            const VTABLE_REF: &'a VtClone<Vt> = &VtClone {
+                tail: *Vt::VTABLE_REF,
+                clone: unsafe { core::mem::transmute(clone::<T> as CloneFn) },
+            }; =>
+            const VTABLE: VtClone<Vt> = VtClone {
+                tail: Vt::VTABLE,
+                clone: unsafe { core::mem::transmute(clone::<T> as CloneFn) },
+            };
+        );
+    }
+    impl<Tail: TransitiveDeref<Vt, N>, Vt, N> TransitiveDeref<Vt, N> for VtClone<Tail> {
+        fn tderef(&self) -> &Vt {
+            self.tail.tderef()
+        }
+    }
+    impl<T: HasDropVt> HasDropVt for VtClone<T> {
+        fn drop_vt(&self) -> &VtDrop {
+            self.tail.drop_vt()
+        }
+    }
+    impl<T: HasSendVt> HasSendVt for VtClone<T> {}
+    impl<T: HasSyncVt> HasSyncVt for VtClone<T> {}
+    impl<T: HasLayoutVt> HasLayoutVt for VtClone<T> {
+        fn layout(&self) -> crate::alloc::Layout {
+            self.tail.layout()
+        }
+    }
+
+    /// Allows extracting the cloning function from a vtable that includes [`VtClone`].
+    pub trait HasCloneVt {
+        /// Returns the function stored in the [`VtClone`] section of the vtable.
+        fn clone_fn(&self) -> CloneFn;
+    }
+    impl<T> HasCloneVt for VtClone<T> {
+        fn clone_fn(&self) -> CloneFn {
+            *unsafe { self.clone.as_ref_unchecked() }
+        }
+    }
+    impl<Head, Tail: HasCloneVt> HasCloneVt for VTable<Head, Tail> {
+        fn clone_fn(&self) -> CloneFn {
+            self.tail.clone_fn()
+        }
+    }
+    impl<T: HasCloneVt> HasCloneVt for VtSend<T> {
+        fn clone_fn(&self) -> CloneFn {
+            self.0.clone_fn()
+        }
+    }
+    impl<T: HasCloneVt> HasCloneVt for VtSync<T> {
+        fn clone_fn(&self) -> CloneFn {
+            self.0.clone_fn()
+        }
+    }
+    impl<T: HasCloneVt> HasCloneVt for VtLayout<T> {
+        fn clone_fn(&self) -> CloneFn {
+            self.tail.clone_fn()
+        }
+    }
+}
+
 impl<Head, Tail> From<crate::vtable::VtSync<VtSend<VTable<Head, Tail>>>> for VTable<Head, Tail> {
     fn from(value: VtSync<VtSend<VTable<Head, Tail>>>) -> Self {
         value.0 .0
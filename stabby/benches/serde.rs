@@ -0,0 +1,67 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use serde::{
+    de::value::{Error, SeqDeserializer},
+    Deserialize,
+};
+use stabby::{sync::ArcSlice, vec::Vec};
+
+/// Tracks the peak amount of memory allocated through it.
+struct PeakAlloc {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = self.current.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        self.peak.fetch_max(current, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.current.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+/// Returns how much more memory than was already allocated `f` needed at its peak.
+fn peak_allocation<T>(f: impl FnOnce() -> T) -> usize {
+    let baseline = ALLOC.current.load(Ordering::Relaxed);
+    ALLOC.peak.store(baseline, Ordering::Relaxed);
+    drop(black_box(f()));
+    ALLOC.peak.load(Ordering::Relaxed) - baseline
+}
+
+const LEN: u32 = 1_000_000;
+/// A sequence whose length is hinted, as in length-prefixed formats.
+fn seq() -> SeqDeserializer<core::ops::Range<u32>, Error> {
+    SeqDeserializer::new(0..LEN)
+}
+/// How `ArcSlice` used to be deserialized: the hint is capped by `Vec`'s visitor, so its buffer has to grow.
+fn through_vec() -> ArcSlice<u32> {
+    Vec::deserialize(seq()).unwrap().into()
+}
+fn in_place() -> ArcSlice<u32> {
+    ArcSlice::deserialize(seq()).unwrap()
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    println!(
+        "peak allocation for {LEN} u32s: through_vec={}B in_place={}B",
+        peak_allocation(through_vec),
+        peak_allocation(in_place)
+    );
+    c.bench_function("arc_slice_deserialize_through_vec", |b| b.iter(through_vec));
+    c.bench_function("arc_slice_deserialize_in_place", |b| b.iter(in_place));
+}
+
+criterion_group!(benches, bench_deserialize);
+criterion_main!(benches);
@@ -59,7 +59,10 @@ pub mod future {
 pub use crate::abi::closure;
 pub use crate::abi::{option, result, slice, str};
 
-pub use crate::abi::{vtable::Any, AccessAs, IStable, IntoSuperTrait};
+pub use crate::abi::{
+    vtable::{markers::*, Any},
+    AccessAs, IStable, IntoSuperTrait,
+};
 
 /// Integration with [`libloading`](::libloading), allowing symbol loads to be validated thanks to either reflection or canaries.
 ///
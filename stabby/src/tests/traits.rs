@@ -177,6 +177,105 @@ fn arc_traits() {
     let dyned: stabby::dynptr!(Arc<dyn MyTrait2 + stabby::Any + Send>) = dyned.into_super();
     assert_eq!(dyned.stable_downcast_ref::<u8, _>(), Some(&6));
     assert!(dyned.stable_downcast_ref::<u16, _>().is_none());
+    let Err(dyned) = dyned.stable_downcast::<u16, _>() else {
+        panic!("downcasting to the wrong type should fail")
+    };
+    let Ok(arc) = dyned.stable_downcast::<u8, _>() else {
+        panic!("downcasting to the right type should succeed")
+    };
+    assert_eq!(*arc, 6);
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+#[test]
+fn arc_downcast_ref() {
+    use stabby::sync::Arc;
+    type Plugin = stabby::dynptr!(Arc<dyn MyTrait2 + stabby::Any + Send>);
+    let small = Arc::new(6u8);
+    let large = Arc::new(300u16);
+    let plugins: [Plugin; 3] = [
+        small.clone().into(),
+        large.clone().into(),
+        Arc::new(7u8).into(),
+    ];
+    let dispatched = plugins
+        .iter()
+        .map(|plugin| {
+            match (
+                plugin.stable_downcast_ref::<u8, _>(),
+                plugin.stable_downcast_ref::<u16, _>(),
+            ) {
+                (Some(small), None) => u32::from(*small),
+                (None, Some(large)) => u32::from(*large) * 10,
+                _ => panic!("each plugin should downcast to exactly one type"),
+            }
+        })
+        .collect::<std::vec::Vec<_>>();
+    assert_eq!(dispatched, [6, 3000, 7]);
+    assert_eq!(plugins[0].do_stuff2(), 6);
+    // Downcasting by reference doesn't touch the reference counts.
+    assert_eq!(Arc::strong_count(&small), 2);
+    assert_eq!(Arc::strong_count(&large), 2);
+    drop(plugins);
+    assert_eq!(Arc::strong_count(&small), 1);
+}
+
+#[test]
+fn arc_new_dyn() {
+    use stabby::sync::{Arc, DynArc};
+    type Plugin = stabby::dynptr!(Arc<dyn MyTrait2 + stabby::Any + Send>);
+    let plugin: Plugin = Arc::new_dyn(6u8);
+    assert_eq!(plugin.stable_downcast_ref::<u8, _>(), Some(&6));
+    // `DynArc` is the type `dynptr!` expands to.
+    let plugin: DynArc<stabby::vtable!(MyTrait2 + Send)> = plugin.into_super();
+    assert_eq!(plugin.do_stuff2(), 6);
+}
+
+#[test]
+fn value_layout() {
+    use stabby::abi::alloc::Layout;
+    #[stabby::stabby]
+    struct Wide {
+        a: u64,
+        b: u8,
+    }
+    type Value = stabby::dynptr!(Box<dyn stabby::HasLayout + stabby::Any + Send>);
+    let values: [Value; 2] = [Box::new(6u8).into(), Box::new(Wide { a: 1, b: 2 }).into()];
+    assert_eq!(values[0].value_layout(), Layout::of::<u8>());
+    assert_eq!(values[1].value_layout(), Layout::of::<Wide>());
+    assert_eq!(values[1].as_ref().value_layout(), Layout::of::<Wide>());
+    assert_eq!(values[1].stable_downcast_ref::<Wide, _>().unwrap().b, 2);
+
+    let dyned = <stabby::dynptr!(Box<dyn MyTrait2 + stabby::HasLayout>)>::from(Box::new(300u16));
+    assert_eq!(dyned.value_layout(), Layout::of::<u16>());
+    let dyned: stabby::dynptr!(Box<dyn MyTrait2>) = dyned.into_super();
+    assert_eq!(dyned.do_stuff2(), 44);
+}
+
+#[test]
+fn dyn_clone() {
+    use stabby::DynClone;
+    #[derive(Clone)]
+    struct Named(stabby::string::String);
+    impl MyTrait2 for Named {
+        extern "C" fn do_stuff2(&self) -> u8 {
+            self.0.len() as u8
+        }
+    }
+    type Plugin = stabby::dynptr!(Box<dyn MyTrait2 + DynClone + Send>);
+    let plugin = Plugin::from(Box::new(Named("plugin".into())));
+    let clone = plugin.clone();
+    assert_eq!(clone.do_stuff2(), 6);
+    assert_ne!(plugin.as_ref().ptr(), clone.as_ref().ptr());
+    drop(plugin);
+    let plugins = [clone.clone(), Plugin::from(Box::new(7u8))];
+    drop(clone);
+    let plugins = plugins.clone();
+    assert_eq!(plugins[0].do_stuff2(), 6);
+    assert_eq!(plugins[1].do_stuff2(), 7);
+    let plugin: stabby::dynptr!(Box<dyn MyTrait2 + DynClone>) = plugins[0].clone().into_super();
+    let plugin: stabby::dynptr!(Box<dyn MyTrait2>) = plugin.into_super();
+    assert_eq!(plugin.do_stuff2(), 6);
 }
 
 #[cfg(not(miri))]
@@ -211,3 +310,50 @@ fn async_trait() {
     }
     check(Box::new(read_task).into(), Box::new(write_task).into())
 }
+
+#[test]
+fn send_sync_vtables() {
+    // Resolves to `false` unless `T` implements the tested trait, in which case the inherent constant takes precedence.
+    struct Probe<T>(core::marker::PhantomData<T>);
+    trait NotImplemented {
+        const SEND: bool = false;
+        const SYNC: bool = false;
+    }
+    impl<T> NotImplemented for Probe<T> {}
+    impl<T: Send> Probe<T> {
+        const SEND: bool = true;
+    }
+    impl<T: Sync> Probe<T> {
+        const SYNC: bool = true;
+    }
+    macro_rules! send_sync {
+        ($t: ty) => {
+            (Probe::<$t>::SEND, Probe::<$t>::SYNC)
+        };
+    }
+    assert_eq!(
+        send_sync!(stabby::dynptr!(Box<dyn MyTrait2>)),
+        (false, false)
+    );
+    assert_eq!(
+        send_sync!(stabby::dynptr!(Box<dyn MyTrait2 + Send>)),
+        (true, false)
+    );
+    assert_eq!(
+        send_sync!(stabby::dynptr!(Box<dyn Sync + MyTrait2>)),
+        (false, true)
+    );
+    assert_eq!(
+        send_sync!(stabby::dynptr!(Box<dyn Send + MyTrait2 + Sync>)),
+        (true, true)
+    );
+    // Like `&T`, sending a `DynRef` requires the value to be `Sync`.
+    assert_eq!(
+        send_sync!(stabby::DynRef<'static, stabby::vtable!(MyTrait2 + Send)>),
+        (false, false)
+    );
+    assert_eq!(
+        send_sync!(stabby::DynRef<'static, stabby::vtable!(MyTrait2 + Sync)>),
+        (true, true)
+    );
+}